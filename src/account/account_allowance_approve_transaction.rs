@@ -167,6 +167,7 @@ impl AccountAllowanceApproveTransaction {
     }
 }
 
+/// An allowance of hbar that a spender may transfer on behalf of an owner.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct HbarAllowance {
@@ -180,6 +181,7 @@ pub struct HbarAllowance {
     pub amount: Hbar,
 }
 
+/// An allowance of a fungible token that a spender may transfer on behalf of an owner.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct TokenAllowance {
@@ -196,6 +198,7 @@ pub struct TokenAllowance {
     pub amount: u64,
 }
 
+/// An allowance of NFTs that a spender may transfer on behalf of an owner.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct NftAllowance {
@@ -0,0 +1,298 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use crate::{
+    AccountId,
+    Error,
+    Hbar,
+    HbarAllowance,
+    NftAllowance,
+    NftId,
+    TokenAllowance,
+    TokenId,
+    TransferTransaction,
+};
+
+/// The allowances an owner account has granted to spenders.
+///
+/// Besides listing the allowances, this builds approved transfers that spend them,
+/// checking and deducting the remaining allowance of each spend.
+///
+/// An approved transfer must be paid for by its spender, the transaction ID must be generated for the spender's account.
+#[derive(Debug, Clone)]
+pub struct AccountAllowances {
+    /// The account that granted the allowances.
+    pub owner_account_id: AccountId,
+
+    /// The remaining hbar allowances of the owner.
+    pub hbar_allowances: Vec<HbarAllowance>,
+
+    /// The remaining fungible token allowances of the owner.
+    pub token_allowances: Vec<TokenAllowance>,
+
+    /// The NFT allowances of the owner, for specific serials or for all of a token's NFTs.
+    pub nft_allowances: Vec<NftAllowance>,
+}
+
+impl AccountAllowances {
+    /// Create an empty set of allowances for `owner_account_id`.
+    #[must_use]
+    pub fn new(owner_account_id: AccountId) -> Self {
+        Self {
+            owner_account_id,
+            hbar_allowances: Vec::new(),
+            token_allowances: Vec::new(),
+            nft_allowances: Vec::new(),
+        }
+    }
+
+    /// Returns the remaining hbar allowance of `spender`.
+    #[must_use]
+    pub fn hbar_allowance(&self, spender: AccountId) -> Hbar {
+        self.hbar_allowances
+            .iter()
+            .find(|it| it.spender_account_id == spender)
+            .map_or(Hbar::ZERO, |it| it.amount)
+    }
+
+    /// Returns the remaining allowance of `spender` for `token_id`.
+    #[must_use]
+    pub fn token_allowance(&self, token_id: TokenId, spender: AccountId) -> u64 {
+        self.token_allowances
+            .iter()
+            .find(|it| it.token_id == token_id && it.spender_account_id == spender)
+            .map_or(0, |it| it.amount)
+    }
+
+    /// Returns whether `spender` may transfer the NFT `nft_id`.
+    #[must_use]
+    pub fn nft_allowed(&self, nft_id: NftId, spender: AccountId) -> bool {
+        self.nft_allowances.iter().any(|it| {
+            it.token_id == nft_id.token_id
+                && it.spender_account_id == spender
+                && (it.approved_for_all == Some(true)
+                    || it.serials.contains(&(nft_id.serial as i64)))
+        })
+    }
+
+    fn insufficient(
+        &self,
+        spender: AccountId,
+        token_id: Option<TokenId>,
+        requested: u64,
+        remaining: u64,
+    ) -> Error {
+        Error::InsufficientAllowance {
+            owner_account_id: self.owner_account_id,
+            spender_account_id: spender,
+            token_id,
+            requested,
+            remaining,
+        }
+    }
+
+    /// Adds a transfer of `amount` from the owner to `receiver` to `transaction`, spending `spender`'s hbar allowance.
+    ///
+    /// # Errors
+    /// - [`Error::InsufficientAllowance`] if `amount` is more than the remaining allowance, `transaction` is unchanged in this case.
+    pub fn approved_hbar_transfer(
+        &mut self,
+        transaction: &mut TransferTransaction,
+        spender: AccountId,
+        receiver: AccountId,
+        amount: Hbar,
+    ) -> crate::Result<()> {
+        let remaining = self.hbar_allowance(spender);
+
+        if amount > remaining || amount < Hbar::ZERO {
+            return Err(self.insufficient(
+                spender,
+                None,
+                amount.to_tinybars().unsigned_abs(),
+                remaining.to_tinybars().unsigned_abs(),
+            ));
+        }
+
+        transaction
+            .approved_hbar_transfer(self.owner_account_id, -amount)
+            .hbar_transfer(receiver, amount);
+
+        if let Some(allowance) =
+            self.hbar_allowances.iter_mut().find(|it| it.spender_account_id == spender)
+        {
+            allowance.amount = remaining - amount;
+        }
+
+        Ok(())
+    }
+
+    /// Adds a transfer of `amount` of `token_id` from the owner to `receiver` to `transaction`,
+    /// spending `spender`'s allowance for the token.
+    ///
+    /// # Errors
+    /// - [`Error::InsufficientAllowance`] if `amount` is more than the remaining allowance, `transaction` is unchanged in this case.
+    pub fn approved_token_transfer(
+        &mut self,
+        transaction: &mut TransferTransaction,
+        token_id: TokenId,
+        spender: AccountId,
+        receiver: AccountId,
+        amount: u64,
+    ) -> crate::Result<()> {
+        let remaining = self.token_allowance(token_id, spender);
+
+        let Some(signed_amount) = i64::try_from(amount).ok().filter(|_| amount <= remaining) else {
+            return Err(self.insufficient(spender, Some(token_id), amount, remaining));
+        };
+
+        transaction
+            .approved_token_transfer(token_id, self.owner_account_id, -signed_amount)
+            .token_transfer(token_id, receiver, signed_amount);
+
+        if let Some(allowance) = self
+            .token_allowances
+            .iter_mut()
+            .find(|it| it.token_id == token_id && it.spender_account_id == spender)
+        {
+            allowance.amount = remaining - amount;
+        }
+
+        Ok(())
+    }
+
+    /// Adds a transfer of the NFT `nft_id` from the owner to `receiver` to `transaction`, spending `spender`'s allowance.
+    ///
+    /// # Errors
+    /// - [`Error::InsufficientAllowance`] if `spender` isn't allowed to transfer the NFT, `transaction` is unchanged in this case.
+    pub fn approved_nft_transfer(
+        &mut self,
+        transaction: &mut TransferTransaction,
+        nft_id: NftId,
+        spender: AccountId,
+        receiver: AccountId,
+    ) -> crate::Result<()> {
+        if !self.nft_allowed(nft_id, spender) {
+            return Err(self.insufficient(spender, Some(nft_id.token_id), 1, 0));
+        }
+
+        transaction.approved_nft_transfer(nft_id, self.owner_account_id, receiver);
+
+        // an allowance for a specific serial is used up once the NFT is transferred.
+        for allowance in &mut self.nft_allowances {
+            if allowance.token_id == nft_id.token_id {
+                allowance.serials.retain(|it| *it != nft_id.serial as i64);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use crate::{
+        AccountAllowances,
+        AccountId,
+        Error,
+        Hbar,
+        HbarAllowance,
+        NftAllowance,
+        TokenAllowance,
+        TokenId,
+        TransferTransaction,
+    };
+
+    const OWNER: AccountId = AccountId::new(0, 0, 1001);
+    const SPENDER: AccountId = AccountId::new(0, 0, 1002);
+    const RECEIVER: AccountId = AccountId::new(0, 0, 1003);
+    const TOKEN_ID: TokenId = TokenId::new(0, 0, 2000);
+
+    fn make_allowances() -> AccountAllowances {
+        let mut allowances = AccountAllowances::new(OWNER);
+
+        allowances.hbar_allowances.push(HbarAllowance {
+            owner_account_id: OWNER,
+            spender_account_id: SPENDER,
+            amount: Hbar::new(10),
+        });
+
+        allowances.token_allowances.push(TokenAllowance {
+            token_id: TOKEN_ID,
+            owner_account_id: OWNER,
+            spender_account_id: SPENDER,
+            amount: 100,
+        });
+
+        allowances.nft_allowances.push(NftAllowance {
+            token_id: TOKEN_ID,
+            owner_account_id: OWNER,
+            spender_account_id: SPENDER,
+            serials: Vec::from([1]),
+            approved_for_all: None,
+            delegating_spender_account_id: None,
+        });
+
+        allowances
+    }
+
+    #[test]
+    fn hbar_spend_is_approved_and_tracked() {
+        let mut allowances = make_allowances();
+        let mut transaction = TransferTransaction::new();
+
+        allowances
+            .approved_hbar_transfer(&mut transaction, SPENDER, RECEIVER, Hbar::new(4))
+            .unwrap();
+
+        assert_eq!(allowances.hbar_allowance(SPENDER), Hbar::new(6));
+
+        let transfers = transaction.data().transfers();
+
+        assert!(transfers.iter().any(|it| it.account_id == OWNER && it.is_approval));
+        assert!(transfers.iter().any(|it| it.account_id == RECEIVER && !it.is_approval));
+    }
+
+    #[test]
+    fn token_spend_over_allowance() {
+        let mut allowances = make_allowances();
+        let mut transaction = TransferTransaction::new();
+
+        assert_matches!(
+            allowances.approved_token_transfer(&mut transaction, TOKEN_ID, SPENDER, RECEIVER, 101),
+            Err(Error::InsufficientAllowance { requested: 101, remaining: 100, .. })
+        );
+
+        assert!(transaction.data().token_transfers().is_empty());
+    }
+
+    #[test]
+    fn nft_serial_allowance_is_used_up() {
+        let mut allowances = make_allowances();
+        let mut transaction = TransferTransaction::new();
+
+        allowances
+            .approved_nft_transfer(&mut transaction, TOKEN_ID.nft(1), SPENDER, RECEIVER)
+            .unwrap();
+
+        assert!(!allowances.nft_allowed(TOKEN_ID.nft(1), SPENDER));
+    }
+}
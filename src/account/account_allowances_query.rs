@@ -0,0 +1,196 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use crate::{
+    mirror_rest,
+    AccountAllowances,
+    AccountId,
+    Client,
+    Hbar,
+    HbarAllowance,
+    NftAllowance,
+    TokenAllowance,
+    TokenId,
+};
+
+#[derive(serde_derive::Deserialize)]
+struct MirrorHbarAllowance {
+    amount: i64,
+    owner: String,
+    spender: String,
+}
+
+#[derive(serde_derive::Deserialize)]
+struct MirrorTokenAllowance {
+    amount: u64,
+    owner: String,
+    spender: String,
+    token_id: String,
+}
+
+#[derive(serde_derive::Deserialize)]
+struct MirrorNftAllowance {
+    approved_for_all: bool,
+    owner: String,
+    spender: String,
+    token_id: String,
+}
+
+#[derive(serde_derive::Deserialize)]
+struct MirrorNft {
+    token_id: String,
+    serial_number: i64,
+    spender: Option<String>,
+    delegating_spender: Option<String>,
+}
+
+/// Query the mirror node for the allowances an account has granted.
+///
+/// This includes hbar and fungible token allowances (with their remaining amounts),
+/// allowances for all of a token's NFTs, and allowances for specific NFTs.
+#[derive(Debug, Clone, Default)]
+pub struct AccountAllowancesQuery {
+    owner_account_id: Option<AccountId>,
+}
+
+impl AccountAllowancesQuery {
+    /// Create a new `AccountAllowancesQuery`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the account whose allowances are queried.
+    #[must_use]
+    pub fn get_owner_account_id(&self) -> Option<AccountId> {
+        self.owner_account_id
+    }
+
+    /// Sets the account whose allowances are queried.
+    pub fn owner_account_id(&mut self, owner_account_id: AccountId) -> &mut Self {
+        self.owner_account_id = Some(owner_account_id);
+
+        self
+    }
+
+    /// Execute this query against the client's mirror node.
+    ///
+    /// # Errors
+    /// - [`Error::MirrorNodeRest`](crate::Error::MirrorNodeRest) if a request to the mirror node fails.
+    /// - [`Error::BasicParse`](crate::Error::BasicParse) if the mirror node returns a malformed entity ID.
+    ///
+    /// # Panics
+    /// - If the owner account ID isn't set.
+    pub async fn execute(&self, client: &Client) -> crate::Result<AccountAllowances> {
+        let owner =
+            self.owner_account_id.expect("must set an owner to use `AccountAllowancesQuery`");
+
+        let hbar: Vec<MirrorHbarAllowance> = mirror_rest::get_all(
+            client,
+            &format!("/api/v1/accounts/{owner}/allowances/crypto?limit=100"),
+            "allowances",
+        )
+        .await?;
+
+        let tokens: Vec<MirrorTokenAllowance> = mirror_rest::get_all(
+            client,
+            &format!("/api/v1/accounts/{owner}/allowances/tokens?limit=100"),
+            "allowances",
+        )
+        .await?;
+
+        let nfts_for_all: Vec<MirrorNftAllowance> = mirror_rest::get_all(
+            client,
+            &format!("/api/v1/accounts/{owner}/allowances/nfts?owner=true&limit=100"),
+            "allowances",
+        )
+        .await?;
+
+        // allowances for specific NFTs are only reported on the NFTs themselves.
+        let nfts: Vec<MirrorNft> = mirror_rest::get_all(
+            client,
+            &format!("/api/v1/accounts/{owner}/nfts?limit=100"),
+            "nfts",
+        )
+        .await?;
+
+        let mut allowances = AccountAllowances::new(owner);
+
+        for it in hbar {
+            allowances.hbar_allowances.push(HbarAllowance {
+                owner_account_id: it.owner.parse()?,
+                spender_account_id: it.spender.parse()?,
+                amount: Hbar::from_tinybars(it.amount),
+            });
+        }
+
+        for it in tokens {
+            allowances.token_allowances.push(TokenAllowance {
+                token_id: it.token_id.parse()?,
+                owner_account_id: it.owner.parse()?,
+                spender_account_id: it.spender.parse()?,
+                amount: it.amount,
+            });
+        }
+
+        for it in nfts_for_all {
+            allowances.nft_allowances.push(NftAllowance {
+                token_id: it.token_id.parse()?,
+                owner_account_id: it.owner.parse()?,
+                spender_account_id: it.spender.parse()?,
+                serials: Vec::new(),
+                approved_for_all: Some(it.approved_for_all),
+                delegating_spender_account_id: None,
+            });
+        }
+
+        for nft in nfts {
+            let Some(spender) = nft.spender else {
+                continue;
+            };
+
+            let token_id: TokenId = nft.token_id.parse()?;
+            let spender_account_id: AccountId = spender.parse()?;
+            let delegating_spender_account_id =
+                nft.delegating_spender.map(|it| it.parse()).transpose()?;
+
+            let existing = allowances.nft_allowances.iter_mut().find(|it| {
+                it.token_id == token_id
+                    && it.spender_account_id == spender_account_id
+                    && it.approved_for_all.is_none()
+                    && it.delegating_spender_account_id == delegating_spender_account_id
+            });
+
+            match existing {
+                Some(allowance) => allowance.serials.push(nft.serial_number),
+                None => allowances.nft_allowances.push(NftAllowance {
+                    token_id,
+                    owner_account_id: owner,
+                    spender_account_id,
+                    serials: Vec::from([nft.serial_number]),
+                    approved_for_all: None,
+                    delegating_spender_account_id,
+                }),
+            }
+        }
+
+        Ok(allowances)
+    }
+}
@@ -94,16 +94,17 @@ impl AccountId {
     /// Create an `AccountId` from an evm address.
     ///
     /// Accepts "0x___" Ethereum public address.
+    ///
+    /// The account is in shard 0, realm 0, see [`from_evm_address_with_shard_realm`](Self::from_evm_address_with_shard_realm) for other realms.
     #[must_use]
     pub fn from_evm_address(address: &EvmAddress) -> Self {
-        Self {
-            shard: 0,
-            realm: 0,
-            num: 0,
-            alias: None,
-            evm_address: Some(*address),
-            checksum: None,
-        }
+        Self::from_evm_address_with_shard_realm(0, 0, address)
+    }
+
+    /// Create an `AccountId` from an evm address, for an account in `shard.realm`.
+    #[must_use]
+    pub fn from_evm_address_with_shard_realm(shard: u64, realm: u64, address: &EvmAddress) -> Self {
+        Self { shard, realm, num: 0, alias: None, evm_address: Some(*address), checksum: None }
     }
 
     /// Convert `self` to a protobuf-encoded [`Vec<u8>`].
@@ -514,4 +515,14 @@ mod tests {
         expect_test::expect!["0x302a300506032b6570032100114e6abc371b82da"]
             .assert_eq(&id.to_string());
     }
+
+    #[test]
+    fn from_evm_address_with_shard_realm() {
+        let evm_address =
+            EvmAddress::from_str("0x302a300506032b6570032100114e6abc371b82da").unwrap();
+
+        let id = AccountId::from_evm_address_with_shard_realm(1, 2, &evm_address);
+
+        assert_eq!((id.shard, id.realm, id.evm_address), (1, 2, Some(evm_address)));
+    }
 }
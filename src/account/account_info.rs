@@ -35,6 +35,7 @@ use crate::{
     PublicKey,
     StakingInfo,
     Tinybar,
+    TokenRelationship,
 };
 
 /// Response from [`AccountInfoQuery`][crate::AccountInfoQuery].
@@ -112,6 +113,9 @@ pub struct AccountInfo {
 
     /// Staking metadata for this account.
     pub staking: Option<StakingInfo>,
+
+    // the tokens this account is associated with, which networks that no longer report them leave empty.
+    pub(crate) token_relationships: Vec<TokenRelationship>,
 }
 
 impl AccountInfo {
@@ -151,9 +155,10 @@ impl AccountInfo {
             generate_receive_record_threshold: self.receive_record_threshold.to_tinybars() as u64,
             generate_send_record_threshold: self.send_record_threshold.to_tinybars() as u64,
 
+            token_relationships: self.token_relationships.to_protobuf(),
+
             // unimplemented fields
             live_hashes: Vec::default(),
-            token_relationships: Vec::default(),
         }
         .encode_to_vec()
     }
@@ -206,6 +211,7 @@ impl FromProtobuf<services::crypto_get_info_response::AccountInfo> for AccountIn
             receive_record_threshold: Hbar::from_tinybars(
                 pb.generate_receive_record_threshold as i64,
             ),
+            token_relationships: Vec::from_protobuf(pb.token_relationships)?,
         })
     }
 }
@@ -20,6 +20,9 @@
 
 mod account_allowance_approve_transaction;
 mod account_allowance_delete_transaction;
+mod account_allowances;
+#[cfg(feature = "mirror-rest")]
+mod account_allowances_query;
 mod account_balance;
 mod account_balance_query;
 mod account_create_transaction;
@@ -35,10 +38,18 @@ mod account_stakers_query;
 mod account_update_transaction;
 mod proxy_staker;
 
-pub use account_allowance_approve_transaction::AccountAllowanceApproveTransaction;
 pub(crate) use account_allowance_approve_transaction::AccountAllowanceApproveTransactionData;
+pub use account_allowance_approve_transaction::{
+    AccountAllowanceApproveTransaction,
+    HbarAllowance,
+    NftAllowance,
+    TokenAllowance,
+};
 pub use account_allowance_delete_transaction::AccountAllowanceDeleteTransaction;
 pub(crate) use account_allowance_delete_transaction::AccountAllowanceDeleteTransactionData;
+pub use account_allowances::AccountAllowances;
+#[cfg(feature = "mirror-rest")]
+pub use account_allowances_query::AccountAllowancesQuery;
 pub use account_balance::AccountBalance;
 pub use account_balance_query::AccountBalanceQuery;
 pub(crate) use account_balance_query::AccountBalanceQueryData;
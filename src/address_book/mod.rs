@@ -18,10 +18,27 @@
  * ‍
  */
 
+mod node_certificate;
 mod node_create_transaction;
 mod node_delete_transaction;
+mod node_update_diff;
 mod node_update_transaction;
+mod node_validation;
 
-pub use node_create_transaction::NodeCreateTransactionData;
-pub use node_delete_transaction::NodeDeleteTransactionData;
-pub use node_update_transaction::NodeUpdateTransactionData;
+pub use node_certificate::NodeCertificate;
+pub use node_create_transaction::{
+    NodeCreateTransaction,
+    NodeCreateTransactionData,
+};
+pub use node_delete_transaction::{
+    NodeDeleteTransaction,
+    NodeDeleteTransactionData,
+};
+pub use node_update_diff::{
+    NodeFieldChange,
+    NodeUpdateDiff,
+};
+pub use node_update_transaction::{
+    NodeUpdateTransaction,
+    NodeUpdateTransactionData,
+};
@@ -18,12 +18,11 @@
  * ‍
  */
 
-use std::net::Ipv4Addr;
-
 use hedera_proto::services;
 use hedera_proto::services::address_book_service_client::AddressBookServiceClient;
 use tonic::transport::Channel;
 
+use super::node_validation;
 use crate::ledger_id::RefLedgerId;
 use crate::protobuf::FromProtobuf;
 use crate::service_endpoint::ServiceEndpoint;
@@ -186,9 +185,50 @@ impl NodeCreateTransaction {
         self.data_mut().admin_key = Some(key.into());
         self
     }
+
+    /// Checks the details of the new node against the network's rules for them, without submitting anything.
+    ///
+    /// This catches malformed endpoints, certificates and hashes before they cost a transaction fee,
+    /// but the network may still reject the node for other reasons, such as its account not existing.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidNodeDetails`] if the account ID or admin key isn't set.
+    /// - [`Error::InvalidNodeDetails`] if the description is longer than 100 bytes.
+    /// - [`Error::InvalidNodeDetails`] if there are no gossip or service endpoints, or more than the network allows.
+    /// - [`Error::InvalidNodeDetails`] if an endpoint has an invalid port, both or neither of an IP address and domain name,
+    ///   or (for gossip endpoints) a domain name.
+    /// - [`Error::InvalidNodeDetails`] if the gossip CA certificate isn't a DER encoded X.509 certificate.
+    /// - [`Error::InvalidNodeDetails`] if the gRPC certificate hash is set, but isn't a SHA-384 hash.
+    pub fn validate(&self) -> crate::Result<()> {
+        let data = self.data();
+
+        if data.account_id.is_none() {
+            return Err(Error::InvalidNodeDetails("an account ID is required".to_owned()));
+        }
+
+        node_validation::validate_description(&data.description)?;
+        node_validation::validate_gossip_endpoints(&data.gossip_endpoints)?;
+        node_validation::validate_service_endpoints(&data.service_endpoints)?;
+        node_validation::validate_gossip_ca_certificate(&data.gossip_ca_certificate)?;
+
+        if !data.grpc_certificate_hash.is_empty() {
+            node_validation::validate_grpc_certificate_hash(&data.grpc_certificate_hash)?;
+        }
+
+        if data.admin_key.is_none() {
+            return Err(Error::InvalidNodeDetails("an admin key is required".to_owned()));
+        }
+
+        Ok(())
+    }
 }
 
-impl TransactionData for NodeCreateTransactionData {}
+impl TransactionData for NodeCreateTransactionData {
+    fn validate_encoding(&self) -> crate::Result<()> {
+        node_validation::reject_ipv6_endpoints("gossip", &self.gossip_endpoints)?;
+        node_validation::reject_ipv6_endpoints("service", &self.service_endpoints)
+    }
+}
 
 impl TransactionExecute for NodeCreateTransactionData {
     fn execute(
@@ -234,32 +274,8 @@ impl From<NodeCreateTransactionData> for AnyTransactionData {
 
 impl FromProtobuf<services::NodeCreateTransactionBody> for NodeCreateTransactionData {
     fn from_protobuf(pb: services::NodeCreateTransactionBody) -> crate::Result<Self> {
-        let gossip_endpoints = pb
-            .gossip_endpoint
-            .iter()
-            .map(|it| {
-                let ip_addr_v4 = &it.ip_address_v4[..];
-                let ip = Ipv4Addr::new(ip_addr_v4[0], ip_addr_v4[1], ip_addr_v4[2], ip_addr_v4[3]);
-                ServiceEndpoint {
-                    ip_address_v4: Some(ip),
-                    port: it.port,
-                    domain_name: it.domain_name.clone(),
-                }
-            })
-            .collect();
-        let service_endpoints = pb
-            .service_endpoint
-            .iter()
-            .map(|it| {
-                let ip_addr_v4 = &it.ip_address_v4[..];
-                let ip = Ipv4Addr::new(ip_addr_v4[0], ip_addr_v4[1], ip_addr_v4[2], ip_addr_v4[3]);
-                ServiceEndpoint {
-                    ip_address_v4: Some(ip),
-                    port: it.port,
-                    domain_name: it.domain_name.clone(),
-                }
-            })
-            .collect();
+        let gossip_endpoints = Vec::from_protobuf(pb.gossip_endpoint)?;
+        let service_endpoints = Vec::from_protobuf(pb.service_endpoint)?;
 
         Ok(Self {
             account_id: FromProtobuf::from_protobuf(pb.account_id)?,
@@ -296,8 +312,12 @@ impl ToProtobuf for NodeCreateTransactionData {
 
 #[cfg(test)]
 mod tests {
-    use std::net::Ipv4Addr;
+    use std::net::{
+        Ipv4Addr,
+        Ipv6Addr,
+    };
 
+    use assert_matches::assert_matches;
     use expect_test::expect_file;
     use hedera_proto::services;
 
@@ -316,6 +336,7 @@ mod tests {
     };
     use crate::{
         AnyTransaction,
+        Error,
         Key,
     };
 
@@ -324,11 +345,7 @@ mod tests {
     const TEST_GRPC_CERTIFICATE_HASH: &[u8] = &[5, 6, 7, 8];
 
     fn make_ip_address_list() -> Vec<ServiceEndpoint> {
-        vec![ServiceEndpoint {
-            ip_address_v4: Some(Ipv4Addr::new(127, 0, 0, 1)),
-            port: 1234,
-            domain_name: "".to_owned(),
-        }]
+        vec![ServiceEndpoint::new(Ipv4Addr::new(127, 0, 0, 1), 1234)]
     }
 
     fn make_transaction() -> NodeCreateTransaction {
@@ -486,4 +503,15 @@ mod tests {
     fn get_set_admin_key_frozen_panic() {
         make_transaction().admin_key(Key::from(unused_private_key().public_key()));
     }
+
+    #[test]
+    fn freeze_ipv6_endpoint() {
+        let mut tx = NodeCreateTransaction::new_for_tests();
+
+        tx.account_id(TEST_ACCOUNT_ID)
+            .gossip_endpoints(make_ip_address_list())
+            .service_endpoints([ServiceEndpoint::new(Ipv6Addr::LOCALHOST, 50211)]);
+
+        assert_matches!(tx.freeze(), Err(Error::InvalidNodeDetails(_)));
+    }
 }
@@ -0,0 +1,80 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::fmt;
+
+/// The changes a [`NodeUpdateTransaction`](crate::NodeUpdateTransaction) would make to a node,
+/// compared to the node's entry in the address book.
+///
+/// See [`NodeUpdateTransaction::diff`](crate::NodeUpdateTransaction::diff).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeUpdateDiff {
+    /// The ID of the node that would be updated.
+    pub node_id: u64,
+
+    /// Each field that would change.
+    pub changes: Vec<NodeFieldChange>,
+}
+
+impl NodeUpdateDiff {
+    /// Returns `true` if the update wouldn't change anything known from the address book.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for NodeUpdateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return write!(f, "node {}: no changes", self.node_id);
+        }
+
+        write!(f, "node {}:", self.node_id)?;
+
+        for change in &self.changes {
+            write!(f, "\n  {change}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A field of a node that would be changed by an update.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeFieldChange {
+    /// The name of the field, as it's named on [`NodeUpdateTransaction`](crate::NodeUpdateTransaction).
+    pub field: &'static str,
+
+    /// The current value, or `None` if the address book doesn't include this field.
+    pub current: Option<String>,
+
+    /// The value it would be changed to.
+    pub proposed: String,
+}
+
+impl fmt::Display for NodeFieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.current {
+            Some(current) => write!(f, "{}: {current} -> {}", self.field, self.proposed),
+            None => write!(f, "{}: (not in address book) -> {}", self.field, self.proposed),
+        }
+    }
+}
@@ -18,12 +18,11 @@
  * ‍
  */
 
-use std::net::Ipv4Addr;
-
 use hedera_proto::services;
 use hedera_proto::services::address_book_service_client::AddressBookServiceClient;
 use tonic::transport::Channel;
 
+use super::node_validation;
 use crate::ledger_id::RefLedgerId;
 use crate::protobuf::FromProtobuf;
 use crate::service_endpoint::ServiceEndpoint;
@@ -40,6 +39,9 @@ use crate::{
     BoxGrpcFuture,
     Error,
     Key,
+    NodeAddress,
+    NodeFieldChange,
+    NodeUpdateDiff,
     ToProtobuf,
     Transaction,
     ValidateChecksums,
@@ -212,9 +214,119 @@ impl NodeUpdateTransaction {
         self.data_mut().admin_key = Some(key.into());
         self
     }
+
+    /// Checks the details that are set against the network's rules for them, without submitting anything.
+    ///
+    /// See [`NodeCreateTransaction::validate`](crate::NodeCreateTransaction::validate) for the rules.
+    /// Fields that aren't set are left unchanged by the update, so they aren't checked.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidNodeDetails`] if any of the details that are set are invalid.
+    pub fn validate(&self) -> crate::Result<()> {
+        let data = self.data();
+
+        if let Some(description) = &data.description {
+            node_validation::validate_description(description)?;
+        }
+
+        if !data.gossip_endpoints.is_empty() {
+            node_validation::validate_gossip_endpoints(&data.gossip_endpoints)?;
+        }
+
+        if !data.service_endpoints.is_empty() {
+            node_validation::validate_service_endpoints(&data.service_endpoints)?;
+        }
+
+        if let Some(certificate) = &data.gossip_ca_certificate {
+            node_validation::validate_gossip_ca_certificate(certificate)?;
+        }
+
+        if let Some(hash) = &data.grpc_certificate_hash {
+            node_validation::validate_grpc_certificate_hash(hash)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the changes this update would make to `current`, the node's entry in the address book,
+    /// as a dry run before submitting it.
+    ///
+    /// The address book doesn't include a node's gossip endpoints, gossip CA certificate or admin key,
+    /// so those are listed as changes whenever they're set.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidNodeDetails`] if `current` is the entry of a different node.
+    pub fn diff(&self, current: &NodeAddress) -> crate::Result<NodeUpdateDiff> {
+        let data = self.data();
+
+        if current.node_id != data.node_id {
+            return Err(Error::InvalidNodeDetails(format!(
+                "the update is for node {}, but the address book entry is for node {}",
+                data.node_id, current.node_id
+            )));
+        }
+
+        let mut changes = Vec::new();
+
+        let mut change = |field, current: Option<String>, proposed: String| {
+            if current.as_ref() != Some(&proposed) {
+                changes.push(NodeFieldChange { field, current, proposed });
+            }
+        };
+
+        if let Some(account_id) = data.account_id {
+            change("account_id", Some(current.node_account_id.to_string()), account_id.to_string());
+        }
+
+        if let Some(description) = &data.description {
+            change("description", Some(current.description.clone()), description.clone());
+        }
+
+        if !data.gossip_endpoints.is_empty() {
+            change("gossip_endpoints", None, format_endpoints(&data.gossip_endpoints));
+        }
+
+        if !data.service_endpoints.is_empty() {
+            change(
+                "service_endpoints",
+                Some(format_endpoints(&current.service_endpoints)),
+                format_endpoints(&data.service_endpoints),
+            );
+        }
+
+        if let Some(certificate) = &data.gossip_ca_certificate {
+            change("gossip_ca_certificate", None, hex::encode(certificate));
+        }
+
+        if let Some(hash) = &data.grpc_certificate_hash {
+            // the address book has the hash as hex text.
+            change(
+                "grpc_certificate_hash",
+                Some(String::from_utf8_lossy(&current.tls_certificate_hash).into_owned()),
+                hex::encode(hash),
+            );
+        }
+
+        if let Some(admin_key) = &data.admin_key {
+            change("admin_key", None, format!("{admin_key:?}"));
+        }
+
+        Ok(NodeUpdateDiff { node_id: data.node_id, changes })
+    }
 }
 
-impl TransactionData for NodeUpdateTransactionData {}
+fn format_endpoints(endpoints: &[ServiceEndpoint]) -> String {
+    let endpoints: Vec<_> = endpoints.iter().map(ToString::to_string).collect();
+
+    endpoints.join(", ")
+}
+
+impl TransactionData for NodeUpdateTransactionData {
+    fn validate_encoding(&self) -> crate::Result<()> {
+        node_validation::reject_ipv6_endpoints("gossip", &self.gossip_endpoints)?;
+        node_validation::reject_ipv6_endpoints("service", &self.service_endpoints)
+    }
+}
 
 impl TransactionExecute for NodeUpdateTransactionData {
     fn execute(
@@ -260,32 +372,8 @@ impl From<NodeUpdateTransactionData> for AnyTransactionData {
 
 impl FromProtobuf<services::NodeUpdateTransactionBody> for NodeUpdateTransactionData {
     fn from_protobuf(pb: services::NodeUpdateTransactionBody) -> crate::Result<Self> {
-        let gossip_endpoints = pb
-            .gossip_endpoint
-            .iter()
-            .map(|it| {
-                let ip_addr_v4 = &it.ip_address_v4[..];
-                let ip = Ipv4Addr::new(ip_addr_v4[0], ip_addr_v4[1], ip_addr_v4[2], ip_addr_v4[3]);
-                ServiceEndpoint {
-                    ip_address_v4: Some(ip),
-                    port: it.port,
-                    domain_name: it.domain_name.clone(),
-                }
-            })
-            .collect();
-        let service_endpoints = pb
-            .service_endpoint
-            .iter()
-            .map(|it| {
-                let ip_addr_v4 = &it.ip_address_v4[..];
-                let ip = Ipv4Addr::new(ip_addr_v4[0], ip_addr_v4[1], ip_addr_v4[2], ip_addr_v4[3]);
-                ServiceEndpoint {
-                    ip_address_v4: Some(ip),
-                    port: it.port,
-                    domain_name: it.domain_name.clone(),
-                }
-            })
-            .collect();
+        let gossip_endpoints = Vec::from_protobuf(pb.gossip_endpoint)?;
+        let service_endpoints = Vec::from_protobuf(pb.service_endpoint)?;
 
         Ok(Self {
             node_id: pb.node_id,
@@ -343,8 +431,11 @@ mod tests {
         TEST_ACCOUNT_ID,
     };
     use crate::{
+        AccountId,
         AnyTransaction,
+        Error,
         Key,
+        NodeAddress,
     };
 
     const TEST_DESCRIPTION: &str = "test description";
@@ -353,16 +444,8 @@ mod tests {
 
     fn make_ip_address_list() -> Vec<ServiceEndpoint> {
         vec![
-            ServiceEndpoint {
-                ip_address_v4: Some(Ipv4Addr::new(127, 0, 0, 1)),
-                port: 1234,
-                domain_name: "".to_owned(),
-            },
-            ServiceEndpoint {
-                ip_address_v4: Some(Ipv4Addr::new(127, 0, 0, 1)),
-                port: 8008,
-                domain_name: "".to_owned(),
-            },
+            ServiceEndpoint::new(Ipv4Addr::new(127, 0, 0, 1), 1234),
+            ServiceEndpoint::new(Ipv4Addr::new(127, 0, 0, 1), 8008),
         ]
     }
 
@@ -536,4 +619,46 @@ mod tests {
     fn get_set_admin_key_frozen_panic() {
         make_transaction().admin_key(Key::from(unused_private_key().public_key()));
     }
+
+    #[test]
+    fn validate() {
+        let mut tx = NodeUpdateTransaction::new();
+
+        // nothing is set, so nothing changes.
+        tx.validate().unwrap();
+
+        tx.description(TEST_DESCRIPTION).service_endpoints(make_ip_address_list());
+        tx.validate().unwrap();
+
+        tx.grpc_certificate_hash(TEST_GRPC_CERTIFICATE_HASH);
+        assert!(matches!(tx.validate(), Err(Error::InvalidNodeDetails(_))));
+    }
+
+    #[test]
+    fn diff() {
+        let current = NodeAddress {
+            node_id: 1,
+            rsa_public_key: Vec::new(),
+            node_account_id: AccountId::new(0, 0, 4),
+            tls_certificate_hash: Vec::new(),
+            service_endpoints: vec!["127.0.0.1:1234".parse().unwrap()],
+            description: TEST_DESCRIPTION.to_owned(),
+        };
+
+        let mut tx = NodeUpdateTransaction::new();
+        tx.node_id(1)
+            .description(TEST_DESCRIPTION)
+            .account_id(AccountId::new(0, 0, 5))
+            .service_endpoints(make_ip_address_list());
+
+        let diff = tx.diff(&current).unwrap();
+
+        assert_eq!(
+            diff.to_string(),
+            "node 1:\n  account_id: 0.0.4 -> 0.0.5\n  service_endpoints: 127.0.0.1:1234 -> 127.0.0.1:1234, 127.0.0.1:8008"
+        );
+
+        tx.node_id(2);
+        assert!(tx.diff(&current).is_err());
+    }
 }
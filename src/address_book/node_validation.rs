@@ -0,0 +1,232 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::net::IpAddr;
+
+use crate::address_book::NodeCertificate;
+use crate::service_endpoint::{
+    validate_domain_name,
+    EndpointAddress,
+    ServiceEndpoint,
+};
+use crate::Error;
+
+/// The most gossip endpoints a node can have.
+const MAX_GOSSIP_ENDPOINTS: usize = 10;
+
+/// The most gRPC service endpoints a node can have.
+const MAX_SERVICE_ENDPOINTS: usize = 8;
+
+/// The most bytes a node's description can have.
+const MAX_DESCRIPTION_LEN: usize = 100;
+
+/// The length of a SHA-384 hash.
+const CERTIFICATE_HASH_LEN: usize = 48;
+
+fn invalid(reason: impl Into<String>) -> Error {
+    Error::InvalidNodeDetails(reason.into())
+}
+
+pub(super) fn validate_description(description: &str) -> crate::Result<()> {
+    if description.len() > MAX_DESCRIPTION_LEN {
+        return Err(invalid(format!(
+            "description is {} bytes, but can be at most {MAX_DESCRIPTION_LEN}",
+            description.len()
+        )));
+    }
+
+    if description.contains('\0') {
+        return Err(invalid("description must not contain NUL characters"));
+    }
+
+    Ok(())
+}
+
+/// Gossip endpoints must be IP addresses, the network doesn't resolve domain names for gossip.
+pub(super) fn validate_gossip_endpoints(endpoints: &[ServiceEndpoint]) -> crate::Result<()> {
+    validate_endpoints("gossip", endpoints, MAX_GOSSIP_ENDPOINTS)?;
+
+    if let Some(domain_name) = endpoints.iter().find_map(ServiceEndpoint::domain_name) {
+        return Err(invalid(format!(
+            "gossip endpoint `{domain_name}` must be an IP address, not a domain name"
+        )));
+    }
+
+    Ok(())
+}
+
+pub(super) fn validate_service_endpoints(endpoints: &[ServiceEndpoint]) -> crate::Result<()> {
+    validate_endpoints("service", endpoints, MAX_SERVICE_ENDPOINTS)
+}
+
+fn validate_endpoints(kind: &str, endpoints: &[ServiceEndpoint], max: usize) -> crate::Result<()> {
+    if endpoints.is_empty() {
+        return Err(invalid(format!("at least one {kind} endpoint is required")));
+    }
+
+    if endpoints.len() > max {
+        return Err(invalid(format!(
+            "{} {kind} endpoints were given, but a node can have at most {max}",
+            endpoints.len()
+        )));
+    }
+
+    reject_ipv6_endpoints(kind, endpoints)?;
+
+    for endpoint in endpoints {
+        validate_endpoint(kind, endpoint)?;
+    }
+
+    Ok(())
+}
+
+/// The network only stores 4 byte addresses for nodes, so an IPv6 endpoint can't be sent to it.
+pub(super) fn reject_ipv6_endpoints(
+    kind: &str,
+    endpoints: &[ServiceEndpoint],
+) -> crate::Result<()> {
+    match endpoints.iter().find(|it| matches!(it.ip(), Some(IpAddr::V6(_)))) {
+        Some(endpoint) => Err(invalid(format!(
+            "{kind} endpoint `{endpoint}` is an IPv6 address, but the network only accepts IPv4 addresses"
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn validate_endpoint(kind: &str, endpoint: &ServiceEndpoint) -> crate::Result<()> {
+    if endpoint.port == 0 {
+        return Err(invalid(format!("{kind} endpoint `{endpoint}` must have a non-zero port")));
+    }
+
+    match &endpoint.address {
+        EndpointAddress::Ip(_) => Ok(()),
+        EndpointAddress::IpAndDomainName { .. } => Err(invalid(format!(
+            "{kind} endpoint `{endpoint}` must have either an IP address or a domain name, not both"
+        ))),
+        EndpointAddress::DomainName(domain_name) => {
+            validate_domain_name(domain_name).map_err(|reason| {
+                invalid(format!("{kind} endpoint domain name `{domain_name}` {reason}"))
+            })
+        }
+    }
+}
+
+/// The gossip CA certificate must be a DER encoded X.509 certificate.
+pub(super) fn validate_gossip_ca_certificate(certificate: &[u8]) -> crate::Result<()> {
+    if certificate.starts_with(b"-----BEGIN") {
+        return Err(invalid(
+            "gossip CA certificate must be DER encoded, use `NodeCertificate::from_pem(..).to_der()`",
+        ));
+    }
+
+    NodeCertificate::from_der(certificate)
+        .map(drop)
+        .map_err(|e| invalid(format!("gossip CA certificate is malformed: {e}")))
+}
+
+/// The gRPC certificate hash must be a SHA-384 hash, see [`NodeCertificate::grpc_certificate_hash`].
+pub(super) fn validate_grpc_certificate_hash(hash: &[u8]) -> crate::Result<()> {
+    if hash.len() != CERTIFICATE_HASH_LEN {
+        return Err(invalid(format!(
+            "gRPC certificate hash is {} bytes, but a SHA-384 hash is {CERTIFICATE_HASH_LEN}",
+            hash.len()
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{
+        Ipv4Addr,
+        Ipv6Addr,
+    };
+
+    use super::{
+        validate_description,
+        validate_gossip_ca_certificate,
+        validate_gossip_endpoints,
+        validate_grpc_certificate_hash,
+        validate_service_endpoints,
+    };
+    use crate::service_endpoint::{
+        EndpointAddress,
+        ServiceEndpoint,
+    };
+
+    fn ip(port: u16) -> ServiceEndpoint {
+        ServiceEndpoint::new(Ipv4Addr::new(10, 0, 0, 1), port)
+    }
+
+    fn domain(domain_name: &str) -> ServiceEndpoint {
+        ServiceEndpoint::new(EndpointAddress::DomainName(domain_name.to_owned()), 50211)
+    }
+
+    #[test]
+    fn service_endpoints() {
+        assert!(validate_service_endpoints(&[ip(50211), domain("node0.example.com")]).is_ok());
+        assert!(validate_service_endpoints(&[domain("node0.example.com.")]).is_ok());
+
+        assert!(validate_service_endpoints(&[]).is_err());
+        assert!(validate_service_endpoints(&vec![ip(50211); 9]).is_err());
+        assert!(validate_service_endpoints(&[ip(0)]).is_err());
+        assert!(validate_service_endpoints(&[ServiceEndpoint::new(Ipv6Addr::LOCALHOST, 50211)])
+            .is_err());
+        assert!(validate_service_endpoints(&[domain("localhost")]).is_err());
+        assert!(validate_service_endpoints(&[domain("-node.example.com")]).is_err());
+        assert!(validate_service_endpoints(&[domain("node_0.example.com")]).is_err());
+        assert!(validate_service_endpoints(&[domain("10.0.0.1")]).is_err());
+        assert!(validate_service_endpoints(&[domain("")]).is_err());
+        assert!(validate_service_endpoints(&[domain("node0.example.com..")]).is_err());
+
+        let both = EndpointAddress::IpAndDomainName {
+            ip: Ipv4Addr::new(10, 0, 0, 1).into(),
+            domain_name: "node0.example.com".to_owned(),
+        };
+
+        assert!(validate_service_endpoints(&[ServiceEndpoint::new(both, 50211)]).is_err());
+    }
+
+    #[test]
+    fn gossip_endpoints() {
+        assert!(validate_gossip_endpoints(&[ip(50111)]).is_ok());
+
+        assert!(validate_gossip_endpoints(&[domain("node0.example.com")]).is_err());
+        assert!(validate_gossip_endpoints(&vec![ip(50111); 11]).is_err());
+    }
+
+    #[test]
+    fn description() {
+        assert!(validate_description("node 0").is_ok());
+
+        assert!(validate_description(&"a".repeat(101)).is_err());
+        assert!(validate_description("node\00").is_err());
+    }
+
+    #[test]
+    fn certificates() {
+        assert!(validate_grpc_certificate_hash(&[0; 48]).is_ok());
+        assert!(validate_grpc_certificate_hash(&[0; 32]).is_err());
+
+        assert!(validate_gossip_ca_certificate(&[1, 2, 3, 4]).is_err());
+        assert!(validate_gossip_ca_certificate(b"-----BEGIN CERTIFICATE-----").is_err());
+    }
+}
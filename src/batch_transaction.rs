@@ -0,0 +1,330 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use hedera_proto::services;
+use hedera_proto::services::util_service_client::UtilServiceClient;
+
+use crate::entity_id::ValidateChecksums;
+use crate::protobuf::{
+    FromProtobuf,
+    ToProtobuf,
+};
+use crate::transaction::{
+    AnyTransactionData,
+    ChunkInfo,
+    ToTransactionDataProtobuf,
+    TransactionData,
+    TransactionExecute,
+};
+use crate::{
+    AnyTransaction,
+    Transaction,
+    TransactionId,
+};
+
+/// Execute multiple transactions atomically, either all of them succeed or none of them do.
+///
+/// Every inner transaction must be frozen and have a batch key set, see [`Transaction::batchify`].
+/// The batch transaction itself must be signed by all of the inner transactions' batch keys.
+///
+/// The inner transactions get their own receipts and records,
+/// see [`TransactionResponse::get_inner_receipts`](crate::TransactionResponse::get_inner_receipts).
+pub type BatchTransaction = Transaction<BatchTransactionData>;
+
+#[derive(Debug, Clone, Default)]
+pub struct BatchTransactionData {
+    inner_transactions: Vec<AnyTransaction>,
+}
+
+impl BatchTransaction {
+    /// Returns the transactions that will be executed as part of this batch.
+    #[must_use]
+    pub fn get_inner_transactions(&self) -> &[AnyTransaction] {
+        &self.data().inner_transactions
+    }
+
+    /// Sets the transactions that will be executed as part of this batch.
+    ///
+    /// # Errors
+    /// - [`Error::NoPayerAccountOrTransactionId`](crate::Error::NoPayerAccountOrTransactionId)
+    ///   if an inner transaction was frozen without an operator and has no transaction ID.
+    ///
+    /// # Panics
+    /// - If any of the transactions can't be added with [`add_inner_transaction`](Self::add_inner_transaction).
+    pub fn inner_transactions<T>(
+        &mut self,
+        transactions: impl IntoIterator<Item = T>,
+    ) -> crate::Result<&mut Self>
+    where
+        T: Into<AnyTransaction>,
+    {
+        self.require_not_frozen();
+
+        let transactions: crate::Result<Vec<_>> =
+            transactions.into_iter().map(|it| prepare_inner_transaction(it.into())).collect();
+
+        self.data_mut().inner_transactions = transactions?;
+
+        Ok(self)
+    }
+
+    /// Adds a transaction to be executed as part of this batch.
+    ///
+    /// This fixes the transaction's ID and signatures, since the batch carries the signed transaction as-is.
+    ///
+    /// # Errors
+    /// - [`Error::NoPayerAccountOrTransactionId`](crate::Error::NoPayerAccountOrTransactionId)
+    ///   if the transaction was frozen without an operator and has no transaction ID.
+    ///
+    /// # Panics
+    /// - If `self` is frozen.
+    /// - If `transaction` isn't frozen or has no batch key.
+    /// - If `transaction` is a `BatchTransaction` or a `FreezeTransaction`, neither of which can be batched.
+    pub fn add_inner_transaction(
+        &mut self,
+        transaction: impl Into<AnyTransaction>,
+    ) -> crate::Result<&mut Self> {
+        self.require_not_frozen();
+
+        let transaction = prepare_inner_transaction(transaction.into())?;
+
+        self.data_mut().inner_transactions.push(transaction);
+
+        Ok(self)
+    }
+
+    /// Returns the transaction IDs of the inner transactions, in the order they will be executed.
+    #[must_use]
+    pub fn get_inner_transaction_ids(&self) -> Vec<TransactionId> {
+        self.data().inner_transaction_ids()
+    }
+}
+
+fn prepare_inner_transaction(mut transaction: AnyTransaction) -> crate::Result<AnyTransaction> {
+    assert!(
+        transaction.is_frozen(),
+        "inner transactions must be frozen before being added to a batch, see `Transaction::batchify`"
+    );
+
+    assert!(
+        transaction.get_batch_key().is_some(),
+        "inner transactions must have a batch key, see `Transaction::batchify`"
+    );
+
+    match transaction.data() {
+        AnyTransactionData::Batch(_) => panic!("Cannot add a `BatchTransaction` to a batch"),
+        AnyTransactionData::Freeze(_) => panic!("Cannot add a `FreezeTransaction` to a batch"),
+        _ => {}
+    }
+
+    transaction.store_sources()?;
+
+    Ok(transaction)
+}
+
+impl FromProtobuf<services::AtomicBatchTransactionBody> for BatchTransactionData {
+    fn from_protobuf(pb: services::AtomicBatchTransactionBody) -> crate::Result<Self> {
+        let inner_transactions = pb
+            .transactions
+            .into_iter()
+            .map(|signed_transaction_bytes| {
+                AnyTransaction::from_transactions(Vec::from([services::Transaction {
+                    signed_transaction_bytes,
+                    ..services::Transaction::default()
+                }]))
+            })
+            .collect::<crate::Result<_>>()?;
+
+        Ok(Self { inner_transactions })
+    }
+}
+
+impl ToProtobuf for BatchTransactionData {
+    type Protobuf = services::AtomicBatchTransactionBody;
+
+    fn to_protobuf(&self) -> Self::Protobuf {
+        let transactions = self
+            .inner_transactions
+            .iter()
+            .map(|it| {
+                // inner transactions always have their sources stored, so this can't fail.
+                let sources = it.make_sources().expect("BUG: inner transaction had no sources");
+
+                sources.transactions()[0].signed_transaction_bytes.clone()
+            })
+            .collect();
+
+        services::AtomicBatchTransactionBody { transactions }
+    }
+}
+
+impl TransactionData for BatchTransactionData {
+    fn inner_transaction_ids(&self) -> Vec<TransactionId> {
+        self.inner_transactions
+            .iter()
+            .map(|it| it.stored_transaction_id().expect("BUG: inner transaction had no sources"))
+            .collect()
+    }
+}
+
+impl From<BatchTransactionData> for AnyTransactionData {
+    fn from(value: BatchTransactionData) -> Self {
+        Self::Batch(value)
+    }
+}
+
+impl ValidateChecksums for BatchTransactionData {
+    fn validate_checksums(&self, ledger_id: &crate::ledger_id::RefLedgerId) -> crate::Result<()> {
+        for transaction in &self.inner_transactions {
+            transaction.validate_checksums(ledger_id)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToTransactionDataProtobuf for BatchTransactionData {
+    fn to_transaction_data_protobuf(
+        &self,
+        chunk_info: &ChunkInfo,
+    ) -> services::transaction_body::Data {
+        let _ = chunk_info.assert_single_transaction();
+
+        services::transaction_body::Data::AtomicBatch(self.to_protobuf())
+    }
+}
+
+impl TransactionExecute for BatchTransactionData {
+    fn execute(
+        &self,
+        channel: tonic::transport::Channel,
+        request: services::Transaction,
+    ) -> crate::BoxGrpcFuture<'_, services::TransactionResponse> {
+        Box::pin(async { UtilServiceClient::new(channel).atomic_batch(request).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transaction::test_helpers::{
+        check_body,
+        transaction_body,
+        unused_private_key,
+        TEST_TX_ID,
+    };
+    use crate::{
+        AccountId,
+        AnyTransaction,
+        BatchTransaction,
+        PrngTransaction,
+        TransactionId,
+    };
+
+    fn make_inner(transaction_id: TransactionId) -> PrngTransaction {
+        let mut tx = PrngTransaction::new_for_tests();
+
+        tx.range(100)
+            .transaction_id(transaction_id)
+            .batch_key(unused_private_key().public_key())
+            .node_account_ids([AccountId::new(0, 0, 0)])
+            .freeze()
+            .unwrap();
+
+        tx
+    }
+
+    fn inner_transaction_ids() -> [TransactionId; 2] {
+        [TEST_TX_ID, TransactionId { nonce: Some(1), ..TEST_TX_ID }]
+    }
+
+    fn make_transaction() -> BatchTransaction {
+        let mut tx = BatchTransaction::new_for_tests();
+
+        tx.inner_transactions(inner_transaction_ids().map(make_inner)).unwrap().freeze().unwrap();
+
+        tx
+    }
+
+    #[test]
+    fn serialize() {
+        let tx = make_transaction();
+
+        let tx = transaction_body(tx);
+
+        let tx = check_body(tx);
+
+        let hedera_proto::services::transaction_body::Data::AtomicBatch(body) = tx else {
+            panic!("expected an atomic batch body, got {tx:?}")
+        };
+
+        assert_eq!(body.transactions.len(), 2);
+    }
+
+    #[test]
+    fn to_from_bytes() {
+        let tx = make_transaction();
+
+        let tx2 = AnyTransaction::from_bytes(&tx.to_bytes().unwrap()).unwrap();
+
+        let tx = transaction_body(tx);
+
+        let tx2 = transaction_body(tx2);
+
+        assert_eq!(tx, tx2);
+    }
+
+    #[test]
+    fn inner_transaction_ids_roundtrip() {
+        let tx = make_transaction();
+
+        assert_eq!(tx.get_inner_transaction_ids(), inner_transaction_ids());
+
+        let tx2 = AnyTransaction::from_bytes(&tx.to_bytes().unwrap())
+            .unwrap()
+            .downcast::<BatchTransaction>()
+            .unwrap();
+
+        assert_eq!(tx2.get_inner_transaction_ids(), inner_transaction_ids());
+
+        let inner = tx2.get_inner_transactions()[0].clone().downcast::<PrngTransaction>().unwrap();
+
+        assert_eq!(inner.get_range(), Some(100));
+        assert_eq!(inner.get_batch_key(), Some(&unused_private_key().public_key().into()));
+        assert_eq!(inner.get_node_account_ids(), Some(&[AccountId::new(0, 0, 0)][..]));
+    }
+
+    #[test]
+    #[should_panic(expected = "inner transactions must be frozen")]
+    fn add_unfrozen_inner_transaction() {
+        let mut inner = PrngTransaction::new_for_tests();
+        inner.batch_key(unused_private_key().public_key());
+
+        BatchTransaction::new().add_inner_transaction(inner).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "inner transactions must have a batch key")]
+    fn add_inner_transaction_without_batch_key() {
+        let mut inner = PrngTransaction::new_for_tests();
+        inner.freeze().unwrap();
+
+        BatchTransaction::new().add_inner_transaction(inner).unwrap();
+    }
+}
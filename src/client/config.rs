@@ -19,15 +19,21 @@
  */
 
 use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 use crate::signer::AnySigner;
 use crate::{
     AccountId,
+    Error,
+    Hbar,
+    LedgerId,
     PrivateKey,
 };
 
-struct FromStrProxy<T>(T);
+pub(super) struct FromStrProxy<T>(pub(super) T);
 
 impl<'de, T: FromStr> serde::Deserialize<'de> for FromStrProxy<T>
 where
@@ -44,26 +50,81 @@ where
     }
 }
 
-#[derive(serde_derive::Deserialize)]
+impl<T: fmt::Display> serde::Serialize for FromStrProxy<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(&self.0)
+    }
+}
+
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
 pub(super) struct Operator {
-    account_id: FromStrProxy<AccountId>,
-    private_key: FromStrProxy<PrivateKey>,
+    pub(super) account_id: FromStrProxy<AccountId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) private_key: Option<FromStrProxy<PrivateKey>>,
+    /// Path to a PEM encoded private key, as an alternative to `private_key`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) private_key_file: Option<PathBuf>,
+    /// Password for an encrypted `private_key_file`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) private_key_password: Option<String>,
 }
 
-impl From<Operator> for super::Operator {
-    fn from(value: Operator) -> Self {
-        Self { account_id: value.account_id.0, signer: AnySigner::PrivateKey(value.private_key.0) }
+impl TryFrom<Operator> for super::Operator {
+    type Error = Error;
+
+    fn try_from(value: Operator) -> crate::Result<Self> {
+        let Operator { account_id, private_key, private_key_file, private_key_password } = value;
+
+        let private_key = match (private_key, private_key_file) {
+            (Some(private_key), None) => {
+                if private_key_password.is_some() {
+                    return Err(Error::basic_parse(
+                        "operator `private_key_password` is only valid with a `private_key_file`",
+                    ));
+                }
+
+                private_key.0
+            }
+            (None, Some(path)) => {
+                let pem = std::fs::read(&path).map_err(|e| {
+                    Error::basic_parse(format!(
+                        "failed to read operator key file `{}`: {e}",
+                        path.display()
+                    ))
+                })?;
+
+                match private_key_password {
+                    Some(password) => PrivateKey::from_pem_with_password(pem, password)?,
+                    None => PrivateKey::from_pem(pem)?,
+                }
+            }
+            (Some(_), Some(_)) => {
+                return Err(Error::basic_parse(
+                    "operator must have only one of `private_key` and `private_key_file`",
+                ))
+            }
+            (None, None) => {
+                return Err(Error::basic_parse(
+                    "operator must have one of `private_key` and `private_key_file`",
+                ))
+            }
+        };
+
+        Ok(Self { account_id: account_id.0, signer: AnySigner::PrivateKey(private_key) })
     }
 }
 
-#[derive(serde_derive::Deserialize)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
 #[serde(untagged)]
 pub(super) enum Either<L, R> {
     Left(L),
     Right(R),
 }
 
-#[derive(serde_derive::Deserialize)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) enum NetworkName {
     Mainnet,
@@ -71,24 +132,85 @@ pub(crate) enum NetworkName {
     Previewnet,
 }
 
-#[derive(serde_derive::Deserialize)]
+/// The on-disk representation of a client config.
+///
+/// All durations are in milliseconds, and a `networkUpdatePeriod` of `0` disables network updates.
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(super) struct ClientConfigInner {
-    operator: Option<Operator>,
-    network: Either<HashMap<String, FromStrProxy<AccountId>>, NetworkName>,
-    mirror_network: Option<Either<Vec<String>, NetworkName>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) operator: Option<Operator>,
+    pub(super) network: Either<HashMap<String, FromStrProxy<AccountId>>, NetworkName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) mirror_network: Option<Either<Vec<String>, NetworkName>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) max_backoff: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) min_backoff: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) max_attempts: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) request_timeout: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) grpc_timeout: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) default_max_transaction_fee: Option<FromStrProxy<Hbar>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) default_max_query_payment: Option<FromStrProxy<Hbar>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) ledger_id: Option<FromStrProxy<LedgerId>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) auto_validate_checksums: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) network_update_period: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) shard: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) realm: Option<u64>,
 }
 
-impl From<ClientConfigInner> for ClientConfig {
-    fn from(value: ClientConfigInner) -> Self {
-        Self {
-            operator: value.operator.map(Into::into),
+impl TryFrom<ClientConfigInner> for ClientConfig {
+    type Error = Error;
+
+    fn try_from(value: ClientConfigInner) -> crate::Result<Self> {
+        fn non_negative(
+            name: &str,
+            fee: Option<FromStrProxy<Hbar>>,
+        ) -> crate::Result<Option<Hbar>> {
+            match fee {
+                Some(FromStrProxy(fee)) if fee < Hbar::ZERO => {
+                    Err(Error::basic_parse(format!("`{name}` must not be negative, got `{fee}`")))
+                }
+                fee => Ok(fee.map(|it| it.0)),
+            }
+        }
+
+        Ok(Self {
+            operator: value.operator.map(TryInto::try_into).transpose()?,
             network: match value.network {
                 Either::Left(it) => Either::Left(it.into_iter().map(|(k, v)| (k, v.0)).collect()),
                 Either::Right(it) => Either::Right(it),
             },
             mirror_network: value.mirror_network,
-        }
+            max_backoff: value.max_backoff.map(Duration::from_millis),
+            min_backoff: value.min_backoff.map(Duration::from_millis),
+            max_attempts: value.max_attempts,
+            request_timeout: value.request_timeout.map(Duration::from_millis),
+            grpc_timeout: value.grpc_timeout.map(Duration::from_millis),
+            default_max_transaction_fee: non_negative(
+                "defaultMaxTransactionFee",
+                value.default_max_transaction_fee,
+            )?,
+            default_max_query_payment: non_negative(
+                "defaultMaxQueryPayment",
+                value.default_max_query_payment,
+            )?,
+            ledger_id: value.ledger_id.map(|it| it.0),
+            auto_validate_checksums: value.auto_validate_checksums,
+            network_update_period: value.network_update_period.map(Duration::from_millis),
+            shard: value.shard.unwrap_or(0),
+            realm: value.realm.unwrap_or(0),
+        })
     }
 }
 
@@ -96,4 +218,300 @@ pub(super) struct ClientConfig {
     pub(super) operator: Option<super::Operator>,
     pub(super) network: Either<HashMap<String, AccountId>, NetworkName>,
     pub(super) mirror_network: Option<Either<Vec<String>, NetworkName>>,
+    pub(super) max_backoff: Option<Duration>,
+    pub(super) min_backoff: Option<Duration>,
+    pub(super) max_attempts: Option<usize>,
+    pub(super) request_timeout: Option<Duration>,
+    pub(super) grpc_timeout: Option<Duration>,
+    pub(super) default_max_transaction_fee: Option<Hbar>,
+    pub(super) default_max_query_payment: Option<Hbar>,
+    pub(super) ledger_id: Option<LedgerId>,
+    pub(super) auto_validate_checksums: Option<bool>,
+    // `Duration::ZERO` disables network updates.
+    pub(super) network_update_period: Option<Duration>,
+    pub(super) shard: u64,
+    pub(super) realm: u64,
+}
+
+#[derive(Copy, Clone)]
+enum EnvValue {
+    String,
+    Number,
+    Bool,
+}
+
+/// Environment variables that override top level config settings.
+const ENV_OVERRIDES: &[(&str, &str, EnvValue)] = &[
+    ("HEDERA_NETWORK", "network", EnvValue::String),
+    ("HEDERA_MAX_BACKOFF", "maxBackoff", EnvValue::Number),
+    ("HEDERA_MIN_BACKOFF", "minBackoff", EnvValue::Number),
+    ("HEDERA_MAX_ATTEMPTS", "maxAttempts", EnvValue::Number),
+    ("HEDERA_REQUEST_TIMEOUT", "requestTimeout", EnvValue::Number),
+    ("HEDERA_GRPC_TIMEOUT", "grpcTimeout", EnvValue::Number),
+    ("HEDERA_DEFAULT_MAX_TRANSACTION_FEE", "defaultMaxTransactionFee", EnvValue::String),
+    ("HEDERA_DEFAULT_MAX_QUERY_PAYMENT", "defaultMaxQueryPayment", EnvValue::String),
+    ("HEDERA_LEDGER_ID", "ledgerId", EnvValue::String),
+    ("HEDERA_AUTO_VALIDATE_CHECKSUMS", "autoValidateChecksums", EnvValue::Bool),
+    ("HEDERA_NETWORK_UPDATE_PERIOD", "networkUpdatePeriod", EnvValue::Number),
+    ("HEDERA_SHARD", "shard", EnvValue::Number),
+    ("HEDERA_REALM", "realm", EnvValue::Number),
+];
+
+/// Environment variables that override operator settings.
+///
+/// The last element is the operator key source the variable replaces, since an operator only has one key.
+const OPERATOR_ENV_OVERRIDES: &[(&str, &str, &[&str])] = &[
+    ("HEDERA_OPERATOR_ID", "account_id", &[]),
+    ("HEDERA_OPERATOR_KEY", "private_key", &["private_key_file", "private_key_password"]),
+    ("HEDERA_OPERATOR_KEY_FILE", "private_key_file", &["private_key"]),
+    ("HEDERA_OPERATOR_KEY_PASSWORD", "private_key_password", &[]),
+];
+
+/// Overrides settings in the json `config` with the `HEDERA_*` variables found by `var`.
+pub(super) fn apply_env_overrides(
+    config: &mut serde_json::Value,
+    var: impl Fn(&str) -> Option<String>,
+) -> crate::Result<()> {
+    use serde_json::Value;
+
+    let Value::Object(config) = config else {
+        return Err(Error::basic_parse("client config must be a json object"));
+    };
+
+    for &(name, key, kind) in ENV_OVERRIDES {
+        let Some(value) = var(name) else {
+            continue;
+        };
+
+        let value = match kind {
+            EnvValue::String => Value::String(value),
+            EnvValue::Number => Value::from(
+                value
+                    .parse::<u64>()
+                    .map_err(|e| Error::basic_parse(format!("invalid value for `{name}`: {e}")))?,
+            ),
+            EnvValue::Bool => Value::Bool(
+                value
+                    .parse::<bool>()
+                    .map_err(|e| Error::basic_parse(format!("invalid value for `{name}`: {e}")))?,
+            ),
+        };
+
+        config.insert(key.to_owned(), value);
+    }
+
+    if let Some(value) = var("HEDERA_MIRROR_NETWORK") {
+        let value = match value.as_str() {
+            "mainnet" | "testnet" | "previewnet" => Value::String(value),
+            _ => value.split(',').map(|it| Value::String(it.trim().to_owned())).collect(),
+        };
+
+        config.insert("mirrorNetwork".to_owned(), value);
+    }
+
+    for &(name, key, replaces) in OPERATOR_ENV_OVERRIDES {
+        let Some(value) = var(name) else {
+            continue;
+        };
+
+        let operator =
+            config.entry("operator").or_insert_with(|| Value::Object(serde_json::Map::new()));
+
+        let Value::Object(operator) = operator else {
+            return Err(Error::basic_parse("client config `operator` must be a json object"));
+        };
+
+        for replaced in replaces {
+            operator.remove(*replaced);
+        }
+
+        operator.insert(key.to_owned(), Value::String(value));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use super::{
+        apply_env_overrides,
+        ClientConfig,
+        ClientConfigInner,
+        Either,
+        NetworkName,
+    };
+    use crate::{
+        AccountId,
+        Hbar,
+        LedgerId,
+    };
+
+    fn parse(json: &str, env: &[(&str, &str)]) -> crate::Result<ClientConfig> {
+        let env: HashMap<_, _> = env.iter().copied().collect();
+
+        let mut config: serde_json::Value = serde_json::from_str(json).unwrap();
+        apply_env_overrides(&mut config, |name| env.get(name).map(|it| (*it).to_owned()))?;
+
+        serde_json::from_value::<ClientConfigInner>(config)
+            .map_err(crate::Error::basic_parse)?
+            .try_into()
+    }
+
+    #[test]
+    fn parse_settings() {
+        let config = parse(
+            r#"{
+                "network": "testnet",
+                "maxBackoff": 8000,
+                "minBackoff": 250,
+                "maxAttempts": 3,
+                "requestTimeout": 120000,
+                "grpcTimeout": 10000,
+                "defaultMaxTransactionFee": "2 ℏ",
+                "defaultMaxQueryPayment": "50000 tℏ",
+                "ledgerId": "testnet",
+                "autoValidateChecksums": true,
+                "networkUpdatePeriod": 0
+            }"#,
+            &[],
+        )
+        .unwrap();
+
+        assert!(matches!(config.network, Either::Right(NetworkName::Testnet)));
+        assert_eq!(config.max_backoff, Some(Duration::from_secs(8)));
+        assert_eq!(config.min_backoff, Some(Duration::from_millis(250)));
+        assert_eq!(config.max_attempts, Some(3));
+        assert_eq!(config.request_timeout, Some(Duration::from_secs(120)));
+        assert_eq!(config.grpc_timeout, Some(Duration::from_secs(10)));
+        assert_eq!(config.default_max_transaction_fee, Some(Hbar::new(2)));
+        assert_eq!(config.default_max_query_payment, Some(Hbar::from_tinybars(50000)));
+        assert_eq!(config.ledger_id, Some(LedgerId::testnet()));
+        assert_eq!(config.auto_validate_checksums, Some(true));
+        assert_eq!(config.network_update_period, Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn env_overrides() {
+        let config = parse(
+            r#"{ "network": "testnet", "maxAttempts": 3 }"#,
+            &[
+                ("HEDERA_NETWORK", "mainnet"),
+                ("HEDERA_MIRROR_NETWORK", "127.0.0.1:5600, 127.0.0.1:5601"),
+                ("HEDERA_MAX_ATTEMPTS", "5"),
+                ("HEDERA_AUTO_VALIDATE_CHECKSUMS", "true"),
+                ("HEDERA_OPERATOR_ID", "0.0.1001"),
+                (
+                    "HEDERA_OPERATOR_KEY",
+                    "302e020100300506032b657004220420db484b828e64b2d8f12ce3c0a0e93a0b8cce7af1bb8f39c97732394482538e10",
+                ),
+            ],
+        )
+        .unwrap();
+
+        assert!(matches!(config.network, Either::Right(NetworkName::Mainnet)));
+        assert!(matches!(
+            config.mirror_network,
+            Some(Either::Left(it)) if it == ["127.0.0.1:5600", "127.0.0.1:5601"]
+        ));
+        assert_eq!(config.max_attempts, Some(5));
+        assert_eq!(config.auto_validate_checksums, Some(true));
+        assert_eq!(config.operator.unwrap().account_id, AccountId::new(0, 0, 1001));
+    }
+
+    #[test]
+    fn env_override_invalid_number() {
+        let res = parse(r#"{ "network": "testnet" }"#, &[("HEDERA_MAX_BACKOFF", "soon")]);
+
+        assert!(matches!(res, Err(crate::Error::BasicParse(_))));
+    }
+
+    #[test]
+    fn operator_needs_a_key() {
+        let res =
+            parse(r#"{ "network": "testnet", "operator": { "account_id": "0.0.1001" } }"#, &[]);
+
+        assert!(matches!(res, Err(crate::Error::BasicParse(_))));
+    }
+
+    #[test]
+    fn operator_key_file_missing() {
+        let res = parse(
+            r#"{
+                "network": "testnet",
+                "operator": { "account_id": "0.0.1001", "private_key_file": "/does/not/exist.pem" }
+            }"#,
+            &[],
+        );
+
+        assert!(matches!(res, Err(crate::Error::BasicParse(_))));
+    }
+
+    #[test]
+    fn negative_fee() {
+        let res = parse(r#"{ "network": "testnet", "defaultMaxTransactionFee": "-1 ℏ" }"#, &[]);
+
+        assert!(matches!(res, Err(crate::Error::BasicParse(_))));
+    }
+
+    #[test]
+    fn to_config_round_trip() {
+        let client = crate::Client::for_network(HashMap::from([(
+            "127.0.0.1:50211".to_owned(),
+            AccountId::new(0, 0, 3),
+        )]))
+        .unwrap();
+
+        client.set_operator(
+            AccountId::new(0, 0, 1001),
+            "302e020100300506032b657004220420db484b828e64b2d8f12ce3c0a0e93a0b8cce7af1bb8f39c97732394482538e10"
+                .parse()
+                .unwrap(),
+        );
+        client.set_mirror_network(["127.0.0.1:5600".to_owned()]);
+        client.set_max_backoff(Duration::from_secs(4));
+        client.set_min_backoff(Duration::from_millis(100));
+        client.set_max_attempts(3);
+        client.set_grpc_timeout(Some(Duration::from_secs(10)));
+        client.set_default_max_transaction_fee(Hbar::new(2));
+        client.set_ledger_id(Some(LedgerId::testnet()));
+        client.set_auto_validate_checksums(true);
+
+        let client2 = crate::Client::from_config(&client.to_config()).unwrap();
+        assert_eq!((client2.shard(), client2.realm()), (0, 0));
+
+        assert_eq!(client2.network(), client.network());
+        assert_eq!(client2.mirror_network(), client.mirror_network());
+        assert_eq!(client2.get_operator_account_id(), client.get_operator_account_id());
+        assert_eq!(client2.get_operator_public_key(), client.get_operator_public_key());
+        assert_eq!(client2.max_backoff(), Duration::from_secs(4));
+        assert_eq!(client2.min_backoff(), Duration::from_millis(100));
+        assert_eq!(client2.max_attempts(), 3);
+        assert_eq!(client2.request_timeout(), None);
+        assert_eq!(client2.grpc_timeout(), Some(Duration::from_secs(10)));
+        assert_eq!(client2.default_max_transaction_fee(), Some(Hbar::new(2)));
+        assert_eq!(client2.default_max_query_payment(), None);
+        assert_eq!(client2.ledger_id_internal().as_deref(), Some(&LedgerId::testnet()));
+        assert!(client2.auto_validate_checksums());
+        assert_eq!(client2.network_update_period(), None);
+    }
+
+    #[test]
+    fn shard_and_realm() {
+        let config =
+            parse(r#"{ "network": { "127.0.0.1:50211": "1.2.3" }, "shard": 1, "realm": 2 }"#, &[])
+                .unwrap();
+
+        assert_eq!((config.shard, config.realm), (1, 2));
+
+        let client = crate::Client::from_config(
+            r#"{ "network": { "127.0.0.1:50211": "1.2.3" }, "shard": 1, "realm": 2 }"#,
+        )
+        .unwrap();
+
+        assert_eq!(client.parse_account_id("1001").unwrap(), AccountId::new(1, 2, 1001));
+        assert!(crate::Client::from_config(r#"{ "network": "testnet", "realm": 2 }"#).is_err());
+    }
 }
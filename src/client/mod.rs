@@ -32,24 +32,39 @@ use std::sync::atomic::{
 };
 use std::time::Duration;
 
+use futures_core::future::BoxFuture;
+pub use network::EndpointPolicy;
 pub(crate) use network::{
     Network,
     NetworkData,
 };
 pub(crate) use operator::Operator;
 use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
 use tokio::sync::watch;
 use triomphe::Arc;
+use unsize::{
+    CoerceUnsize,
+    Coercion,
+};
 
-use self::network::managed::ManagedNetwork;
+use self::network::managed::{
+    AddressBookCache,
+    ManagedNetwork,
+};
 use self::network::mirror::MirrorNetwork;
 pub(crate) use self::network::mirror::MirrorNetworkData;
 use crate::ping_query::PingQuery;
 use crate::signer::AnySigner;
 use crate::{
     AccountId,
+    AddressBookStore,
     ArcSwapOption,
     Error,
+    ExchangeRates,
+    FileContentsQuery,
+    FileId,
     Hbar,
     LedgerId,
     NodeAddressBook,
@@ -63,6 +78,9 @@ mod config;
 mod network;
 mod operator;
 
+/// How long ago an address book can have been saved and still be loaded by [`Client::set_address_book_store`].
+const MAX_SAVED_ADDRESS_BOOK_AGE: time::Duration = time::Duration::days(30);
+
 #[derive(Copy, Clone)]
 pub(crate) struct ClientBackoff {
     pub(crate) max_backoff: Duration,
@@ -94,8 +112,10 @@ struct ClientBuilder {
     ledger_id: Option<LedgerId>,
     auto_validate_checksums: bool,
     regenerate_transaction_ids: bool,
-    update_network: bool,
+    network_update_period: Option<Duration>,
     backoff: ClientBackoff,
+    shard: u64,
+    realm: u64,
 }
 
 impl ClientBuilder {
@@ -109,13 +129,19 @@ impl ClientBuilder {
             ledger_id: None,
             auto_validate_checksums: false,
             regenerate_transaction_ids: true,
-            update_network: true,
+            network_update_period: Some(Duration::from_secs(24 * 60 * 60)),
             backoff: ClientBackoff::default(),
+            shard: 0,
+            realm: 0,
         }
     }
 
+    fn shard_and_realm(self, shard: u64, realm: u64) -> Self {
+        Self { shard, realm, ..self }
+    }
+
     fn disable_network_updating(self) -> Self {
-        Self { update_network: false, ..self }
+        Self { network_update_period: None, ..self }
     }
 
     fn ledger_id(self, ledger_id: Option<LedgerId>) -> Self {
@@ -131,17 +157,20 @@ impl ClientBuilder {
             ledger_id,
             auto_validate_checksums,
             regenerate_transaction_ids,
-            update_network,
+            network_update_period,
             backoff,
+            shard,
+            realm,
         } = self;
 
-        let network_update_tx = match update_network {
-            true => network::managed::spawn_network_update(
+        let network_update_tx = match network_update_period {
+            Some(period) => network::managed::spawn_network_update(
                 network.clone(),
-                Some(Duration::from_secs(24 * 60 * 60)),
+                Some(period),
+                FileId::address_book_for(shard, realm),
             ),
             // yeah, we just drop the rx.
-            false => watch::channel(None).0,
+            None => watch::channel(None).0,
         };
 
         Client(Arc::new(ClientInner {
@@ -151,11 +180,17 @@ impl ClientBuilder {
                 max_transaction_fee.map_or(0, NonZeroU64::get),
             ),
             max_query_payment_tinybar: AtomicU64::new(max_query_payment.map_or(0, NonZeroU64::get)),
+            max_transaction_fee_usd_cents: ArcSwapOption::new(None),
+            max_query_payment_usd_cents: ArcSwapOption::new(None),
+            exchange_rates: ArcSwapOption::new(None),
+            exchange_rates_refresh: tokio::sync::Mutex::new(()),
             ledger_id: ArcSwapOption::new(ledger_id.map(Arc::new)),
             auto_validate_checksums: AtomicBool::new(auto_validate_checksums),
             regenerate_transaction_ids: AtomicBool::new(regenerate_transaction_ids),
             network_update_tx,
             backoff: RwLock::new(backoff),
+            shard,
+            realm,
         }))
     }
 }
@@ -165,11 +200,18 @@ struct ClientInner {
     operator: ArcSwapOption<Operator>,
     max_transaction_fee_tinybar: AtomicU64,
     max_query_payment_tinybar: AtomicU64,
+    max_transaction_fee_usd_cents: ArcSwapOption<Decimal>,
+    max_query_payment_usd_cents: ArcSwapOption<Decimal>,
+    exchange_rates: ArcSwapOption<ExchangeRates>,
+    // held while the exchange rates are refreshed, so that concurrent requests share one refresh.
+    exchange_rates_refresh: tokio::sync::Mutex<()>,
     ledger_id: ArcSwapOption<LedgerId>,
     auto_validate_checksums: AtomicBool,
     regenerate_transaction_ids: AtomicBool,
     network_update_tx: watch::Sender<Option<Duration>>,
     backoff: RwLock<ClientBackoff>,
+    shard: u64,
+    realm: u64,
 }
 
 /// Managed client for use on the Hedera network.
@@ -186,18 +228,83 @@ impl fmt::Debug for Client {
 impl Client {
     #[cfg(feature = "serde")]
     fn from_config_data(config: config::ClientConfig) -> crate::Result<Self> {
-        let config::ClientConfig { operator, network, mirror_network } = config;
+        let config::ClientConfig {
+            operator,
+            network,
+            mirror_network,
+            max_backoff,
+            min_backoff,
+            max_attempts,
+            request_timeout,
+            grpc_timeout,
+            default_max_transaction_fee,
+            default_max_query_payment,
+            ledger_id,
+            auto_validate_checksums,
+            network_update_period,
+            shard,
+            realm,
+        } = config;
+
+        if matches!(network, config::Either::Right(_)) && (shard, realm) != (0, 0) {
+            return Err(Error::basic_parse(format!(
+                "named networks are in shard 0, realm 0, not `{shard}.{realm}`"
+            )));
+        }
 
         // fixme: check to ensure net and mirror net are the same when they're a network name (no other SDK actually checks this though)
-        let client = match network {
-            config::Either::Left(network) => Client::for_network(network)?,
+        let mut builder = match network {
+            config::Either::Left(network) => ClientBuilder::new(ManagedNetwork::new(
+                Network::from_addresses(&network)?,
+                MirrorNetwork::default(),
+            ))
+            .disable_network_updating(),
             config::Either::Right(it) => match it {
-                config::NetworkName::Mainnet => Client::for_mainnet(),
-                config::NetworkName::Testnet => Client::for_testnet(),
-                config::NetworkName::Previewnet => Client::for_previewnet(),
+                config::NetworkName::Mainnet => ClientBuilder::new(ManagedNetwork::mainnet())
+                    .ledger_id(Some(LedgerId::mainnet())),
+                config::NetworkName::Testnet => ClientBuilder::new(ManagedNetwork::testnet())
+                    .ledger_id(Some(LedgerId::testnet())),
+                config::NetworkName::Previewnet => ClientBuilder::new(ManagedNetwork::previewnet())
+                    .ledger_id(Some(LedgerId::previewnet())),
             },
         };
 
+        builder.operator = operator;
+        builder = builder.shard_and_realm(shard, realm);
+
+        if let Some(max_backoff) = max_backoff {
+            builder.backoff.max_backoff = max_backoff;
+        }
+
+        if let Some(min_backoff) = min_backoff {
+            builder.backoff.initial_backoff = min_backoff;
+        }
+
+        if let Some(max_attempts) = max_attempts {
+            builder.backoff.max_attempts = max_attempts;
+        }
+
+        builder.backoff.request_timeout = request_timeout;
+        builder.backoff.grpc_timeout = grpc_timeout;
+
+        // the config ensures that these are non-negative, and zero is "unlimited", just like `None`.
+        builder.max_transaction_fee =
+            default_max_transaction_fee.and_then(|it| NonZeroU64::new(it.to_tinybars() as u64));
+        builder.max_query_payment =
+            default_max_query_payment.and_then(|it| NonZeroU64::new(it.to_tinybars() as u64));
+
+        if let Some(ledger_id) = ledger_id {
+            builder = builder.ledger_id(Some(ledger_id));
+        }
+
+        if let Some(auto_validate_checksums) = auto_validate_checksums {
+            builder.auto_validate_checksums = auto_validate_checksums;
+        }
+
+        if let Some(period) = network_update_period {
+            builder.network_update_period = (!period.is_zero()).then_some(period);
+        }
+
         let mirror_network = mirror_network.map(|mirror_network| match mirror_network {
             config::Either::Left(mirror_network) => {
                 MirrorNetwork::from_addresses(mirror_network.into_iter().map(Cow::Owned).collect())
@@ -209,9 +316,7 @@ impl Client {
             },
         });
 
-        if let Some(operator) = operator {
-            client.0.operator.store(Some(Arc::new(operator)));
-        }
+        let client = builder.build();
 
         if let Some(mirror_network) = mirror_network {
             client.set_mirror_network(mirror_network.load().addresses());
@@ -222,17 +327,105 @@ impl Client {
 
     /// Create a client from the given json config.
     ///
+    /// Besides `network`, `mirrorNetwork` and `operator`, the config can contain
+    /// `maxBackoff`, `minBackoff`, `maxAttempts`, `requestTimeout`, `grpcTimeout`,
+    /// `defaultMaxTransactionFee`, `defaultMaxQueryPayment`, `ledgerId`, `autoValidateChecksums`,
+    /// `networkUpdatePeriod`, `shard` and `realm`.
+    /// Durations are in milliseconds, and a `networkUpdatePeriod` of `0` disables network updates.
+    ///
+    /// The operator key can be given either inline as `private_key`,
+    /// or as the path to a PEM file with `private_key_file` (and `private_key_password` if the file is encrypted).
+    ///
     /// # Errors
     /// - [`Error::BasicParse`] if an error occurs parsing the configuration.
+    /// - [`Error::KeyParse`] if the operator's PEM file can't be parsed or decrypted.
     #[cfg(feature = "serde")]
     pub fn from_config(json: &str) -> crate::Result<Self> {
         let config = serde_json::from_str::<config::ClientConfigInner>(json)
             .map_err(crate::Error::basic_parse)?
-            .into();
+            .try_into()?;
 
         Self::from_config_data(config)
     }
 
+    /// Create a client from the given json config, with settings overridden by `HEDERA_*` environment variables.
+    ///
+    /// The supported variables are
+    /// `HEDERA_NETWORK`, `HEDERA_MIRROR_NETWORK` (a network name or a comma separated list of addresses),
+    /// `HEDERA_OPERATOR_ID`, `HEDERA_OPERATOR_KEY`, `HEDERA_OPERATOR_KEY_FILE`, `HEDERA_OPERATOR_KEY_PASSWORD`,
+    /// `HEDERA_MAX_BACKOFF`, `HEDERA_MIN_BACKOFF`, `HEDERA_MAX_ATTEMPTS`, `HEDERA_REQUEST_TIMEOUT`,
+    /// `HEDERA_GRPC_TIMEOUT`, `HEDERA_DEFAULT_MAX_TRANSACTION_FEE`, `HEDERA_DEFAULT_MAX_QUERY_PAYMENT`,
+    /// `HEDERA_LEDGER_ID`, `HEDERA_AUTO_VALIDATE_CHECKSUMS`, `HEDERA_NETWORK_UPDATE_PERIOD`, `HEDERA_SHARD` and `HEDERA_REALM`.
+    ///
+    /// See [`from_config`](Self::from_config) for the meaning of each setting.
+    ///
+    /// # Errors
+    /// - [`Error::BasicParse`] if an error occurs parsing the configuration or an environment variable.
+    /// - [`Error::KeyParse`] if the operator's PEM file can't be parsed or decrypted.
+    #[cfg(feature = "serde")]
+    pub fn from_config_with_env(json: &str) -> crate::Result<Self> {
+        let mut config =
+            serde_json::from_str::<serde_json::Value>(json).map_err(crate::Error::basic_parse)?;
+
+        config::apply_env_overrides(&mut config, |name| std::env::var(name).ok())?;
+
+        let config = serde_json::from_value::<config::ClientConfigInner>(config)
+            .map_err(crate::Error::basic_parse)?
+            .try_into()?;
+
+        Self::from_config_data(config)
+    }
+
+    /// Export this client's configuration as json, in the format accepted by [`from_config`](Self::from_config).
+    ///
+    /// The network is exported as the current list of node addresses.
+    ///
+    /// <div class="warning">The exported config contains the operator's private key in plain text.</div>
+    ///
+    /// An operator set with [`set_operator_with`](Self::set_operator_with) can't be exported and is left out.
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn to_config(&self) -> String {
+        use config::FromStrProxy;
+
+        let operator = self.load_operator().as_deref().and_then(|it| match &it.signer {
+            AnySigner::PrivateKey(key) => Some(config::Operator {
+                account_id: FromStrProxy(it.account_id),
+                private_key: Some(FromStrProxy(key.clone())),
+                private_key_file: None,
+                private_key_password: None,
+            }),
+            AnySigner::Arbitrary(..) => None,
+        });
+
+        let backoff = self.backoff();
+
+        let config = config::ClientConfigInner {
+            operator,
+            network: config::Either::Left(
+                self.network().into_iter().map(|(k, v)| (k, FromStrProxy(v))).collect(),
+            ),
+            mirror_network: Some(config::Either::Left(self.mirror_network())),
+            max_backoff: Some(backoff.max_backoff.as_millis() as u64),
+            min_backoff: Some(backoff.initial_backoff.as_millis() as u64),
+            max_attempts: Some(backoff.max_attempts),
+            request_timeout: backoff.request_timeout.map(|it| it.as_millis() as u64),
+            grpc_timeout: backoff.grpc_timeout.map(|it| it.as_millis() as u64),
+            default_max_transaction_fee: self.default_max_transaction_fee().map(FromStrProxy),
+            default_max_query_payment: self.default_max_query_payment().map(FromStrProxy),
+            ledger_id: self.ledger_id_internal().as_deref().cloned().map(FromStrProxy),
+            auto_validate_checksums: Some(self.auto_validate_checksums()),
+            network_update_period: Some(
+                self.network_update_period().map_or(0, |it| it.as_millis() as u64),
+            ),
+            shard: Some(self.shard()),
+            realm: Some(self.realm()),
+        };
+
+        // all map keys are strings, so this can't fail.
+        serde_json::to_string_pretty(&config).expect("BUG: failed to serialize client config")
+    }
+
     /// Returns the addresses for the configured mirror network.
     ///
     /// Unless _explicitly_ set, the return value isn't guaranteed to be anything in particular in order to allow future changes without breaking semver.
@@ -283,10 +476,31 @@ impl Client {
     // allowed for API compatibility.
     #[allow(clippy::needless_pass_by_value)]
     pub fn for_network(network: HashMap<String, AccountId>) -> crate::Result<Self> {
+        Self::for_network_with_shard_and_realm(network, 0, 0)
+    }
+
+    /// Construct a client with the given nodes configured, for a network in `shard.realm`.
+    ///
+    /// The shard and realm are used wherever the client fills in an entity ID,
+    /// such as the network's address book and exchange rate files, see [`shard`](Self::shard).
+    ///
+    /// Note that this disables network auto-updating.
+    ///
+    /// # Errors
+    /// - [`Error::BasicParse`] if an error occurs parsing the configuration.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn for_network_with_shard_and_realm(
+        network: HashMap<String, AccountId>,
+        shard: u64,
+        realm: u64,
+    ) -> crate::Result<Self> {
         let network =
             ManagedNetwork::new(Network::from_addresses(&network)?, MirrorNetwork::default());
 
-        Ok(ClientBuilder::new(network).disable_network_updating().build())
+        Ok(ClientBuilder::new(network)
+            .disable_network_updating()
+            .shard_and_realm(shard, realm)
+            .build())
     }
 
     /// Construct a Hedera client pre-configured for mainnet access.
@@ -314,10 +528,87 @@ impl Client {
     /// Note: This is only really useful if you used `for_network`, because the network can auto-update.
     ///
     /// If network auto-updating is enabled this will eventually be overridden.
+    ///
+    /// The address book is saved to the [address book store](Self::set_address_book_store), if there is one.
     // allowed for API compatibility.
     #[allow(clippy::needless_pass_by_value)]
     pub fn set_network_from_address_book(&self, address_book: NodeAddressBook) {
-        self.net().update_from_address_book(&address_book);
+        self.0.network.update_from_address_book(&address_book);
+    }
+
+    /// Sets where the client saves each address book it updates the network from,
+    /// and updates the network from the address book saved there, if there is one.
+    ///
+    /// This lets a client start with the nodes it last knew about,
+    /// instead of the addresses built into the SDK, which may be out of date until the first network update.
+    /// An address book saved for a different ledger than the client's [ledger ID](Self::set_ledger_id) is ignored,
+    /// and so is one saved more than 30 days ago, since its nodes may have moved since.
+    ///
+    /// Returns `true` if the network was updated from a saved address book.
+    ///
+    /// # Errors
+    /// - Any error returned by the store's [`load`](AddressBookStore::load),
+    ///   the store is still used to save address books from then on.
+    pub fn set_address_book_store(
+        &self,
+        store: impl AddressBookStore + 'static,
+    ) -> crate::Result<bool> {
+        let store = Arc::new(store).unsize(Coercion!(to dyn AddressBookStore));
+        let ledger_id = self.ledger_id_internal().as_deref().cloned();
+
+        self.0.network.address_book_cache.store(Some(Arc::new(AddressBookCache {
+            store: store.clone(),
+            ledger_id: ledger_id.clone(),
+        })));
+
+        let Some(cached) = store.load(ledger_id.as_ref())? else {
+            return Ok(false);
+        };
+
+        if let (Some(expected), Some(actual)) = (&ledger_id, cached.ledger_id()) {
+            if expected != actual {
+                log::warn!("ignoring the saved address book for ledger `{actual}`, the client is for `{expected}`");
+                return Ok(false);
+            }
+        }
+
+        if cached.saved_at() + MAX_SAVED_ADDRESS_BOOK_AGE < OffsetDateTime::now_utc() {
+            log::info!(
+                "ignoring the saved address book from {}, it's too old to be trusted",
+                cached.saved_at()
+            );
+            return Ok(false);
+        }
+
+        self.0.network.apply_address_book(cached.address_book());
+
+        Ok(true)
+    }
+
+    /// Loads the address book from the network's address book file (`shard.realm.102`), and updates the network to use it.
+    ///
+    /// Unlike network auto-updating, this queries the consensus nodes instead of the mirror node.
+    /// The address book is saved to the [address book store](Self::set_address_book_store), if there is one.
+    ///
+    /// The query for the file is paid in hbar, up to the client's hbar defaults (or 1 ℏ).
+    ///
+    /// # Errors
+    /// - If the [`FileContentsQuery`] fails.
+    /// - [`Error::FromProtobuf`] if the file doesn't contain a valid address book.
+    pub async fn update_network_from_address_book_file(&self) -> crate::Result<NodeAddressBook> {
+        let contents = FileContentsQuery::new()
+            .file_id(FileId::address_book_for(self.shard(), self.realm()))
+            .max_payment_amount(self.default_max_query_payment().unwrap_or(Hbar::new(1)))
+            .max_payment_transaction_fee(self.default_max_transaction_fee().unwrap_or(Hbar::new(1)))
+            .execute(self)
+            .await?
+            .contents;
+
+        let address_book = NodeAddressBook::from_bytes(&contents)?;
+
+        self.0.network.update_from_address_book(&address_book);
+
+        Ok(address_book)
     }
 
     /// Updates the network to use the given addresses.
@@ -338,6 +629,21 @@ impl Client {
         Ok(())
     }
 
+    /// Returns how the endpoints of an address book are combined with the network's static addresses.
+    #[must_use]
+    pub fn endpoint_policy(&self) -> EndpointPolicy {
+        *self.0.network.endpoint_policy.read()
+    }
+
+    /// Sets how the endpoints of an address book are combined with the network's static addresses.
+    ///
+    /// The static addresses are the ones the network was created with, or the ones given to [`set_network`](Self::set_network).
+    ///
+    /// This takes effect the next time the network is updated from an address book.
+    pub fn set_endpoint_policy(&self, policy: EndpointPolicy) {
+        *self.0.network.endpoint_policy.write() = policy;
+    }
+
     /// Returns the nodes associated with this client.
     #[must_use]
     pub fn network(&self) -> HashMap<String, AccountId> {
@@ -381,15 +687,37 @@ impl Client {
     /// # Errors
     /// - [`Error::BasicParse`] if the network name is not a supported network name.
     pub fn for_name(name: &str) -> crate::Result<Self> {
+        Self::for_name_with_shard_and_realm(name, 0, 0)
+    }
+
+    /// Construct a hedera client pre-configured for access to the given network in `shard.realm`.
+    ///
+    /// This is mostly useful for `"localhost"`, whose node is `shard.realm.3`,
+    /// the public networks are all in shard 0, realm 0.
+    ///
+    /// # Errors
+    /// - [`Error::BasicParse`] if the network name is not a supported network name.
+    /// - [`Error::BasicParse`] if the network is a public network and `shard.realm` isn't `0.0`.
+    pub fn for_name_with_shard_and_realm(
+        name: &str,
+        shard: u64,
+        realm: u64,
+    ) -> crate::Result<Self> {
+        if name != "localhost" && (shard, realm) != (0, 0) {
+            return Err(Error::basic_parse(format!(
+                "network `{name}` is in shard 0, realm 0, not `{shard}.{realm}`"
+            )));
+        }
+
         match name {
             "mainnet" => Ok(Self::for_mainnet()),
             "testnet" => Ok(Self::for_testnet()),
             "previewnet" => Ok(Self::for_previewnet()),
             "localhost" => {
                 let mut network: HashMap<String, AccountId> = HashMap::new();
-                network.insert("127.0.0.1:50211".to_string(), AccountId::new(0, 0, 3));
+                network.insert("127.0.0.1:50211".to_string(), AccountId::new(shard, realm, 3));
 
-                let client = Client::for_network(network).unwrap();
+                let client = Client::for_network_with_shard_and_realm(network, shard, realm)?;
                 client.set_mirror_network(["127.0.0.1:5600".to_string()]);
                 Ok(client)
             }
@@ -397,6 +725,46 @@ impl Client {
         }
     }
 
+    /// Returns the shard of the client's network.
+    ///
+    /// Along with [`realm`](Self::realm), this is used for entity IDs the client fills in,
+    /// such as the network's system files and [`parse_account_id`](Self::parse_account_id).
+    #[must_use]
+    pub fn shard(&self) -> u64 {
+        self.0.shard
+    }
+
+    /// Returns the realm of the client's network.
+    #[must_use]
+    pub fn realm(&self) -> u64 {
+        self.0.realm
+    }
+
+    /// Parses an account ID, filling in the client's shard and realm where `s` doesn't give them.
+    ///
+    /// Besides everything [`AccountId::from_str`](std::str::FromStr::from_str) accepts,
+    /// a bare account number (ex. `1001`) is accepted as `shard.realm.1001`
+    /// and an EVM address (ex. `0x...`) is resolved in the client's shard and realm.
+    ///
+    /// # Errors
+    /// - [`Error::BasicParse`] if `s` isn't an account ID.
+    pub fn parse_account_id(&self, s: &str) -> crate::Result<AccountId> {
+        if let Ok(num) = s.parse::<u64>() {
+            return Ok(AccountId::new(self.shard(), self.realm(), num));
+        }
+
+        if s.starts_with("0x") {
+            let evm_address = s.parse()?;
+            return Ok(AccountId::from_evm_address_with_shard_realm(
+                self.shard(),
+                self.realm(),
+                &evm_address,
+            ));
+        }
+
+        s.parse()
+    }
+
     // optimized function to avoid allocations/pointer chasing.
     // this shouldn't be exposed because it exposes repr.
     pub(crate) fn ledger_id_internal(&self) -> arc_swap::Guard<Option<Arc<LedgerId>>> {
@@ -405,6 +773,14 @@ impl Client {
 
     /// Sets the ledger ID for the Client's network.
     pub fn set_ledger_id(&self, ledger_id: Option<LedgerId>) {
+        // address books saved from now on are for the new ledger.
+        if let Some(cache) = self.0.network.address_book_cache.load_full() {
+            self.0.network.address_book_cache.store(Some(Arc::new(AddressBookCache {
+                store: cache.store.clone(),
+                ledger_id: ledger_id.clone(),
+            })));
+        }
+
         self.0.ledger_id.store(ledger_id.map(Arc::new));
     }
 
@@ -476,14 +852,37 @@ impl Client {
 
     /// Sets the maximum transaction fee to be used when no explicit max transaction fee is set.
     ///
+    /// This replaces a default set with [`set_default_max_transaction_fee_usd_cents`](Self::set_default_max_transaction_fee_usd_cents).
+    ///
     /// Note: Setting `amount` to zero is "unlimited"
     /// # Panics
     /// - if amount is negative
     pub fn set_default_max_transaction_fee(&self, amount: Hbar) {
         assert!(amount >= Hbar::ZERO);
+        self.0.max_transaction_fee_usd_cents.store(None);
         self.0.max_transaction_fee_tinybar.store(amount.to_tinybars() as u64, Ordering::Relaxed);
     }
 
+    /// Sets the maximum transaction fee, in cents (USD), to be used when no explicit max transaction fee is set.
+    ///
+    /// The fee is converted to hbar when each transaction is frozen, see [`Transaction::max_transaction_fee_usd_cents`](crate::Transaction::max_transaction_fee_usd_cents).
+    ///
+    /// This replaces a default set with [`set_default_max_transaction_fee`](Self::set_default_max_transaction_fee).
+    ///
+    /// # Panics
+    /// - if `cents` is negative
+    pub fn set_default_max_transaction_fee_usd_cents(&self, cents: Decimal) {
+        assert!(!cents.is_sign_negative());
+        self.0.max_transaction_fee_tinybar.store(0, Ordering::Relaxed);
+        self.0.max_transaction_fee_usd_cents.store(Some(Arc::new(cents)));
+    }
+
+    /// Gets the maximum transaction fee, in cents (USD), the paying account is willing to pay.
+    #[must_use]
+    pub fn default_max_transaction_fee_usd_cents(&self) -> Option<Decimal> {
+        self.0.max_transaction_fee_usd_cents.load().as_deref().copied()
+    }
+
     /// Gets the maximum transaction fee the paying account is willing to pay.
     #[must_use]
     pub fn default_max_transaction_fee(&self) -> Option<Hbar> {
@@ -507,9 +906,118 @@ impl Client {
     /// - if amount is negative
     pub fn set_default_max_query_payment(&self, amount: Hbar) {
         assert!(amount >= Hbar::ZERO);
+        self.0.max_query_payment_usd_cents.store(None);
         self.0.max_query_payment_tinybar.store(amount.to_tinybars() as u64, Ordering::Relaxed);
     }
 
+    /// Sets the maximum query payment, in cents (USD), to be used when no explicit max query payment is set.
+    ///
+    /// The payment is converted to hbar when each query is executed, see [`Query::max_payment_amount_usd_cents`](crate::Query::max_payment_amount_usd_cents).
+    ///
+    /// This replaces a default set with [`set_default_max_query_payment`](Self::set_default_max_query_payment).
+    ///
+    /// # Panics
+    /// - if `cents` is negative
+    pub fn set_default_max_query_payment_usd_cents(&self, cents: Decimal) {
+        assert!(!cents.is_sign_negative());
+        self.0.max_query_payment_tinybar.store(0, Ordering::Relaxed);
+        self.0.max_query_payment_usd_cents.store(Some(Arc::new(cents)));
+    }
+
+    /// Gets the maximum query payment, in cents (USD), the paying account is willing to pay.
+    #[must_use]
+    pub fn default_max_query_payment_usd_cents(&self) -> Option<Decimal> {
+        self.0.max_query_payment_usd_cents.load().as_deref().copied()
+    }
+
+    /// Returns the cached exchange rates, if they've been loaded.
+    #[must_use]
+    pub fn exchange_rates(&self) -> Option<ExchangeRates> {
+        self.0.exchange_rates.load().as_deref().cloned()
+    }
+
+    /// Loads the exchange rates from the network's exchange rate file (`shard.realm.112`) into the client's cache.
+    ///
+    /// The cache is used to convert fees and payments given in cents (USD) to hbar,
+    /// executing a request refreshes it once its current rate expires.
+    ///
+    /// The query for the file is paid in hbar, up to the client's hbar defaults (or 1 ℏ).
+    ///
+    /// # Errors
+    /// - If the [`FileContentsQuery`] fails.
+    /// - [`Error::FromProtobuf`] if the file doesn't contain valid exchange rates.
+    pub async fn update_exchange_rates(&self) -> crate::Result<ExchangeRates> {
+        let contents = FileContentsQuery::new()
+            .file_id(FileId::exchange_rates_for(self.shard(), self.realm()))
+            .max_payment_amount(self.default_max_query_payment().unwrap_or(Hbar::new(1)))
+            .max_payment_transaction_fee(self.default_max_transaction_fee().unwrap_or(Hbar::new(1)))
+            .execute(self)
+            .await?
+            .contents;
+
+        let exchange_rates = ExchangeRates::from_bytes(&contents)?;
+
+        self.0.exchange_rates.store(Some(Arc::new(exchange_rates.clone())));
+
+        Ok(exchange_rates)
+    }
+
+    /// Loads the exchange rates unless the cached current rate hasn't expired yet.
+    // boxed, because loading the rates executes a query, which may refresh the rates.
+    pub(crate) fn refresh_exchange_rates(&self) -> BoxFuture<'_, crate::Result<()>> {
+        Box::pin(async move {
+            let expired =
+                || {
+                    self.0.exchange_rates.load().as_deref().map_or(true, |it| {
+                        OffsetDateTime::now_utc() >= it.current_rate.expiration_time
+                    })
+                };
+
+            if !expired() {
+                return Ok(());
+            }
+
+            let _refresh = self.0.exchange_rates_refresh.lock().await;
+
+            // another request may have refreshed the rates while we waited.
+            if expired() {
+                self.update_exchange_rates().await?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Converts `cents` (USD) to hbar at the cached exchange rate in effect now.
+    pub(crate) fn usd_cents_to_hbar(&self, cents: Decimal) -> crate::Result<Hbar> {
+        let now = OffsetDateTime::now_utc();
+        let exchange_rates = self.0.exchange_rates.load();
+
+        // the next rate is only good until it expires as well, after that we'd be guessing.
+        let exchange_rates = exchange_rates
+            .as_deref()
+            .filter(|it| now < it.next_rate.expiration_time)
+            .ok_or(Error::ExchangeRatesUnavailable)?;
+
+        exchange_rates.rate_at(now).cents_to_hbar(cents)
+    }
+
+    /// Returns the default max transaction fee in hbar, converting a default in cents (USD).
+    pub(crate) fn resolved_default_max_transaction_fee(&self) -> crate::Result<Option<Hbar>> {
+        match self.default_max_transaction_fee_usd_cents() {
+            Some(cents) => self.usd_cents_to_hbar(cents).map(Some),
+            None => Ok(self.default_max_transaction_fee()),
+        }
+    }
+
+    /// Returns the default max query payment in hbar, converting a default in cents (USD).
+    pub(crate) fn resolved_default_max_query_payment(&self) -> crate::Result<Option<Hbar>> {
+        match self.default_max_query_payment_usd_cents() {
+            Some(cents) => self.usd_cents_to_hbar(cents).map(Some),
+            None => Ok(self.default_max_query_payment()),
+        }
+    }
+
     /// Returns the maximum amount of time that will be spent on a request.
     #[must_use]
     pub fn request_timeout(&self) -> Option<Duration> {
@@ -521,6 +1029,17 @@ impl Client {
         self.0.backoff.write().request_timeout = timeout;
     }
 
+    /// Returns the maximum amount of time that will be spent on a single gRPC call.
+    #[must_use]
+    pub fn grpc_timeout(&self) -> Option<Duration> {
+        self.backoff().grpc_timeout
+    }
+
+    /// Sets the maximum amount of time that will be spent on a single gRPC call.
+    pub fn set_grpc_timeout(&self, timeout: Option<Duration>) {
+        self.0.backoff.write().grpc_timeout = timeout;
+    }
+
     /// Returns the maximum number of attempts for a request.
     #[must_use]
     pub fn max_attempts(&self) -> usize {
@@ -541,8 +1060,8 @@ impl Client {
 
     /// Sets the initial backoff for a request being executed.
     #[doc(alias = "set_initial_backoff")]
-    pub fn set_min_backoff(&self, max_backoff: Duration) {
-        self.0.backoff.write().max_backoff = max_backoff;
+    pub fn set_min_backoff(&self, min_backoff: Duration) {
+        self.0.backoff.write().initial_backoff = min_backoff;
     }
 
     /// Returns the maximum amount of time a request will wait between attempts.
@@ -616,7 +1135,7 @@ impl Client {
     /// Note that network updates will not affect any in-flight requests.
     pub fn set_network_update_period(&self, period: Option<Duration>) {
         self.0.network_update_tx.send_if_modified(|place| {
-            let changed = *place == period;
+            let changed = *place != period;
             if changed {
                 *place = period;
             }
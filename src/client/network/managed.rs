@@ -1,12 +1,24 @@
 use std::time::Duration;
 
+use parking_lot::RwLock;
 use rand::Rng;
 use tokio::sync::watch;
 use triomphe::Arc;
 
 use super::mirror::MirrorNetwork;
-use super::Network;
-use crate::NodeAddressBookQuery;
+use super::{
+    EndpointPolicy,
+    Network,
+};
+use crate::{
+    AddressBookStore,
+    ArcSwapOption,
+    CachedAddressBook,
+    FileId,
+    LedgerId,
+    NodeAddressBook,
+    NodeAddressBookQuery,
+};
 
 #[derive(Clone)]
 pub(crate) struct ManagedNetwork(Arc<ManagedNetworkInner>);
@@ -20,7 +32,12 @@ impl ManagedNetwork {
         mirror: MirrorNetwork,
         // first_update_delay: Duration,
     ) -> Self {
-        Self(Arc::new(ManagedNetworkInner { primary, mirror }))
+        Self(Arc::new(ManagedNetworkInner {
+            primary,
+            mirror,
+            address_book_cache: ArcSwapOption::new(None),
+            endpoint_policy: RwLock::new(EndpointPolicy::default()),
+        }))
     }
 
     pub(crate) fn mainnet() -> Self {
@@ -49,16 +66,51 @@ pub(crate) struct ManagedNetworkInner {
     pub(crate) primary: Network,
     //
     pub(crate) mirror: MirrorNetwork,
+
+    /// Where each address book the network is updated from is saved, if anywhere.
+    pub(crate) address_book_cache: ArcSwapOption<AddressBookCache>,
+
+    /// How address book endpoints are combined with the primary network's static addresses.
+    pub(crate) endpoint_policy: RwLock<EndpointPolicy>,
+}
+
+impl ManagedNetworkInner {
+    /// Updates the consensus network from `address_book`, without saving it.
+    pub(crate) fn apply_address_book(&self, address_book: &NodeAddressBook) {
+        self.primary.update_from_address_book(address_book, *self.endpoint_policy.read());
+    }
+
+    /// Updates the consensus network from `address_book`, and saves it to the cache.
+    pub(crate) fn update_from_address_book(&self, address_book: &NodeAddressBook) {
+        self.apply_address_book(address_book);
+
+        if let Some(cache) = &*self.address_book_cache.load() {
+            let cached = CachedAddressBook::new(address_book.clone(), cache.ledger_id.clone());
+
+            // the network is already updated, so failing to save only costs the next startup.
+            if let Err(e) = cache.store.save(&cached) {
+                log::warn!("failed to save the address book: {e:?}");
+            }
+        }
+    }
+}
+
+pub(crate) struct AddressBookCache {
+    pub(crate) store: Arc<dyn AddressBookStore>,
+
+    /// The ledger ID that saved address books are marked with.
+    pub(crate) ledger_id: Option<LedgerId>,
 }
 
 pub(crate) fn spawn_network_update(
     network: ManagedNetwork,
     initial_update_interval: Option<Duration>,
+    address_book_file_id: FileId,
 ) -> watch::Sender<Option<Duration>> {
     let (tx, rx) = watch::channel(initial_update_interval);
 
     // note: this 100% dies if there's no runtime.
-    tokio::task::spawn(update_network(network, rx));
+    tokio::task::spawn(update_network(network, rx, address_book_file_id));
 
     tx
 }
@@ -69,6 +121,7 @@ pub(crate) fn spawn_network_update(
 async fn update_network(
     network: ManagedNetwork,
     mut update_interval_rx: watch::Receiver<Option<Duration>>,
+    address_book_file_id: FileId,
 ) {
     tokio::time::sleep(ManagedNetwork::NETWORK_FIRST_UPDATE_DELAY).await;
 
@@ -79,10 +132,11 @@ async fn update_network(
         // note: ideally we'd have a `select!` on the channel closing, but, we can't
         // since there's no `async fn closed()`, and honestly, I'm not 100% certain these futures are cancel safe.
         match NodeAddressBookQuery::new()
+            .file_id(address_book_file_id)
             .execute_mirrornet(network.mirror.load().channel(), None)
             .await
         {
-            Ok(it) => network.primary.update_from_address_book(&it),
+            Ok(it) => network.update_from_address_book(&it),
             Err(e) => {
                 log::warn!("{e:?}");
             }
@@ -27,7 +27,6 @@ use std::collections::{
     HashMap,
 };
 use std::fmt;
-use std::net::Ipv4Addr;
 use std::num::NonZeroUsize;
 use std::str::FromStr;
 use std::time::{
@@ -50,6 +49,7 @@ use crate::{
     ArcSwap,
     Error,
     NodeAddressBook,
+    ServiceEndpoint,
 };
 
 pub(crate) const MAINNET: &[(u64, &[&str])] = &[
@@ -104,19 +104,67 @@ pub(crate) const PREVIEWNET: &[(u64, &[&str])] = &[
     (9, &["6.previewnet.hedera.com", "34.125.23.49", "50.18.17.93", "20.150.136.89"]),
 ];
 
+/// How the endpoints of an address book are combined with the addresses a network was created with.
+///
+/// The addresses a network is created with are its *static* addresses:
+/// the SDK's built-in addresses for mainnet, testnet, and previewnet,
+/// or the ones given to [`Client::for_network`](crate::Client::for_network) or [`Client::set_network`](crate::Client::set_network).
+///
+/// Either way, the nodes in the network are the nodes in the address book.
+///
+/// TLS endpoints (those on port 50212) are out of scope and always skipped, whatever the policy.
+/// Nodes are only dialed in plaintext, because dialing over TLS would need the node's certificate
+/// checked against the certificate hash in the address book, which the SDK doesn't do.
+/// A node whose only endpoints are TLS endpoints is dialed at its static addresses, if it has any.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EndpointPolicy {
+    /// Dial nodes at the endpoints in the address book,
+    /// falling back to a node's static addresses if it has no endpoints the SDK can dial.
+    #[default]
+    AddressBook,
+
+    /// Dial nodes at both their static addresses and the endpoints in the address book.
+    Merge,
+
+    /// Dial nodes at their static addresses,
+    /// only using the endpoints in the address book for nodes that have no static addresses.
+    PreferStatic,
+}
+
+impl EndpointPolicy {
+    fn apply(
+        self,
+        static_addresses: Option<&BTreeSet<HostAndPort>>,
+        mut from_address_book: BTreeSet<HostAndPort>,
+    ) -> BTreeSet<HostAndPort> {
+        let static_addresses = static_addresses.cloned().unwrap_or_default();
+
+        match self {
+            Self::AddressBook if from_address_book.is_empty() => static_addresses,
+            Self::AddressBook => from_address_book,
+            Self::Merge => {
+                from_address_book.extend(static_addresses);
+                from_address_book
+            }
+            Self::PreferStatic if static_addresses.is_empty() => from_address_book,
+            Self::PreferStatic => static_addresses,
+        }
+    }
+}
+
 pub(crate) struct Network(pub(crate) ArcSwap<NetworkData>);
 
 impl Network {
     pub(super) fn mainnet() -> Self {
-        NetworkData::from_static(MAINNET).into()
+        NetworkData::from_static(MAINNET, 0, 0).into()
     }
 
     pub(super) fn testnet() -> Self {
-        NetworkData::from_static(TESTNET).into()
+        NetworkData::from_static(TESTNET, 0, 0).into()
     }
 
     pub(super) fn previewnet() -> Self {
-        NetworkData::from_static(PREVIEWNET).into()
+        NetworkData::from_static(PREVIEWNET, 0, 0).into()
     }
 
     pub(super) fn from_addresses(addresses: &HashMap<String, AccountId>) -> crate::Result<Self> {
@@ -160,9 +208,19 @@ impl Network {
         Ok(())
     }
 
-    pub(crate) fn update_from_address_book(&self, address_book: &NodeAddressBook) {
+    pub(crate) fn update_from_address_book(
+        &self,
+        address_book: &NodeAddressBook,
+        policy: EndpointPolicy,
+    ) {
         // todo: skip the updating whem `map` is the same and `connections` is the same.
-        self.rcu(|old| NetworkData::with_address_book(old, address_book));
+        self.rcu(|old| match NetworkData::with_address_book(old, address_book, policy) {
+            Some(new) => Arc::new(new),
+            None => {
+                log::warn!("none of the nodes in the address book can be dialed, keeping the current network");
+                old.clone()
+            }
+        });
     }
 }
 
@@ -181,6 +239,8 @@ pub(crate) struct NetworkData {
     // Health stuff has to be in an Arc because it needs to stick around even if the map changes.
     health: Box<[Arc<parking_lot::RwLock<NodeHealth>>]>,
     connections: Box<[NodeConnection]>,
+    /// The addresses each node had when the network was created, see [`EndpointPolicy`].
+    static_addresses: HashMap<AccountId, BTreeSet<HostAndPort>>,
 }
 
 impl NetworkData {
@@ -188,19 +248,27 @@ impl NetworkData {
         Self::default().with_addresses(addresses)
     }
 
-    pub(crate) fn from_static(network: &'static [(u64, &'static [&'static str])]) -> Self {
+    /// Creates a network of the nodes `shard.realm.num` at their static addresses.
+    pub(crate) fn from_static(
+        network: &'static [(u64, &'static [&'static str])],
+        shard: u64,
+        realm: u64,
+    ) -> Self {
         let mut map = HashMap::with_capacity(network.len());
         let mut node_ids = Vec::with_capacity(network.len());
         let mut connections = Vec::with_capacity(network.len());
         let mut health = Vec::with_capacity(network.len());
+        let mut static_addresses = HashMap::with_capacity(network.len());
 
         for (i, (num, address)) in network.iter().copied().enumerate() {
-            let node_account_id = AccountId::from(num);
+            let node_account_id = AccountId::new(shard, realm, num);
+            let connection = NodeConnection::new_static(address);
 
             map.insert(node_account_id, i);
             node_ids.push(node_account_id);
             health.push(Arc::default());
-            connections.push(NodeConnection::new_static(address));
+            static_addresses.insert(node_account_id, connection.addresses.clone());
+            connections.push(connection);
         }
 
         Self {
@@ -209,10 +277,16 @@ impl NetworkData {
             health: health.into_boxed_slice(),
             connections: connections.into_boxed_slice(),
             backoff: NodeBackoff::default().into(),
+            static_addresses,
         }
     }
 
-    fn with_address_book(old: &Self, address_book: &NodeAddressBook) -> Self {
+    /// Returns `None` if none of the nodes in `address_book` can be dialed.
+    fn with_address_book(
+        old: &Self,
+        address_book: &NodeAddressBook,
+        policy: EndpointPolicy,
+    ) -> Option<Self> {
         let address_book = &address_book.node_addresses;
 
         let mut map = HashMap::with_capacity(address_book.len());
@@ -220,13 +294,23 @@ impl NetworkData {
         let mut connections = Vec::with_capacity(address_book.len());
         let mut health = Vec::with_capacity(address_book.len());
 
-        for (i, address) in address_book.iter().enumerate() {
-            let new: BTreeSet<_> = address
-                .service_endpoints
-                .iter()
-                .filter(|it| it.port() == NodeConnection::PLAINTEXT_PORT)
-                .map(|it| (*it.ip()).into())
-                .collect();
+        for address in address_book {
+            let from_address_book: BTreeSet<_> =
+                address.service_endpoints.iter().filter_map(HostAndPort::from_endpoint).collect();
+
+            let new =
+                policy.apply(old.static_addresses.get(&address.node_account_id), from_address_book);
+
+            if new.is_empty() {
+                log::warn!(
+                    "node {} ({}) has no endpoints that can be dialed, leaving it out of the network",
+                    address.node_id,
+                    address.node_account_id
+                );
+                continue;
+            }
+
+            let i = node_ids.len();
 
             // if the node is the exact same we want to reuse everything (namely the connections and `healthy`).
             // if the node has different routes then we still want to reuse `healthy` but replace the channel with a new channel.
@@ -254,13 +338,18 @@ impl NetworkData {
             connections.push(upsert.1);
         }
 
-        Self {
+        if node_ids.is_empty() {
+            return None;
+        }
+
+        Some(Self {
             map,
             node_ids: node_ids.into_boxed_slice(),
             health: health.into_boxed_slice(),
             connections: connections.into_boxed_slice(),
             backoff: NodeBackoff::default().into(),
-        }
+            static_addresses: old.static_addresses.clone(),
+        })
     }
 
     fn with_addresses(&self, addresses: &HashMap<String, AccountId>) -> crate::Result<Self> {
@@ -296,12 +385,19 @@ impl NetworkData {
             };
         }
 
+        let static_addresses = node_ids
+            .iter()
+            .zip(&connections)
+            .map(|(id, connection)| (*id, connection.addresses.clone()))
+            .collect();
+
         Ok(Self {
             map,
             node_ids: node_ids.into_boxed_slice(),
             health: health.into_boxed_slice(),
             connections: connections.into_boxed_slice(),
             backoff: NodeBackoff::default().into(),
+            static_addresses,
         })
     }
 
@@ -541,13 +637,35 @@ impl HostAndPort {
     const fn from_static(host: &'static str) -> Self {
         Self { host: Cow::Borrowed(host), port: NodeConnection::PLAINTEXT_PORT }
     }
+
+    /// Returns where to dial `endpoint`, or `None` if it's for a protocol the SDK doesn't speak.
+    fn from_endpoint(endpoint: &ServiceEndpoint) -> Option<Self> {
+        // note: the TLS port needs the node's certificate checked against its hash, which the SDK doesn't do.
+        if endpoint.port == NodeConnection::TLS_PORT {
+            log::debug!("skipping TLS endpoint `{endpoint}`");
+            return None;
+        }
+
+        // address books list the gRPC port of some nodes as 0 or the gossip port, both of which mean the plaintext port.
+        let port = match endpoint.port {
+            0 | NodeConnection::GOSSIP_PORT => NodeConnection::PLAINTEXT_PORT,
+            port => port,
+        };
+
+        Some(Self { host: Cow::Owned(endpoint.address.to_string()), port })
+    }
 }
 
 impl FromStr for HostAndPort {
     type Err = crate::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (host, port) = s.split_once(':').ok_or_else(|| Error::basic_parse("Invalid uri"))?;
+        // IPv6 addresses are bracketed, since they contain `:` themselves.
+        let (host, port) = match s.strip_prefix('[') {
+            Some(s) => s.split_once("]:"),
+            None => s.rsplit_once(':'),
+        }
+        .ok_or_else(|| Error::basic_parse("Invalid uri"))?;
 
         Ok(Self {
             host: Cow::Owned(host.to_owned()),
@@ -558,13 +676,11 @@ impl FromStr for HostAndPort {
 
 impl fmt::Display for HostAndPort {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}:{}", self.host, self.port)
-    }
-}
-
-impl From<Ipv4Addr> for HostAndPort {
-    fn from(value: Ipv4Addr) -> Self {
-        Self { host: Cow::Owned(value.to_string()), port: NodeConnection::PLAINTEXT_PORT }
+        if self.host.contains(':') {
+            write!(f, "[{}]:{}", self.host, self.port)
+        } else {
+            write!(f, "{}:{}", self.host, self.port)
+        }
     }
 }
 
@@ -575,7 +691,9 @@ struct NodeConnection {
 }
 
 impl NodeConnection {
+    const GOSSIP_PORT: u16 = 50111;
     const PLAINTEXT_PORT: u16 = 50211;
+    const TLS_PORT: u16 = 50212;
 
     fn new_static(addresses: &[&'static str]) -> NodeConnection {
         Self {
@@ -604,3 +722,136 @@ impl NodeConnection {
         channel
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::net::{
+        Ipv4Addr,
+        Ipv6Addr,
+    };
+
+    use super::{
+        EndpointPolicy,
+        HostAndPort,
+        NetworkData,
+    };
+    use crate::{
+        AccountId,
+        EndpointAddress,
+        NodeAddress,
+        NodeAddressBook,
+        ServiceEndpoint,
+    };
+
+    fn make_address_book(endpoints: Vec<ServiceEndpoint>) -> NodeAddressBook {
+        NodeAddressBook {
+            node_addresses: vec![NodeAddress {
+                node_id: 0,
+                rsa_public_key: Vec::new(),
+                node_account_id: AccountId::new(0, 0, 3),
+                tls_certificate_hash: Vec::new(),
+                service_endpoints: endpoints,
+                description: String::new(),
+            }],
+        }
+    }
+
+    fn make_network() -> NetworkData {
+        NetworkData::from_addresses(&HashMap::from([(
+            "10.0.0.1:50211".to_owned(),
+            AccountId::new(0, 0, 3),
+        )]))
+        .unwrap()
+    }
+
+    fn addresses(network: &NetworkData) -> Vec<String> {
+        let mut addresses: Vec<_> = network.addresses().into_keys().collect();
+        addresses.sort();
+        addresses
+    }
+
+    #[test]
+    fn host_and_port_round_trip() {
+        for s in ["127.0.0.1:50211", "[::1]:50211", "0.testnet.hedera.com:50211"] {
+            assert_eq!(s.parse::<HostAndPort>().unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn address_book_endpoints() {
+        let address_book = make_address_book(vec![
+            ServiceEndpoint::new(Ipv4Addr::new(35, 237, 200, 180), 50211),
+            ServiceEndpoint::new(Ipv6Addr::LOCALHOST, 50211),
+            ServiceEndpoint::new(EndpointAddress::DomainName("node0.example.com".to_owned()), 443),
+            ServiceEndpoint::new(Ipv4Addr::new(35, 237, 200, 180), 50212),
+            ServiceEndpoint::new(Ipv4Addr::new(35, 237, 200, 181), 0),
+            ServiceEndpoint::new(Ipv4Addr::new(35, 237, 200, 182), 50111),
+            ServiceEndpoint::new(
+                EndpointAddress::IpAndDomainName {
+                    ip: Ipv4Addr::new(35, 237, 200, 183).into(),
+                    domain_name: "node3.example.com".to_owned(),
+                },
+                50211,
+            ),
+        ]);
+
+        let network = NetworkData::with_address_book(
+            &make_network(),
+            &address_book,
+            EndpointPolicy::AddressBook,
+        )
+        .unwrap();
+
+        assert_eq!(
+            addresses(&network),
+            [
+                "35.237.200.180:50211",
+                "35.237.200.181:50211",
+                "35.237.200.182:50211",
+                "35.237.200.183:50211",
+                "[::1]:50211",
+                "node0.example.com:443"
+            ]
+        );
+    }
+
+    #[test]
+    fn endpoint_policy() {
+        let address_book =
+            make_address_book(vec![ServiceEndpoint::new(Ipv4Addr::new(35, 237, 200, 180), 50211)]);
+
+        let with_policy = |policy| {
+            addresses(
+                &NetworkData::with_address_book(&make_network(), &address_book, policy).unwrap(),
+            )
+        };
+
+        assert_eq!(with_policy(EndpointPolicy::AddressBook), ["35.237.200.180:50211"]);
+        assert_eq!(with_policy(EndpointPolicy::Merge), ["10.0.0.1:50211", "35.237.200.180:50211"]);
+        assert_eq!(with_policy(EndpointPolicy::PreferStatic), ["10.0.0.1:50211"]);
+    }
+
+    #[test]
+    fn no_dialable_endpoints() {
+        let address_book =
+            make_address_book(vec![ServiceEndpoint::new(Ipv4Addr::new(35, 237, 200, 180), 50212)]);
+
+        // the node falls back to its static addresses.
+        let network = NetworkData::with_address_book(
+            &make_network(),
+            &address_book,
+            EndpointPolicy::AddressBook,
+        )
+        .unwrap();
+        assert_eq!(addresses(&network), ["10.0.0.1:50211"]);
+
+        // without any, there's nothing to dial at all.
+        assert!(NetworkData::with_address_book(
+            &NetworkData::default(),
+            &address_book,
+            EndpointPolicy::AddressBook
+        )
+        .is_none());
+    }
+}
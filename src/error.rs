@@ -24,8 +24,17 @@ use std::result::Result as StdResult;
 use crate::entity_id::Checksum;
 use crate::{
     AccountId,
+    FileId,
     Hbar,
+    IncompleteTopicMessage,
+    Key,
+    ScheduleId,
     Status,
+    SystemFileKind,
+    TokenAssociation,
+    TokenId,
+    TokenKeyType,
+    TopicId,
     TransactionId,
 };
 
@@ -166,11 +175,36 @@ pub enum Error {
     #[error("failed to convert a mnemonic to entropy: {0}")]
     MnemonicEntropy(#[from] MnemonicEntropyError),
 
+    /// NFT metadata didn't follow [HIP-412](https://hips.hedera.com/hip/hip-412).
+    #[cfg(feature = "nft-metadata")]
+    #[error("invalid NFT metadata: {0}")]
+    NftMetadata(#[from] NftMetadataError),
+
     /// The [`Client`](crate::Client) had no payer account (operator)
     /// and the attempted request had no explicit [`TransactionId`].
     #[error("client must be configured with a payer account or requests must be given an explicit transaction id")]
     NoPayerAccountOrTransactionId,
 
+    /// Arithmetic on [`Hbar`], or a conversion to or from it, was out of range.
+    #[error("hbar amount out of range")]
+    HbarOverflow,
+
+    /// A fee or payment in cents (USD) couldn't be converted to hbar, because the client has no current exchange rates.
+    ///
+    /// See [`Client::update_exchange_rates`](crate::Client::update_exchange_rates).
+    #[error("no current exchange rates to convert a fee in cents (USD) to hbar")]
+    ExchangeRatesUnavailable,
+
+    /// An [`ExchangeRate`](crate::ExchangeRate) with a zero side was used to convert between hbar and cents (USD).
+    #[error("exchange rate of {hbars} hbar to {cents} cents can't be used for conversions")]
+    InvalidExchangeRate {
+        /// The hbar side of the rate.
+        hbars: u32,
+
+        /// The cents side of the rate.
+        cents: u32,
+    },
+
     /// Cost of a [`Query`](crate::Query) is more expensive than `max_query_payment`.
     ///
     /// The actual cost of the `Query` is `query_cost`.
@@ -206,6 +240,178 @@ pub enum Error {
     /// Failed to verify a signature.
     #[error("failed to verify a signature: {0}")]
     SignatureVerify(#[source] BoxStdError),
+
+    /// A schedule expired or was deleted before its scheduled transaction was executed.
+    #[error("schedule `{schedule_id}` expired or was deleted before it was executed ({} signatories missing)", missing_signatories.len())]
+    ScheduleNotExecuted {
+        /// The schedule that didn't execute.
+        schedule_id: ScheduleId,
+
+        /// The required keys that hadn't signed the schedule when it was last queried.
+        missing_signatories: Vec<Key>,
+    },
+
+    /// A change to the keys of a token was refused, because it's invalid for the token or would lock it.
+    #[error("refused to change the {key_type} key of token `{token_id}`: {reason}")]
+    TokenKeyChangeRefused {
+        /// The token whose key would have been changed.
+        token_id: TokenId,
+
+        /// The key that would have been changed.
+        key_type: TokenKeyType,
+
+        /// Why the change was refused.
+        reason: &'static str,
+    },
+
+    /// The keys of a token didn't match the requested changes after it was updated.
+    #[error("the keys of token `{token_id}` weren't updated: {key_types:?}")]
+    TokenKeysNotUpdated {
+        /// The token that was updated.
+        token_id: TokenId,
+
+        /// The keys that don't match the requested changes.
+        key_types: Vec<TokenKeyType>,
+    },
+
+    /// A change to the keys of a token wasn't submitted, because not every key it needs would sign it.
+    #[error("the keys of token `{token_id}` weren't changed, {} required keys wouldn't sign", missing_signatories.len())]
+    TokenKeyChangeUnsigned {
+        /// The token whose keys would have been changed.
+        token_id: TokenId,
+
+        /// The required keys that the signers and the operator don't satisfy.
+        missing_signatories: Vec<Key>,
+    },
+
+    /// A spend exceeds the remaining allowance of its spender.
+    #[error("account `{spender_account_id}` can't spend {requested} of account `{owner_account_id}`'s allowance, only {remaining} remains")]
+    InsufficientAllowance {
+        /// The account that granted the allowance.
+        owner_account_id: AccountId,
+
+        /// The account spending the allowance.
+        spender_account_id: AccountId,
+
+        /// The token of the allowance, `None` for hbar allowances.
+        token_id: Option<TokenId>,
+
+        /// The amount the spend needs, in tinybars or the token's smallest denomination.
+        requested: u64,
+
+        /// The remaining amount of the allowance.
+        remaining: u64,
+    },
+
+    /// A request to the mirror node REST API failed.
+    #[cfg(feature = "mirror-rest")]
+    #[error("mirror node request failed: {0}")]
+    MirrorNodeRest(#[source] BoxStdError),
+
+    /// A message of a topic failed running hash verification.
+    ///
+    /// See [`TopicMessageQuery::verify_running_hashes`](crate::TopicMessageQuery::verify_running_hashes).
+    #[error("topic `{topic_id}` failed running hash verification: {reason}")]
+    TopicRunningHash {
+        /// The topic whose messages were verified.
+        topic_id: TopicId,
+
+        /// Why verification failed.
+        #[source]
+        reason: TopicRunningHashError,
+    },
+
+    /// Reading or writing the checkpoint of a durable topic subscription failed.
+    ///
+    /// See [`TopicCheckpointStore`](crate::TopicCheckpointStore).
+    #[error("failed to access a topic checkpoint: {0}")]
+    TopicCheckpointStore(#[source] BoxStdError),
+
+    /// Failed to load or save a client's cached address book.
+    ///
+    /// See [`AddressBookStore`](crate::AddressBookStore).
+    #[error("failed to access the cached address book: {0}")]
+    AddressBookStore(#[source] BoxStdError),
+
+    /// The details of a node don't follow the network's rules for them.
+    ///
+    /// See [`NodeCreateTransaction::validate`](crate::NodeCreateTransaction::validate).
+    #[error("invalid node details: {0}")]
+    InvalidNodeDetails(String),
+
+    /// A message was acknowledged that the subscription didn't deliver.
+    ///
+    /// See [`TopicMessageSubscription::ack`](crate::TopicMessageSubscription::ack).
+    #[error(
+        "message {sequence_number} of topic `{topic_id}` wasn't delivered by this subscription"
+    )]
+    TopicMessageNotDelivered {
+        /// The topic of the subscription.
+        topic_id: TopicId,
+
+        /// The sequence number of the acknowledged message.
+        sequence_number: u64,
+    },
+
+    /// An uploaded file doesn't have the contents that were uploaded.
+    ///
+    /// See [`FileUploadFlow`](crate::FileUploadFlow).
+    #[error(
+        "file `{file_id}` has SHA-384 hash {}, but contents with hash {} were uploaded",
+        hex::encode(actual_hash),
+        hex::encode(expected_hash)
+    )]
+    FileContentsMismatch {
+        /// The uploaded file.
+        file_id: FileId,
+
+        /// The SHA-384 hash of the contents that were uploaded.
+        expected_hash: Vec<u8>,
+
+        /// The SHA-384 hash of the contents of the file.
+        actual_hash: Vec<u8>,
+    },
+
+    /// A [`SystemFile`](crate::SystemFile) can't be encoded without losing data.
+    ///
+    /// Address books have deprecated fields that [`NodeAddressBook`](crate::NodeAddressBook) doesn't keep.
+    #[error("a `{kind:?}` system file can't be encoded without losing data")]
+    SystemFileNotEncodable {
+        /// The kind of system file.
+        kind: SystemFileKind,
+    },
+
+    /// A chunked topic message couldn't be reassembled.
+    ///
+    /// See [`ChunkReassemblyPolicy`](crate::ChunkReassemblyPolicy).
+    #[error("chunked message `{}` of {} chunks couldn't be reassembled ({:?}, {} chunks received)", .0.initial_transaction_id, .0.total, .0.reason, .0.chunks.len())]
+    IncompleteTopicMessage(Box<IncompleteTopicMessage>),
+
+    /// Receivers of a transfer aren't associated with tokens they'd receive, and can't be associated automatically.
+    #[error("{} transfer receivers can't accept their tokens: {associations:?}", associations.len())]
+    TokenNotAssociated {
+        /// The receivers and the tokens they can't accept.
+        associations: Vec<TokenAssociation>,
+    },
+
+    /// The combined amount of a token sent to a receiver is out of range.
+    #[error("amount of token `{token_id}` sent to `{receiver}` is out of range")]
+    TokenAmountOverflow {
+        /// The token being sent.
+        token_id: TokenId,
+
+        /// The account receiving the token.
+        receiver: AccountId,
+    },
+
+    /// The time a network upgrade was scheduled for has already passed.
+    ///
+    /// See [`NetworkUpgradeFlow::start_time`](crate::NetworkUpgradeFlow::start_time).
+    #[error("upgrade start time {start_time} has already passed")]
+    UpgradeStartTimePassed {
+        /// The time the network was to freeze for the upgrade.
+        start_time: time::OffsetDateTime,
+    },
 }
 
 impl Error {
@@ -230,6 +436,47 @@ impl Error {
     }
 }
 
+/// Why a topic message failed running hash verification.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum TopicRunningHashError {
+    /// The message's running hash isn't the hash of its contents and the previous running hash.
+    #[error("the running hash of message {sequence_number} doesn't match the message")]
+    Mismatch {
+        /// The sequence number of the message.
+        sequence_number: u64,
+    },
+
+    /// A message is missing from the stream, or the stream didn't start after the trusted running hash.
+    #[error("expected message {expected}, but received message {received}")]
+    SequenceGap {
+        /// The sequence number of the next message.
+        expected: u64,
+
+        /// The sequence number of the message received instead.
+        received: u64,
+    },
+
+    /// The message's running hash was computed with a version that can't be verified.
+    #[error("message {sequence_number} has running hash version {version}, only version 3 can be verified")]
+    UnsupportedVersion {
+        /// The sequence number of the message.
+        sequence_number: u64,
+
+        /// The message's running hash version.
+        version: u64,
+    },
+
+    /// The mirror node didn't report the message's payer, which the running hash covers.
+    ///
+    /// The mirror node only reports the payer of messages that were submitted with chunk info.
+    #[error("message {sequence_number} has no payer, so its running hash can't be verified")]
+    MissingPayer {
+        /// The sequence number of the message.
+        sequence_number: u64,
+    },
+}
+
 /// Failed to parse a mnemonic.
 #[cfg(feature = "mnemonic")]
 #[derive(Debug, thiserror::Error)]
@@ -253,6 +500,38 @@ pub enum MnemonicParseError {
     },
 }
 
+/// NFT metadata didn't follow [HIP-412](https://hips.hedera.com/hip/hip-412).
+#[cfg(feature = "nft-metadata")]
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum NftMetadataError {
+    /// The metadata isn't valid JSON, or doesn't have the shape of HIP-412 metadata.
+    #[error("failed to parse metadata JSON: {0}")]
+    Json(#[source] serde_json::Error),
+
+    /// A required field is missing or empty.
+    #[error("missing required field `{0}`")]
+    MissingField(String),
+
+    /// A field has an invalid value.
+    #[error("invalid `{field}`: {reason}")]
+    InvalidField {
+        /// The path of the field, such as `attributes[0].value`.
+        field: String,
+        /// Why the value is invalid.
+        reason: String,
+    },
+
+    /// The metadata is too long to be stored on-chain.
+    #[error("{len} bytes is longer than the {max} bytes that can be stored on-chain")]
+    TooLong {
+        /// The length of the metadata.
+        len: usize,
+        /// The most bytes that can be stored on-chain.
+        max: usize,
+    },
+}
+
 /// Failed to convert a [`Mnemonic`](crate::Mnemonic) to a [`PrivateKey`](crate::PrivateKey)
 // todo: find a better name before release.
 #[cfg(feature = "mnemonic")]
@@ -19,9 +19,18 @@
  */
 
 use hedera_proto::services;
+use rust_decimal::Decimal;
 use time::OffsetDateTime;
 
-use crate::protobuf::FromProtobuf;
+use crate::protobuf::{
+    FromProtobuf,
+    ToProtobuf,
+};
+use crate::{
+    Error,
+    Hbar,
+    HbarUnit,
+};
 
 /// The current and next exchange rates between [`Hbar`](crate::HbarUnit::Hbar) and USD-cents.
 #[derive(Debug, Clone)]
@@ -41,6 +50,24 @@ impl ExchangeRates {
     pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
         FromProtobuf::from_bytes(bytes)
     }
+
+    /// Convert `self` to a protobuf-encoded [`Vec<u8>`].
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        ToProtobuf::to_bytes(self)
+    }
+
+    /// Returns the rate in effect at `time`.
+    ///
+    /// This is the current rate until it expires, and the next rate after that.
+    #[must_use]
+    pub fn rate_at(&self, time: OffsetDateTime) -> &ExchangeRate {
+        if time < self.current_rate.expiration_time {
+            &self.current_rate
+        } else {
+            &self.next_rate
+        }
+    }
 }
 
 impl FromProtobuf<services::ExchangeRateSet> for ExchangeRates {
@@ -52,6 +79,17 @@ impl FromProtobuf<services::ExchangeRateSet> for ExchangeRates {
     }
 }
 
+impl ToProtobuf for ExchangeRates {
+    type Protobuf = services::ExchangeRateSet;
+
+    fn to_protobuf(&self) -> Self::Protobuf {
+        services::ExchangeRateSet {
+            current_rate: Some(self.current_rate.to_protobuf()),
+            next_rate: Some(self.next_rate.to_protobuf()),
+        }
+    }
+}
+
 /// Denotes a conversion between Hbars and cents (USD).
 #[derive(Debug, Clone)]
 pub struct ExchangeRate {
@@ -71,6 +109,55 @@ impl ExchangeRate {
     pub fn exchange_rate_in_cents(&self) -> f64 {
         f64::from(self.cents) / f64::from(self.hbars)
     }
+
+    /// Returns the value of `hbar` in cents (USD) at this rate.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidExchangeRate`] if `self.hbars` or `self.cents` is zero.
+    pub fn hbar_to_cents(&self, hbar: Hbar) -> crate::Result<Decimal> {
+        self.require_valid()?;
+
+        hbar.get_value()
+            .checked_mul(Decimal::from(self.cents))
+            .and_then(|it| it.checked_div(Decimal::from(self.hbars)))
+            .ok_or(Error::HbarOverflow)
+    }
+
+    /// Returns the amount of hbar worth `cents` (USD) at this rate, truncated to the nearest tinybar.
+    ///
+    /// # Errors
+    /// - [`Error::InvalidExchangeRate`] if `self.hbars` or `self.cents` is zero.
+    /// - [`Error::HbarOverflow`] if the amount overflows a i64 of tinybars.
+    ///
+    /// # Examples
+    /// ```
+    /// use hedera::{ExchangeRate, Hbar};
+    /// use rust_decimal::Decimal;
+    /// # let expiration_time = time::OffsetDateTime::UNIX_EPOCH;
+    /// // 1 ℏ is worth 12 cents.
+    /// let rate = ExchangeRate { hbars: 1, cents: 12, expiration_time };
+    ///
+    /// assert_eq!(rate.cents_to_hbar(Decimal::from(6)).unwrap(), Hbar::from_tinybars(50_000_000));
+    /// assert_eq!(rate.hbar_to_cents(Hbar::new(2)).unwrap(), Decimal::from(24));
+    /// ```
+    pub fn cents_to_hbar(&self, cents: Decimal) -> crate::Result<Hbar> {
+        self.require_valid()?;
+
+        let hbars = cents
+            .checked_mul(Decimal::from(self.hbars))
+            .and_then(|it| it.checked_div(Decimal::from(self.cents)))
+            .ok_or(Error::HbarOverflow)?;
+
+        Hbar::checked_from_unit(hbars, HbarUnit::Hbar)
+    }
+
+    fn require_valid(&self) -> crate::Result<()> {
+        if self.hbars == 0 || self.cents == 0 {
+            return Err(Error::InvalidExchangeRate { hbars: self.hbars, cents: self.cents });
+        }
+
+        Ok(())
+    }
 }
 
 impl FromProtobuf<services::ExchangeRate> for ExchangeRate {
@@ -82,12 +169,32 @@ impl FromProtobuf<services::ExchangeRate> for ExchangeRate {
     }
 }
 
+impl ToProtobuf for ExchangeRate {
+    type Protobuf = services::ExchangeRate;
+
+    fn to_protobuf(&self) -> Self::Protobuf {
+        services::ExchangeRate {
+            hbar_equiv: self.hbars as i32,
+            cent_equiv: self.cents as i32,
+            expiration_time: Some(self.expiration_time.into()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use assert_matches::assert_matches;
     use expect_test::expect;
     use hex_literal::hex;
+    use rust_decimal::Decimal;
+    use time::OffsetDateTime;
 
-    use crate::ExchangeRates;
+    use crate::{
+        Error,
+        ExchangeRate,
+        ExchangeRates,
+        Hbar,
+    };
 
     #[test]
     fn from_protobuf() {
@@ -112,4 +219,58 @@ mod tests {
         "#]]
         .assert_debug_eq(&exchange_rates);
     }
+
+    #[test]
+    fn to_from_bytes() {
+        let bytes =
+            hex!("0a1008b0ea0110b6b4231a0608f0bade9006121008b0ea01108cef231a060880d7de9006");
+
+        assert_eq!(ExchangeRates::from_bytes(&bytes).unwrap().to_bytes(), bytes);
+    }
+
+    #[test]
+    fn rate_at_expiration() {
+        let exchange_rates = ExchangeRates::from_bytes(&hex!(
+            "0a1008b0ea0110b6b4231a0608f0bade9006121008b0ea01108cef231a060880d7de9006"
+        ))
+        .unwrap();
+
+        let expiration_time = exchange_rates.current_rate.expiration_time;
+
+        assert_eq!(exchange_rates.rate_at(expiration_time - time::Duration::SECOND).cents, 580150);
+        assert_eq!(exchange_rates.rate_at(expiration_time).cents, 587660);
+    }
+
+    #[test]
+    fn convert_cents() {
+        let rate = ExchangeRate {
+            hbars: 30000,
+            cents: 580150,
+            expiration_time: OffsetDateTime::UNIX_EPOCH,
+        };
+
+        let hbar = rate.cents_to_hbar(Decimal::from(100)).unwrap();
+
+        assert_eq!(hbar, Hbar::from_tinybars(517_107_644));
+        assert!(rate.hbar_to_cents(hbar).unwrap() <= Decimal::from(100));
+    }
+
+    #[test]
+    fn convert_with_zero_rate() {
+        let rate =
+            ExchangeRate { hbars: 0, cents: 12, expiration_time: OffsetDateTime::UNIX_EPOCH };
+
+        assert_matches!(
+            rate.hbar_to_cents(Hbar::new(1)),
+            Err(Error::InvalidExchangeRate { hbars: 0, cents: 12 })
+        );
+
+        let rate = ExchangeRate { hbars: 1, cents: 0, ..rate };
+
+        assert_matches!(
+            rate.cents_to_hbar(Decimal::from(100)),
+            Err(Error::InvalidExchangeRate { hbars: 1, cents: 0 })
+        );
+        assert_matches!(rate.hbar_to_cents(Hbar::new(1)), Err(Error::InvalidExchangeRate { .. }));
+    }
 }
@@ -430,6 +430,9 @@ pub enum RequestType {
 
     /// Cancel airdrop tokens.
     TokenCancelAirdrop,
+
+    /// Submit an atomic batch of transactions.
+    AtomicBatch,
 }
 
 impl FromProtobuf<services::HederaFunctionality> for RequestType {
@@ -518,6 +521,7 @@ impl FromProtobuf<services::HederaFunctionality> for RequestType {
             HederaFunctionality::TokenAirdrop => Self::TokenAirdrop,
             HederaFunctionality::TokenClaimAirdrop => Self::TokenClaimAirdrop,
             HederaFunctionality::TokenCancelAirdrop => Self::TokenCancelAirdrop,
+            HederaFunctionality::AtomicBatch => Self::AtomicBatch,
         };
 
         Ok(value)
@@ -612,6 +616,7 @@ impl ToProtobuf for RequestType {
             Self::TokenAirdrop => HederaFunctionality::TokenAirdrop,
             Self::TokenClaimAirdrop => HederaFunctionality::TokenClaimAirdrop,
             Self::TokenCancelAirdrop => HederaFunctionality::TokenCancelAirdrop,
+            Self::AtomicBatch => HederaFunctionality::AtomicBatch,
         }
     }
 }
@@ -58,6 +58,9 @@ pub struct FileId {
 }
 
 impl FileId {
+    /// Address of the node address book with every node's full details, including its internal endpoints.
+    pub const NODE_DETAILS: Self = Self::new(0, 0, 101);
+
     /// Address of the public [node address book](crate::NodeAddressBook) for the current network.
     pub const ADDRESS_BOOK: Self = Self::new(0, 0, 102);
 
@@ -67,11 +70,71 @@ impl FileId {
     /// Address of the [current exchange rate](crate::ExchangeRates) of HBAR to USD.
     pub const EXCHANGE_RATES: Self = Self::new(0, 0, 112);
 
+    /// Address of the [application properties](crate::ServicesConfiguration) of the network.
+    pub const APPLICATION_PROPERTIES: Self = Self::new(0, 0, 121);
+
+    /// Address of the [API permissions](crate::ServicesConfiguration) of the network.
+    pub const API_PERMISSIONS: Self = Self::new(0, 0, 122);
+
+    /// Address of the [throttle definitions](crate::ThrottleDefinitions) of the network.
+    pub const THROTTLE_DEFINITIONS: Self = Self::new(0, 0, 123);
+
+    /// Address of the file that network software upgrade packages are uploaded to.
+    pub const UPGRADE: Self = Self::new(0, 0, 150);
+
     /// Create a `FileId` with the given `shard.realm.num`.
     pub const fn new(shard: u64, realm: u64, num: u64) -> Self {
         Self { shard, realm, num, checksum: None }
     }
 
+    /// Returns the [`NODE_DETAILS`](Self::NODE_DETAILS) file of a network in `shard.realm`.
+    #[must_use]
+    pub const fn node_details_for(shard: u64, realm: u64) -> Self {
+        Self::new(shard, realm, Self::NODE_DETAILS.num)
+    }
+
+    /// Returns the [`ADDRESS_BOOK`](Self::ADDRESS_BOOK) file of a network in `shard.realm`.
+    #[must_use]
+    pub const fn address_book_for(shard: u64, realm: u64) -> Self {
+        Self::new(shard, realm, Self::ADDRESS_BOOK.num)
+    }
+
+    /// Returns the [`FEE_SCHEDULE`](Self::FEE_SCHEDULE) file of a network in `shard.realm`.
+    #[must_use]
+    pub const fn fee_schedule_for(shard: u64, realm: u64) -> Self {
+        Self::new(shard, realm, Self::FEE_SCHEDULE.num)
+    }
+
+    /// Returns the [`EXCHANGE_RATES`](Self::EXCHANGE_RATES) file of a network in `shard.realm`.
+    #[must_use]
+    pub const fn exchange_rates_for(shard: u64, realm: u64) -> Self {
+        Self::new(shard, realm, Self::EXCHANGE_RATES.num)
+    }
+
+    /// Returns the [`APPLICATION_PROPERTIES`](Self::APPLICATION_PROPERTIES) file of a network in `shard.realm`.
+    #[must_use]
+    pub const fn application_properties_for(shard: u64, realm: u64) -> Self {
+        Self::new(shard, realm, Self::APPLICATION_PROPERTIES.num)
+    }
+
+    /// Returns the [`API_PERMISSIONS`](Self::API_PERMISSIONS) file of a network in `shard.realm`.
+    #[must_use]
+    pub const fn api_permissions_for(shard: u64, realm: u64) -> Self {
+        Self::new(shard, realm, Self::API_PERMISSIONS.num)
+    }
+
+    /// Returns the [`THROTTLE_DEFINITIONS`](Self::THROTTLE_DEFINITIONS) file of a network in `shard.realm`.
+    #[must_use]
+    pub const fn throttle_definitions_for(shard: u64, realm: u64) -> Self {
+        Self::new(shard, realm, Self::THROTTLE_DEFINITIONS.num)
+    }
+
+    /// Returns the [`UPGRADE`](Self::UPGRADE) file of a network in `shard.realm`.
+    #[must_use]
+    pub const fn upgrade_for(shard: u64, realm: u64) -> Self {
+        Self::new(shard, realm, Self::UPGRADE.num)
+    }
+
     /// Create a new `FileId` from protobuf-encoded `bytes`.
     ///
     /// # Errors
@@ -0,0 +1,441 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use sha2::{
+    Digest,
+    Sha384,
+};
+
+use crate::signer::AnySigner;
+use crate::{
+    AccountId,
+    Client,
+    Error,
+    FileAppendTransaction,
+    FileContentsQuery,
+    FileCreateTransaction,
+    FileId,
+    FileUpdateTransaction,
+    Key,
+    PrivateKey,
+    PublicKey,
+    Transaction,
+};
+
+/// Upload contents of any size to a new or existing file, and verify that they arrived intact.
+///
+/// The operation of this flow is as follows:
+/// 1. Create the file (via a [`FileCreateTransaction`]), or replace the contents of the existing
+///    [file](Self::file_id) (via a [`FileUpdateTransaction`]), with the first chunk of the contents.
+/// 2. Append each remaining chunk with a [`FileAppendTransaction`], with at most
+///    [`max_concurrency`](Self::max_concurrency) transactions waiting for their receipt at a time.
+/// 3. Fetch the file with a [`FileContentsQuery`], and compare its SHA-384 hash to the hash of the contents.
+///
+/// Appends are always submitted one after another, to the same node, so that they reach consensus in order.
+#[derive(Debug)]
+pub struct FileUploadFlow {
+    node_account_ids: Option<Vec<AccountId>>,
+    file_id: Option<FileId>,
+    contents: Vec<u8>,
+    keys: Option<Vec<Key>>,
+    file_memo: Option<String>,
+    chunk_size: usize,
+    max_concurrency: usize,
+    verify: bool,
+    signers: Vec<AnySigner>,
+}
+
+impl Default for FileUploadFlow {
+    fn default() -> Self {
+        Self {
+            node_account_ids: None,
+            file_id: None,
+            contents: Vec::new(),
+            keys: None,
+            file_memo: None,
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+            max_concurrency: 1,
+            verify: true,
+            signers: Vec::new(),
+        }
+    }
+}
+
+impl FileUploadFlow {
+    const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+    /// Create a new `FileUploadFlow`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the account IDs of the nodes the transactions may be submitted to.
+    #[must_use]
+    pub fn get_node_account_ids(&self) -> Option<&[AccountId]> {
+        self.node_account_ids.as_deref()
+    }
+
+    /// Sets the account IDs of the nodes the transactions may be submitted to.
+    ///
+    /// Defaults to the full list of nodes configured on the client.
+    /// Appends are submitted to whichever node the first transaction was submitted to.
+    pub fn node_account_ids(
+        &mut self,
+        node_account_ids: impl IntoIterator<Item = AccountId>,
+    ) -> &mut Self {
+        self.node_account_ids = Some(node_account_ids.into_iter().collect());
+
+        self
+    }
+
+    /// Returns the existing file whose contents are replaced, if one was set.
+    #[must_use]
+    pub fn get_file_id(&self) -> Option<FileId> {
+        self.file_id
+    }
+
+    /// Sets an existing file whose contents are replaced, instead of creating a new file.
+    ///
+    /// Its keys must sign the transactions, see [`sign`](Self::sign).
+    pub fn file_id(&mut self, file_id: impl Into<FileId>) -> &mut Self {
+        self.file_id = Some(file_id.into());
+
+        self
+    }
+
+    /// Returns the contents to upload.
+    #[must_use]
+    pub fn get_contents(&self) -> &[u8] {
+        &self.contents
+    }
+
+    /// Sets the contents to upload.
+    pub fn contents(&mut self, contents: impl Into<Vec<u8>>) -> &mut Self {
+        self.contents = contents.into();
+
+        self
+    }
+
+    /// Returns the keys of the new file.
+    #[must_use]
+    pub fn get_keys(&self) -> Option<&[Key]> {
+        self.keys.as_deref()
+    }
+
+    /// Sets the keys of the new file, all of which must sign to change it.
+    ///
+    /// Defaults to the client operator's key. Ignored when replacing the contents of an existing file.
+    pub fn keys<K: Into<Key>>(&mut self, keys: impl IntoIterator<Item = K>) -> &mut Self {
+        self.keys = Some(keys.into_iter().map(Into::into).collect());
+
+        self
+    }
+
+    /// Returns the memo of the new file.
+    #[must_use]
+    pub fn get_file_memo(&self) -> Option<&str> {
+        self.file_memo.as_deref()
+    }
+
+    /// Sets the memo of the new file, or the new memo of an existing file.
+    pub fn file_memo(&mut self, file_memo: impl Into<String>) -> &mut Self {
+        self.file_memo = Some(file_memo.into());
+
+        self
+    }
+
+    /// Returns the most bytes of the contents that are uploaded by a single transaction.
+    #[must_use]
+    pub fn get_chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Sets the most bytes of the contents that are uploaded by a single transaction.
+    ///
+    /// Defaults to 4096.
+    ///
+    /// # Panics
+    /// - If `chunk_size` is zero.
+    pub fn chunk_size(&mut self, chunk_size: usize) -> &mut Self {
+        assert!(chunk_size > 0, "chunk size must be greater than zero");
+
+        self.chunk_size = chunk_size;
+
+        self
+    }
+
+    /// Returns the most append transactions that wait for their receipts at the same time.
+    #[must_use]
+    pub fn get_max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    /// Sets the most append transactions that wait for their receipts at the same time.
+    ///
+    /// Defaults to 1, which waits for each append to reach consensus before submitting the next one.
+    ///
+    /// # Panics
+    /// - If `max_concurrency` is zero.
+    pub fn max_concurrency(&mut self, max_concurrency: usize) -> &mut Self {
+        assert!(max_concurrency > 0, "max concurrency must be greater than zero");
+
+        self.max_concurrency = max_concurrency;
+
+        self
+    }
+
+    /// Returns whether the uploaded file is fetched and compared to the contents.
+    #[must_use]
+    pub fn get_verify(&self) -> bool {
+        self.verify
+    }
+
+    /// Sets whether the uploaded file is fetched and compared to the contents.
+    ///
+    /// Defaults to `true`.
+    pub fn verify(&mut self, verify: bool) -> &mut Self {
+        self.verify = verify;
+
+        self
+    }
+
+    /// Adds a key to sign every transaction with, usually one of the file's keys.
+    pub fn sign(&mut self, key: PrivateKey) -> &mut Self {
+        self.sign_signer(AnySigner::PrivateKey(key))
+    }
+
+    /// Adds a signer to sign every transaction with, usually for one of the file's keys.
+    pub fn sign_with<F: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static>(
+        &mut self,
+        public_key: PublicKey,
+        signer: F,
+    ) -> &mut Self {
+        self.sign_signer(AnySigner::arbitrary(Box::new(public_key), signer))
+    }
+
+    pub(crate) fn sign_signer(&mut self, signer: AnySigner) -> &mut Self {
+        if !self.signers.iter().any(|it| it.public_key() == signer.public_key()) {
+            self.signers.push(signer);
+        }
+
+        self
+    }
+
+    /// Uploads the contents, returning the ID of the file.
+    ///
+    /// # Errors
+    /// - [`Error::FileContentsMismatch`] if the uploaded file doesn't have the same hash as the contents.
+    /// - [`Error::ReceiptStatus`] if a transaction failed.
+    /// - Any other error from executing a transaction, querying its receipt, or fetching the file.
+    ///
+    /// # Panics
+    /// - If no file ID or keys are set, and the client has no operator.
+    pub async fn execute(&self, client: &Client) -> crate::Result<FileId> {
+        self.execute_with_optional_timeout(client, None).await
+    }
+
+    /// Uploads the contents, returning the ID of the file.
+    ///
+    /// # Errors
+    /// - [`Error::FileContentsMismatch`] if the uploaded file doesn't have the same hash as the contents.
+    /// - [`Error::ReceiptStatus`] if a transaction failed.
+    /// - Any other error from executing a transaction, querying its receipt, or fetching the file.
+    ///
+    /// # Panics
+    /// - If no file ID or keys are set, and the client has no operator.
+    pub async fn execute_with_timeout(
+        &self,
+        client: &Client,
+        timeout_per_transaction: Duration,
+    ) -> crate::Result<FileId> {
+        self.execute_with_optional_timeout(client, Some(timeout_per_transaction)).await
+    }
+
+    pub(crate) async fn execute_with_optional_timeout(
+        &self,
+        client: &Client,
+        timeout: Option<Duration>,
+    ) -> crate::Result<FileId> {
+        let mut chunks = self.contents.chunks(self.chunk_size);
+        let first_chunk = chunks.next().unwrap_or_default().to_vec();
+
+        let response = match self.file_id {
+            Some(file_id) => {
+                let mut transaction = FileUpdateTransaction::new();
+
+                transaction.file_id(file_id).contents(first_chunk);
+
+                if let Some(file_memo) = &self.file_memo {
+                    transaction.file_memo(file_memo.clone());
+                }
+
+                self.prepare(&mut transaction);
+
+                transaction.execute_with_optional_timeout(client, timeout).await?
+            }
+
+            None => {
+                let keys = match &self.keys {
+                    Some(keys) => keys.clone(),
+                    None => vec![client
+                        .load_operator()
+                        .as_deref()
+                        .map(|it| Key::from(it.signer.public_key()))
+                        .expect(
+                            "must set keys or call `Client::set_operator` to use `FileUploadFlow`",
+                        )],
+                };
+
+                let mut transaction = FileCreateTransaction::new();
+
+                transaction.contents(first_chunk).keys(keys);
+
+                if let Some(file_memo) = &self.file_memo {
+                    transaction.file_memo(file_memo.clone());
+                }
+
+                self.prepare(&mut transaction);
+
+                transaction.execute_with_optional_timeout(client, timeout).await?
+            }
+        };
+
+        let receipt =
+            response.get_receipt_query().execute_with_optional_timeout(client, timeout).await?;
+
+        let file_id =
+            self.file_id.or(receipt.file_id).expect("Creating a file means there's a file ID");
+
+        // every append goes to the same node, so the node submits them to consensus in the order they were sent.
+        let node_account_id = response.node_account_id;
+
+        let appends = chunks.map(|chunk| {
+            let mut transaction = FileAppendTransaction::new();
+
+            transaction.file_id(file_id).contents(chunk.to_vec()).chunk_size(self.chunk_size);
+
+            self.prepare(&mut transaction);
+
+            transaction.node_account_ids([node_account_id]);
+
+            transaction
+        });
+
+        // submitting is sequential (`then`), waiting for receipts is concurrent (`buffered`).
+        let mut receipts = futures_util::stream::iter(appends)
+            .then(|mut transaction| async move {
+                transaction.execute_with_optional_timeout(client, timeout).await
+            })
+            .map(|response| async move {
+                response?.get_receipt_query().execute_with_optional_timeout(client, timeout).await
+            })
+            .buffered(self.max_concurrency);
+
+        while let Some(receipt) = receipts.next().await {
+            receipt?;
+        }
+
+        if self.verify {
+            let uploaded = FileContentsQuery::new()
+                .file_id(file_id)
+                .execute_with_optional_timeout(client, timeout)
+                .await?
+                .contents;
+
+            let expected_hash = Sha384::digest(&self.contents).to_vec();
+            let actual_hash = Sha384::digest(&uploaded).to_vec();
+
+            if expected_hash != actual_hash {
+                return Err(Error::FileContentsMismatch { file_id, expected_hash, actual_hash });
+            }
+        }
+
+        Ok(file_id)
+    }
+
+    fn prepare<D>(&self, transaction: &mut Transaction<D>) {
+        if let Some(node_account_ids) = &self.node_account_ids {
+            transaction.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        for signer in &self.signers {
+            transaction.sign_signer(signer.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccountId,
+        FileId,
+        FileUploadFlow,
+    };
+
+    #[test]
+    fn get_set_file_id() {
+        let mut flow = FileUploadFlow::new();
+        flow.file_id(FileId::new(0, 0, 150));
+
+        assert_eq!(flow.get_file_id(), Some(FileId::new(0, 0, 150)));
+    }
+
+    #[test]
+    fn get_set_contents() {
+        let mut flow = FileUploadFlow::new();
+        flow.contents([1, 2, 3]);
+
+        assert_eq!(flow.get_contents(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn get_set_chunk_size() {
+        let mut flow = FileUploadFlow::new();
+        flow.chunk_size(1024);
+
+        assert_eq!(flow.get_chunk_size(), 1024);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_chunk_size() {
+        FileUploadFlow::new().chunk_size(0);
+    }
+
+    #[test]
+    fn get_set_max_concurrency() {
+        let mut flow = FileUploadFlow::new();
+        flow.max_concurrency(8);
+
+        assert_eq!(flow.get_max_concurrency(), 8);
+    }
+
+    #[test]
+    fn get_set_node_account_ids() {
+        let mut flow = FileUploadFlow::new();
+        flow.node_account_ids([AccountId::new(0, 0, 3)]);
+
+        assert_eq!(flow.get_node_account_ids(), Some([AccountId::new(0, 0, 3)].as_slice()));
+    }
+}
@@ -3,8 +3,11 @@ use std::fmt::{
     Display,
     Formatter,
 };
-use std::ops;
 use std::str::FromStr;
+use std::{
+    iter,
+    ops,
+};
 
 use rust_decimal::prelude::*;
 
@@ -170,13 +173,39 @@ impl Hbar {
     #[must_use]
     #[track_caller]
     pub fn from_unit<T>(amount: T, unit: HbarUnit) -> Self
+    where
+        T: Into<Decimal>,
+    {
+        Self::checked_from_unit(amount, unit).unwrap()
+    }
+
+    /// Convert from `amount` in `unit` to `Hbar`, returning an error instead of panicking on overflow.
+    ///
+    /// Truncates `amount` to the nearest tinybar, like [`from_unit`](Self::from_unit).
+    ///
+    /// # Errors
+    /// - [`Error::HbarOverflow`] if `amount * unit.tinybars()` would overflow a i64.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hedera::Hbar;
+    /// use hedera::HbarUnit;
+    /// assert!(Hbar::checked_from_unit(20, HbarUnit::Millibar).is_ok());
+    /// assert!(Hbar::checked_from_unit(100, HbarUnit::Gigabar).is_err());
+    /// ```
+    pub fn checked_from_unit<T>(amount: T, unit: HbarUnit) -> crate::Result<Self>
     where
         T: Into<Decimal>,
     {
         let unit_tinybars: Decimal = unit.tinybars().into();
-        let amount_tinybars = amount.into().checked_mul(unit_tinybars).unwrap();
 
-        Hbar::from_tinybars(amount_tinybars.to_i64().unwrap())
+        amount
+            .into()
+            .checked_mul(unit_tinybars)
+            .and_then(|it| it.trunc().to_i64())
+            .map(Hbar::from_tinybars)
+            .ok_or(Error::HbarOverflow)
     }
 
     /// Returns the value of `self` in `Tinybar`s.
@@ -214,6 +243,143 @@ impl Hbar {
     pub fn negated(self) -> Self {
         -self
     }
+
+    /// Returns `self + rhs`.
+    ///
+    /// # Errors
+    /// - [`Error::HbarOverflow`] if the result would overflow a i64 of tinybars.
+    pub fn checked_add(self, rhs: Self) -> crate::Result<Self> {
+        self.0.checked_add(rhs.0).map(Self).ok_or(Error::HbarOverflow)
+    }
+
+    /// Returns `self - rhs`.
+    ///
+    /// # Errors
+    /// - [`Error::HbarOverflow`] if the result would overflow a i64 of tinybars.
+    pub fn checked_sub(self, rhs: Self) -> crate::Result<Self> {
+        self.0.checked_sub(rhs.0).map(Self).ok_or(Error::HbarOverflow)
+    }
+
+    /// Returns `self * rhs`.
+    ///
+    /// # Errors
+    /// - [`Error::HbarOverflow`] if the result would overflow a i64 of tinybars.
+    pub fn checked_mul(self, rhs: i64) -> crate::Result<Self> {
+        self.0.checked_mul(rhs).map(Self).ok_or(Error::HbarOverflow)
+    }
+
+    /// Returns the sum of `amounts`.
+    ///
+    /// # Errors
+    /// - [`Error::HbarOverflow`] if the sum would overflow a i64 of tinybars at any point.
+    ///
+    /// # Examples
+    /// ```
+    /// use hedera::Hbar;
+    /// let total = Hbar::checked_sum([Hbar::new(1), Hbar::new(2)]).unwrap();
+    /// assert_eq!(total, Hbar::new(3));
+    /// assert!(Hbar::checked_sum([Hbar::from_tinybars(i64::MAX), Hbar::from_tinybars(1)]).is_err());
+    /// ```
+    pub fn checked_sum<I>(amounts: I) -> crate::Result<Self>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        amounts.into_iter().try_fold(Self::ZERO, Self::checked_add)
+    }
+
+    /// Returns `self + rhs`, saturating at the bounds of a i64 of tinybars.
+    #[must_use]
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// Returns `self - rhs`, saturating at the bounds of a i64 of tinybars.
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Returns `self * rhs`, saturating at the bounds of a i64 of tinybars.
+    #[must_use]
+    pub const fn saturating_mul(self, rhs: i64) -> Self {
+        Self(self.0.saturating_mul(rhs))
+    }
+
+    /// Returns the sum of `amounts`, saturating at the bounds of a i64 of tinybars.
+    #[must_use]
+    pub fn saturating_sum<I>(amounts: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        amounts.into_iter().fold(Self::ZERO, Self::saturating_add)
+    }
+
+    /// Returns a [`Display`]able view of `self` in `unit`.
+    ///
+    /// # Examples
+    /// ```
+    /// use hedera::{Hbar, HbarUnit};
+    /// let hbar = Hbar::from_tinybars(123_456_789);
+    /// assert_eq!(hbar.display_in(HbarUnit::Hbar).to_string(), "1.23456789 ℏ");
+    /// assert_eq!(hbar.display_in(HbarUnit::Millibar).precision(1).to_string(), "1234.6 mℏ");
+    /// assert_eq!(hbar.display_in(HbarUnit::Hbar).precision(2).without_symbol().to_string(), "1.23");
+    /// ```
+    #[must_use]
+    pub fn display_in(self, unit: HbarUnit) -> HbarDisplay {
+        HbarDisplay { hbar: self, unit, precision: None, symbol: true }
+    }
+}
+
+/// Displays an [`Hbar`] in a chosen [`HbarUnit`], see [`Hbar::display_in`].
+///
+/// Without an explicit [`precision`](Self::precision), the formatter's precision (`{:.2}`) is used if given,
+/// otherwise the amount is shown exactly.
+#[derive(Debug, Copy, Clone)]
+pub struct HbarDisplay {
+    hbar: Hbar,
+    unit: HbarUnit,
+    precision: Option<u32>,
+    symbol: bool,
+}
+
+impl HbarDisplay {
+    /// Round to `precision` decimal places (half away from zero), padding with zeros.
+    #[must_use]
+    pub fn precision(mut self, precision: u32) -> Self {
+        self.precision = Some(precision);
+
+        self
+    }
+
+    /// Omit the unit's symbol, leaving only the number.
+    #[must_use]
+    pub fn without_symbol(mut self) -> Self {
+        self.symbol = false;
+
+        self
+    }
+}
+
+impl Display for HbarDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = self.hbar.to(self.unit);
+
+        match self.precision.or_else(|| f.precision().and_then(|it| u32::try_from(it).ok())) {
+            Some(precision) => {
+                let value =
+                    value.round_dp_with_strategy(precision, RoundingStrategy::MidpointAwayFromZero);
+
+                write!(f, "{value:.0$}", precision as usize)?;
+            }
+            None => write!(f, "{}", value.normalize())?,
+        }
+
+        if self.symbol {
+            write!(f, " {}", self.unit.symbol())?;
+        }
+
+        Ok(())
+    }
 }
 
 impl From<Hbar> for Decimal {
@@ -248,10 +414,17 @@ impl FromStr for Hbar {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (amount, unit) = s.split_once(' ').unwrap_or((s, "ℏ"));
+        let s = s.trim();
+
+        // the symbol may follow the amount with or without a space, and defaults to `ℏ`.
+        let (amount, unit) = match s.find(|c: char| !(c.is_ascii_digit() || "+-.".contains(c))) {
+            Some(index) => (s[..index].trim_end(), s[index..].trim_start()),
+            None => (s, "ℏ"),
+        };
+
         let amount: Decimal = amount.parse().map_err(Error::basic_parse)?;
         let unit = HbarUnit::from_str(unit)?;
-        Ok(Hbar::from_unit(amount, unit))
+        Hbar::checked_from_unit(amount, unit)
     }
 }
 
@@ -291,6 +464,12 @@ impl ops::SubAssign for Hbar {
     }
 }
 
+impl iter::Sum for Hbar {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, ops::Add::add)
+    }
+}
+
 impl<T> ops::Mul<T> for Hbar
 where
     i64: ops::Mul<T, Output = i64>,
@@ -335,9 +514,11 @@ where
 mod tests {
     use std::str::FromStr;
 
+    use assert_matches::assert_matches;
     use rust_decimal::Decimal;
 
     use crate::{
+        Error,
         Hbar,
         HbarUnit,
     };
@@ -383,4 +564,37 @@ mod tests {
         assert_eq!(m.to_tinybars(), 3);
         assert_eq!((-m).to_tinybars(), -3);
     }
+
+    #[test]
+    fn it_can_parse_without_space() {
+        assert_eq!(Hbar::from_str("12mℏ").unwrap(), Hbar::from_unit(12, HbarUnit::Millibar));
+        assert_eq!(Hbar::from_str("-1.5ℏ").unwrap(), Hbar::from_tinybars(-150_000_000));
+        assert!(Hbar::from_str("100 Gℏ").is_err());
+    }
+
+    #[test]
+    fn it_can_display_in_unit() {
+        let hbar = Hbar::from_tinybars(-123_456_789);
+
+        assert_eq!(hbar.display_in(HbarUnit::Tinybar).to_string(), "-123456789 tℏ");
+        assert_eq!(hbar.display_in(HbarUnit::Kilobar).to_string(), "-0.00123456789 kℏ");
+        assert_eq!(hbar.display_in(HbarUnit::Hbar).precision(4).to_string(), "-1.2346 ℏ");
+        assert_eq!(format!("{:.3}", hbar.display_in(HbarUnit::Hbar)), "-1.235 ℏ");
+        assert_eq!(Hbar::new(2).display_in(HbarUnit::Hbar).precision(2).to_string(), "2.00 ℏ");
+    }
+
+    #[test]
+    fn it_can_checked_arithmetic() {
+        let max = Hbar::from_tinybars(i64::MAX);
+
+        assert_matches!(max.checked_add(Hbar::from_tinybars(1)), Err(Error::HbarOverflow));
+        assert_matches!(Hbar::new(-1).checked_sub(max), Err(Error::HbarOverflow));
+        assert_matches!(max.checked_mul(2), Err(Error::HbarOverflow));
+        assert_eq!(Hbar::new(3).checked_mul(2).unwrap(), Hbar::new(6));
+
+        assert_eq!(max.saturating_add(Hbar::new(1)), max);
+        assert_eq!(Hbar::saturating_sum([max, max, -max]), Hbar::ZERO);
+        assert_eq!(Hbar::from_tinybars(i64::MIN).saturating_mul(-1), max);
+        assert_eq!([Hbar::new(1), Hbar::new(2)].into_iter().sum::<Hbar>(), Hbar::new(3));
+    }
 }
@@ -54,6 +54,20 @@ impl Key {
     pub fn to_bytes(&self) -> Vec<u8> {
         ToProtobuf::to_bytes(self)
     }
+
+    /// Returns whether signatures of `signatories` satisfy `self`.
+    ///
+    /// A [`KeyList`] is satisfied when at least `threshold` (or all, if there's no threshold) of its keys are.
+    pub(crate) fn is_satisfied_by(&self, signatories: &[Key]) -> bool {
+        match self {
+            Self::KeyList(list) => {
+                let threshold = list.threshold.map_or(list.keys.len(), |it| it as usize);
+
+                list.keys.iter().filter(|it| it.is_satisfied_by(signatories)).count() >= threshold
+            }
+            _ => signatories.contains(self),
+        }
+    }
 }
 
 impl ToProtobuf for Key {
@@ -119,6 +119,8 @@ mod protobuf;
 
 mod account;
 mod address_book;
+mod atomic_file;
+mod batch_transaction;
 mod client;
 mod contract;
 mod downcast;
@@ -133,12 +135,17 @@ mod hbar;
 mod key;
 mod ledger_id;
 mod mirror_query;
+#[cfg(feature = "mirror-rest")]
+mod mirror_rest;
 #[cfg(feature = "mnemonic")]
 mod mnemonic;
 mod network_version_info;
 mod network_version_info_query;
+#[cfg(feature = "nft-metadata")]
+mod nft_metadata;
 mod node_address;
 mod node_address_book;
+mod node_address_book_cache;
 mod node_address_book_query;
 mod pending_airdrop_id;
 mod pending_airdrop_record;
@@ -149,10 +156,12 @@ mod retry;
 mod schedule;
 mod semantic_version;
 mod service_endpoint;
+mod services_configuration;
 mod signer;
 mod staked_id;
 mod staking_info;
 mod system;
+mod throttle_definitions;
 mod token;
 mod topic;
 mod transaction;
@@ -164,12 +173,16 @@ mod transaction_record;
 mod transaction_record_query;
 mod transaction_response;
 mod transfer;
+mod transfer_flow;
 mod transfer_transaction;
 
+#[cfg(feature = "mirror-rest")]
+pub use account::AccountAllowancesQuery;
 pub use account::{
     account_info_flow,
     AccountAllowanceApproveTransaction,
     AccountAllowanceDeleteTransaction,
+    AccountAllowances,
     AccountBalance,
     AccountBalanceQuery,
     AccountCreateTransaction,
@@ -181,10 +194,25 @@ pub use account::{
     AccountStakersQuery,
     AccountUpdateTransaction,
     AllProxyStakers,
+    HbarAllowance,
+    NftAllowance,
     ProxyStaker,
+    TokenAllowance,
 };
-pub use client::Client;
+pub use address_book::{
+    NodeCertificate,
+    NodeCreateTransaction,
+    NodeDeleteTransaction,
+    NodeFieldChange,
+    NodeUpdateDiff,
+    NodeUpdateTransaction,
+};
+pub use batch_transaction::BatchTransaction;
 pub(crate) use client::Operator;
+pub use client::{
+    Client,
+    EndpointPolicy,
+};
 pub use contract::{
     ContractBytecodeQuery,
     ContractCallQuery,
@@ -204,9 +232,12 @@ pub use contract::{
 };
 pub use entity_id::EntityId;
 pub(crate) use entity_id::ValidateChecksums;
+#[cfg(feature = "nft-metadata")]
+pub use error::NftMetadataError;
 pub use error::{
     Error,
     Result,
+    TopicRunningHashError,
 };
 #[cfg(feature = "mnemonic")]
 pub use error::{
@@ -244,9 +275,11 @@ pub use file::{
     FileInfo,
     FileInfoQuery,
     FileUpdateTransaction,
+    FileUploadFlow,
 };
 pub use hbar::{
     Hbar,
+    HbarDisplay,
     HbarUnit,
     Tinybar,
 };
@@ -268,8 +301,22 @@ pub use mnemonic::Mnemonic;
 pub use network_version_info::NetworkVersionInfo;
 pub use network_version_info_query::NetworkVersionInfoQuery;
 pub(crate) use network_version_info_query::NetworkVersionInfoQueryData;
+#[cfg(feature = "nft-metadata")]
+pub use nft_metadata::{
+    NftMetadata,
+    NftMetadataAttribute,
+    NftMetadataFile,
+    NftMetadataLocalization,
+    OnChainNftMetadata,
+    MAX_ON_CHAIN_NFT_METADATA_LEN,
+};
 pub use node_address::NodeAddress;
 pub use node_address_book::NodeAddressBook;
+pub use node_address_book_cache::{
+    AddressBookStore,
+    CachedAddressBook,
+    FileAddressBookStore,
+};
 pub use node_address_book_query::NodeAddressBookQuery;
 pub(crate) use node_address_book_query::NodeAddressBookQueryData;
 pub use pending_airdrop_record::PendingAirdropRecord;
@@ -287,23 +334,44 @@ pub(crate) use retry::retry;
 pub use schedule::{
     ScheduleCreateTransaction,
     ScheduleDeleteTransaction,
+    ScheduleFlow,
+    ScheduleFlowStatus,
     ScheduleId,
     ScheduleInfo,
     ScheduleInfoQuery,
     ScheduleSignTransaction,
 };
 pub use semantic_version::SemanticVersion;
+pub use service_endpoint::{
+    EndpointAddress,
+    ServiceEndpoint,
+};
+pub use services_configuration::{
+    ServicesConfiguration,
+    Setting,
+};
 pub use staking_info::StakingInfo;
 pub use system::{
     FreezeTransaction,
     FreezeType,
+    NetworkUpgradeFlow,
     SystemDeleteTransaction,
+    SystemFile,
+    SystemFileKind,
+    SystemFileQuery,
     SystemUndeleteTransaction,
 };
+pub use throttle_definitions::{
+    ThrottleBucket,
+    ThrottleDefinitions,
+    ThrottleGroup,
+};
 pub use token::{
     AnyCustomFee,
     AssessedCustomFee,
     CustomFee,
+    CustomFeeLimit,
+    CustomFeeSimulator,
     Fee,
     FeeAssessmentMethod,
     FixedFee,
@@ -313,6 +381,7 @@ pub use token::{
     NftId,
     RoyaltyFee,
     RoyaltyFeeData,
+    TokenAirdropFlow,
     TokenAirdropTransaction,
     TokenAssociateTransaction,
     TokenAssociation,
@@ -328,7 +397,10 @@ pub use token::{
     TokenId,
     TokenInfo,
     TokenInfoQuery,
+    TokenKeyType,
+    TokenKeyUpdateFlow,
     TokenKeyValidation,
+    TokenMintFlow,
     TokenMintTransaction,
     TokenNftInfo,
     TokenNftInfoQuery,
@@ -336,6 +408,7 @@ pub use token::{
     TokenPauseTransaction,
     TokenRejectFlow,
     TokenRejectTransaction,
+    TokenRelationship,
     TokenRevokeKycTransaction,
     TokenSupplyType,
     TokenType,
@@ -346,19 +419,35 @@ pub use token::{
     TokenWipeTransaction,
 };
 pub use topic::{
+    ChunkReassemblyPolicy,
+    FileTopicCheckpointStore,
+    IncompleteTopicMessage,
+    IncompleteTopicMessageReason,
+    TopicCheckpoint,
+    TopicCheckpointStore,
     TopicCreateTransaction,
     TopicDeleteTransaction,
     TopicId,
     TopicInfo,
     TopicInfoQuery,
     TopicMessage,
+    TopicMessageChunk,
+    TopicMessageItem,
     TopicMessageQuery,
     TopicMessageSubmitTransaction,
+    TopicMessageSubscription,
+    TopicMultiplexer,
+    TopicMultiplexerHandle,
+    TopicRunningHash,
     TopicUpdateTransaction,
 };
 pub use transaction::{
+    AccountTransfers,
     AnyTransaction,
+    KeyDescription,
+    SignatureDescription,
     Transaction,
+    TransactionDescription,
 };
 pub use transaction_hash::TransactionHash;
 pub use transaction_id::TransactionId;
@@ -369,6 +458,10 @@ pub use transaction_record_query::TransactionRecordQuery;
 pub(crate) use transaction_record_query::TransactionRecordQueryData;
 pub use transaction_response::TransactionResponse;
 pub use transfer::Transfer;
+pub use transfer_flow::{
+    TransferAssociationReport,
+    TransferFlow,
+};
 pub use transfer_transaction::TransferTransaction;
 
 /// Like [`arc_swap::ArcSwapOption`] but with a [`triomphe::Arc`].
@@ -0,0 +1,175 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+//! Minimal client for the mirror node REST API.
+
+use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{
+    Client,
+    Error,
+    TransactionId,
+};
+
+static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+
+// the REST API of a local mirror node doesn't share the gRPC API's port.
+const LOCAL_REST_PORT: u16 = 5551;
+
+/// Returns the origin of the REST API of the mirror node at `address` (the address of its gRPC API).
+pub(crate) fn rest_origin(address: &str) -> String {
+    let host = address.rsplit_once(':').map_or(address, |(host, _)| host);
+
+    match host {
+        "localhost" | "127.0.0.1" => format!("http://{host}:{LOCAL_REST_PORT}"),
+        _ => format!("https://{host}"),
+    }
+}
+
+fn origin(client: &Client) -> crate::Result<String> {
+    client
+        .mirror_network()
+        .first()
+        .map(|address| rest_origin(address))
+        .ok_or_else(|| Error::MirrorNodeRest("client has no mirror network".into()))
+}
+
+/// GETs `path` (such as `/api/v1/accounts/0.0.3`) from the mirror node at `origin`.
+///
+/// Returns `None` if the mirror node doesn't know about what `path` refers to.
+async fn get_from<T: DeserializeOwned>(
+    client: &Client,
+    origin: &str,
+    path: &str,
+) -> crate::Result<Option<T>> {
+    let mut request = HTTP_CLIENT.get(format!("{origin}{path}"));
+
+    if let Some(timeout) = client.request_timeout() {
+        request = request.timeout(timeout);
+    }
+
+    let response = request.send().await.map_err(|e| Error::MirrorNodeRest(e.into()))?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    response
+        .error_for_status()
+        .map_err(|e| Error::MirrorNodeRest(e.into()))?
+        .json()
+        .await
+        .map(Some)
+        .map_err(|e| Error::MirrorNodeRest(e.into()))
+}
+
+/// GETs every page of `path` from the client's mirror node, collecting the `key` array of each page.
+pub(crate) async fn get_all<T: DeserializeOwned>(
+    client: &Client,
+    path: &str,
+    key: &str,
+) -> crate::Result<Vec<T>> {
+    get_all_if_found(client, path, key)
+        .await?
+        .ok_or_else(|| Error::MirrorNodeRest(format!("mirror node has no `{path}`").into()))
+}
+
+/// Like [`get_all`], but returns `None` if the mirror node doesn't know about what `path` refers to.
+pub(crate) async fn get_all_if_found<T: DeserializeOwned>(
+    client: &Client,
+    path: &str,
+    key: &str,
+) -> crate::Result<Option<Vec<T>>> {
+    let origin = origin(client)?;
+
+    let mut items = Vec::new();
+    let mut next = Some(path.to_owned());
+
+    while let Some(path) = next.take() {
+        let Some(mut page): Option<Value> = get_from(client, &origin, &path).await? else {
+            return Ok(None);
+        };
+
+        if let Value::Array(page_items) = page[key].take() {
+            for item in page_items {
+                items.push(
+                    serde_json::from_value(item).map_err(|e| Error::MirrorNodeRest(e.into()))?,
+                );
+            }
+        }
+
+        // `links.next` is a path on the same mirror node, or `null` on the last page.
+        next = page["links"]["next"].as_str().map(str::to_owned);
+    }
+
+    Ok(Some(items))
+}
+
+/// Formats `transaction_id` the way the mirror node's REST API expects it, such as `0.0.2-1234567890-000000001`.
+pub(crate) fn transaction_id_path_segment(transaction_id: &TransactionId) -> String {
+    format!(
+        "{}-{}-{:09}",
+        transaction_id.account_id,
+        transaction_id.valid_start.unix_timestamp(),
+        transaction_id.valid_start.nanosecond()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use time::OffsetDateTime;
+
+    use super::{
+        rest_origin,
+        transaction_id_path_segment,
+    };
+    use crate::{
+        AccountId,
+        TransactionId,
+    };
+
+    #[test]
+    fn origin_of_public_mirror_node() {
+        assert_eq!(
+            rest_origin("testnet.mirrornode.hedera.com:443"),
+            "https://testnet.mirrornode.hedera.com"
+        );
+    }
+
+    #[test]
+    fn origin_of_local_mirror_node() {
+        assert_eq!(rest_origin("127.0.0.1:5600"), "http://127.0.0.1:5551");
+    }
+
+    #[test]
+    fn transaction_id_segment() {
+        let transaction_id = TransactionId {
+            account_id: AccountId::new(0, 0, 2),
+            valid_start: OffsetDateTime::from_unix_timestamp_nanos(1_234_567_890_000_000_001)
+                .unwrap(),
+            nonce: None,
+            scheduled: false,
+        };
+
+        assert_eq!(transaction_id_path_segment(&transaction_id), "0.0.2-1234567890-000000001");
+    }
+}
@@ -0,0 +1,492 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+//! [HIP-412](https://hips.hedera.com/hip/hip-412) NFT metadata.
+
+use serde_json::{
+    Map,
+    Value,
+};
+
+use crate::{
+    Error,
+    NftMetadataError,
+    TokenNftInfo,
+};
+
+/// The most bytes of metadata the network stores for a single NFT.
+pub const MAX_ON_CHAIN_NFT_METADATA_LEN: usize = 100;
+
+/// NFT metadata, as described by [HIP-412](https://hips.hedera.com/hip/hip-412).
+///
+/// This is the JSON document that's stored off-chain (for example on IPFS),
+/// the NFT itself usually only stores its URI, see [`NftMetadata::uri_to_mint_metadata`].
+#[derive(Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct NftMetadata {
+    /// The name of the NFT.
+    pub name: String,
+
+    /// The artist(s) that created the NFT.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+
+    /// The decentralized identifier of the creator.
+    #[serde(rename = "creatorDID", default, skip_serializing_if = "Option::is_none")]
+    pub creator_did: Option<String>,
+
+    /// A human readable description of the NFT.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The URI of the NFT's preview image.
+    pub image: String,
+
+    /// The checksum of the image, for example `sha256:<hex>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+
+    /// The MIME type of the image.
+    #[serde(rename = "type")]
+    pub mime_type: String,
+
+    /// The metadata format, [`NftMetadata::FORMAT`] for HIP-412 metadata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+
+    /// Arbitrary properties of the NFT.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<Map<String, Value>>,
+
+    /// The files of the NFT, other than its preview image.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<NftMetadataFile>,
+
+    /// Traits of the NFT, usually shown by marketplaces.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<NftMetadataAttribute>,
+
+    /// Where to find translations of this metadata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localization: Option<NftMetadataLocalization>,
+}
+
+/// A file of an NFT.
+#[derive(Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct NftMetadataFile {
+    /// The URI of the file.
+    pub uri: String,
+
+    /// The checksum of the file, for example `sha256:<hex>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+
+    /// Whether this is the file that should be shown by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_default_file: Option<bool>,
+
+    /// The MIME type of the file.
+    #[serde(rename = "type")]
+    pub mime_type: String,
+
+    /// Metadata of the file itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Box<NftMetadata>>,
+
+    /// The URI of the file's metadata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_uri: Option<String>,
+}
+
+/// A trait of an NFT.
+#[derive(Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct NftMetadataAttribute {
+    /// The name of the trait.
+    pub trait_type: String,
+
+    /// How the trait should be displayed, one of [`NftMetadataAttribute::DISPLAY_TYPES`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_type: Option<String>,
+
+    /// The value of the trait, a string, number or boolean.
+    pub value: Value,
+
+    /// The largest value the trait can have, for numeric traits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_value: Option<Value>,
+}
+
+/// Where to find translations of NFT metadata.
+#[derive(Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct NftMetadataLocalization {
+    /// The URI of the translations, with `{locale}` in place of the locale.
+    pub uri: String,
+
+    /// The locale of the metadata containing this localization.
+    pub default: String,
+
+    /// The locales translations are available in.
+    pub locales: Vec<String>,
+}
+
+/// NFT metadata as stored on-chain, see [`NftMetadata::from_nft_info`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum OnChainNftMetadata {
+    /// The URI of the off-chain metadata JSON.
+    Uri(String),
+
+    /// The metadata JSON itself, for metadata small enough to be stored on-chain.
+    Inline(NftMetadata),
+}
+
+fn invalid(field: impl Into<String>, reason: impl Into<String>) -> Error {
+    NftMetadataError::InvalidField { field: field.into(), reason: reason.into() }.into()
+}
+
+fn require_non_empty(field: &str, value: &str) -> crate::Result<()> {
+    if value.trim().is_empty() {
+        return Err(NftMetadataError::MissingField(field.to_owned()).into());
+    }
+
+    Ok(())
+}
+
+fn validate_mime_type(field: &str, mime_type: &str) -> crate::Result<()> {
+    require_non_empty(field, mime_type)?;
+
+    match mime_type.split_once('/') {
+        Some((kind, subtype)) if !kind.is_empty() && !subtype.is_empty() => Ok(()),
+        _ => Err(invalid(field, format!("`{mime_type}` isn't a MIME type"))),
+    }
+}
+
+fn validate_checksum(field: &str, checksum: Option<&str>) -> crate::Result<()> {
+    let Some(checksum) = checksum else {
+        return Ok(());
+    };
+
+    match checksum.split_once(':') {
+        Some((algorithm, digest))
+            if !algorithm.is_empty() && digest.bytes().all(|it| it.is_ascii_hexdigit()) =>
+        {
+            Ok(())
+        }
+        _ => Err(invalid(field, "expected `<algorithm>:<hex digest>`")),
+    }
+}
+
+fn is_locale(locale: &str) -> bool {
+    locale.len() == 2 && locale.bytes().all(|it| it.is_ascii_lowercase())
+}
+
+impl NftMetadata {
+    /// The `format` of HIP-412 metadata.
+    pub const FORMAT: &'static str = "HIP412@2.0.0";
+
+    /// Create new metadata with the required fields, and `format` set to [`NftMetadata::FORMAT`].
+    #[must_use]
+    pub fn new(
+        name: impl Into<String>,
+        image: impl Into<String>,
+        mime_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            creator: None,
+            creator_did: None,
+            description: None,
+            image: image.into(),
+            checksum: None,
+            mime_type: mime_type.into(),
+            format: Some(Self::FORMAT.to_owned()),
+            properties: None,
+            files: Vec::new(),
+            attributes: Vec::new(),
+            localization: None,
+        }
+    }
+
+    /// Check that `self` follows the HIP-412 schema.
+    ///
+    /// # Errors
+    /// - [`Error::NftMetadata`] if a required field is empty or a field has an invalid value.
+    pub fn validate(&self) -> crate::Result<()> {
+        require_non_empty("name", &self.name)?;
+        require_non_empty("image", &self.image)?;
+        validate_mime_type("type", &self.mime_type)?;
+        validate_checksum("checksum", self.checksum.as_deref())?;
+
+        if let Some(format) = &self.format {
+            if !format.starts_with("HIP412@") {
+                return Err(invalid("format", format!("`{format}` isn't a HIP-412 format")));
+            }
+        }
+
+        for (index, file) in self.files.iter().enumerate() {
+            require_non_empty(&format!("files[{index}].uri"), &file.uri)?;
+            validate_mime_type(&format!("files[{index}].type"), &file.mime_type)?;
+            validate_checksum(&format!("files[{index}].checksum"), file.checksum.as_deref())?;
+
+            if let Some(metadata) = &file.metadata {
+                metadata.validate()?;
+            }
+        }
+
+        if self.files.iter().filter(|it| it.is_default_file == Some(true)).count() > 1 {
+            return Err(invalid("files", "more than one file is the default file"));
+        }
+
+        for (index, attribute) in self.attributes.iter().enumerate() {
+            attribute.validate(index)?;
+        }
+
+        if let Some(localization) = &self.localization {
+            if !localization.uri.contains("{locale}") {
+                return Err(invalid("localization.uri", "must contain `{locale}`"));
+            }
+
+            if !is_locale(&localization.default) {
+                return Err(invalid("localization.default", "expected a 2 letter locale"));
+            }
+
+            if !localization.locales.iter().all(|it| is_locale(it)) {
+                return Err(invalid("localization.locales", "expected 2 letter locales"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse and validate metadata from its JSON encoding.
+    ///
+    /// # Errors
+    /// - [`Error::NftMetadata`] if `bytes` isn't metadata JSON, or the metadata is invalid.
+    pub fn from_json_bytes(bytes: &[u8]) -> crate::Result<Self> {
+        let metadata: Self = serde_json::from_slice(bytes).map_err(NftMetadataError::Json)?;
+
+        metadata.validate()?;
+
+        Ok(metadata)
+    }
+
+    /// Validate `self` and encode it as JSON, ready to be uploaded.
+    ///
+    /// # Errors
+    /// - [`Error::NftMetadata`] if the metadata is invalid.
+    pub fn to_json_bytes(&self) -> crate::Result<Vec<u8>> {
+        self.validate()?;
+
+        Ok(serde_json::to_vec(self).map_err(NftMetadataError::Json)?)
+    }
+
+    /// Encode `self` as metadata to mint an NFT with, when it's small enough to be stored on-chain.
+    ///
+    /// Most metadata is too large, upload [`to_json_bytes`](Self::to_json_bytes) and use [`uri_to_mint_metadata`](Self::uri_to_mint_metadata) instead.
+    ///
+    /// # Errors
+    /// - [`Error::NftMetadata`] if the metadata is invalid or its JSON is longer than [`MAX_ON_CHAIN_NFT_METADATA_LEN`].
+    pub fn to_mint_metadata(&self) -> crate::Result<Vec<u8>> {
+        let bytes = self.to_json_bytes()?;
+
+        if bytes.len() > MAX_ON_CHAIN_NFT_METADATA_LEN {
+            return Err(NftMetadataError::TooLong {
+                len: bytes.len(),
+                max: MAX_ON_CHAIN_NFT_METADATA_LEN,
+            }
+            .into());
+        }
+
+        Ok(bytes)
+    }
+
+    /// Encode the URI of uploaded metadata as metadata to mint an NFT with,
+    /// such as `ipfs://<CID>/metadata.json`.
+    ///
+    /// # Errors
+    /// - [`Error::NftMetadata`] if `uri` isn't a URI, or is longer than [`MAX_ON_CHAIN_NFT_METADATA_LEN`] bytes.
+    pub fn uri_to_mint_metadata(uri: &str) -> crate::Result<Vec<u8>> {
+        match uri.split_once("://") {
+            Some((scheme, rest)) if !scheme.is_empty() && !rest.is_empty() => {}
+            _ => return Err(invalid("uri", format!("`{uri}` isn't a URI"))),
+        }
+
+        if uri.len() > MAX_ON_CHAIN_NFT_METADATA_LEN {
+            return Err(NftMetadataError::TooLong {
+                len: uri.len(),
+                max: MAX_ON_CHAIN_NFT_METADATA_LEN,
+            }
+            .into());
+        }
+
+        Ok(uri.as_bytes().to_vec())
+    }
+
+    /// Parse the on-chain metadata of an NFT, which is either the URI of its metadata or the metadata itself.
+    ///
+    /// # Errors
+    /// - [`Error::NftMetadata`] if the metadata is neither a URI nor valid metadata JSON.
+    pub fn from_nft_info(info: &TokenNftInfo) -> crate::Result<OnChainNftMetadata> {
+        OnChainNftMetadata::from_bytes(&info.metadata)
+    }
+}
+
+impl OnChainNftMetadata {
+    /// Parse on-chain NFT metadata, which is either the URI of its metadata or the metadata itself.
+    ///
+    /// # Errors
+    /// - [`Error::NftMetadata`] if `bytes` are neither a URI nor valid metadata JSON.
+    pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
+        if bytes.iter().find(|it| !it.is_ascii_whitespace()) == Some(&b'{') {
+            return NftMetadata::from_json_bytes(bytes).map(Self::Inline);
+        }
+
+        let uri = std::str::from_utf8(bytes)
+            .map_err(|_| invalid("metadata", "neither a URI nor metadata JSON"))?;
+
+        NftMetadata::uri_to_mint_metadata(uri)?;
+
+        Ok(Self::Uri(uri.to_owned()))
+    }
+}
+
+impl NftMetadataAttribute {
+    /// The display types HIP-412 defines.
+    pub const DISPLAY_TYPES: [&'static str; 7] =
+        ["text", "boolean", "percentage", "boost", "datetime", "date", "color"];
+
+    fn validate(&self, index: usize) -> crate::Result<()> {
+        require_non_empty(&format!("attributes[{index}].trait_type"), &self.trait_type)?;
+
+        if !matches!(self.value, Value::String(_) | Value::Number(_) | Value::Bool(_)) {
+            return Err(invalid(
+                format!("attributes[{index}].value"),
+                "expected a string, number or boolean",
+            ));
+        }
+
+        if let Some(display_type) = &self.display_type {
+            if !Self::DISPLAY_TYPES.contains(&display_type.as_str()) {
+                return Err(invalid(
+                    format!("attributes[{index}].display_type"),
+                    format!("unknown display type `{display_type}`"),
+                ));
+            }
+        }
+
+        if let Some(max_value) = &self.max_value {
+            if !max_value.is_number() {
+                return Err(invalid(format!("attributes[{index}].max_value"), "expected a number"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use serde_json::json;
+
+    use super::{
+        NftMetadata,
+        NftMetadataAttribute,
+        OnChainNftMetadata,
+    };
+    use crate::{
+        Error,
+        NftMetadataError,
+    };
+
+    fn make_metadata() -> NftMetadata {
+        let mut metadata =
+            NftMetadata::new("Example", "ipfs://bafkreiexample/image.png", "image/png");
+
+        metadata.attributes.push(NftMetadataAttribute {
+            trait_type: "color".to_owned(),
+            display_type: Some("color".to_owned()),
+            value: json!("#ff0000"),
+            max_value: None,
+        });
+
+        metadata
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let metadata = make_metadata();
+
+        let bytes = metadata.to_json_bytes().unwrap();
+
+        assert_eq!(NftMetadata::from_json_bytes(&bytes).unwrap(), metadata);
+    }
+
+    #[test]
+    fn parses_hip412_keys() {
+        let metadata = NftMetadata::from_json_bytes(
+            br#"{"name":"a","creatorDID":"did:x","image":"ipfs://x","type":"image/png","files":[{"uri":"ipfs://y","type":"video/mp4","is_default_file":true}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(metadata.creator_did.as_deref(), Some("did:x"));
+        assert_eq!(metadata.files[0].is_default_file, Some(true));
+    }
+
+    #[test]
+    fn rejects_missing_name() {
+        let mut metadata = make_metadata();
+        metadata.name.clear();
+
+        assert_matches!(
+            metadata.validate(),
+            Err(Error::NftMetadata(NftMetadataError::MissingField(field))) if field == "name"
+        );
+    }
+
+    #[test]
+    fn rejects_bad_attribute_value() {
+        let mut metadata = make_metadata();
+        metadata.attributes[0].value = json!([1, 2]);
+
+        assert_matches!(
+            metadata.validate(),
+            Err(Error::NftMetadata(NftMetadataError::InvalidField { .. }))
+        );
+    }
+
+    #[test]
+    fn uri_too_long() {
+        let uri = format!("ipfs://{}", "a".repeat(100));
+
+        assert_matches!(
+            NftMetadata::uri_to_mint_metadata(&uri),
+            Err(Error::NftMetadata(NftMetadataError::TooLong { len: 107, max: 100 }))
+        );
+    }
+
+    #[test]
+    fn on_chain_uri() {
+        let bytes = NftMetadata::uri_to_mint_metadata("ipfs://bafkreiexample").unwrap();
+
+        assert_eq!(
+            OnChainNftMetadata::from_bytes(&bytes).unwrap(),
+            OnChainNftMetadata::Uri("ipfs://bafkreiexample".to_owned())
+        );
+    }
+}
@@ -18,8 +18,6 @@
  * ‍
  */
 
-use std::net::SocketAddrV4;
-
 use hedera_proto::services;
 
 use crate::protobuf::ToProtobuf;
@@ -27,23 +25,9 @@ use crate::{
     AccountId,
     Error,
     FromProtobuf,
+    ServiceEndpoint,
 };
 
-fn parse_socket_addr_v4(ip: Vec<u8>, port: i32) -> crate::Result<SocketAddrV4> {
-    let octets: Result<[u8; 4], _> = ip.try_into();
-    let octets = octets.map_err(|v| {
-        Error::from_protobuf(format!("expected 4 byte ip address, got `{}` bytes", v.len()))
-    })?;
-
-    let port = u16::try_from(port).map_err(|_| {
-        Error::from_protobuf(format!(
-            "expected 16 bit non-negative port number, but the port was actually `{port}`",
-        ))
-    })?;
-
-    Ok(SocketAddrV4::new(octets.into(), port))
-}
-
 /// The data about a node, including its service endpoints and the Hedera account to be paid for
 /// services provided by the node (that is, queries answered and transactions submitted.).
 #[derive(Debug, Clone)]
@@ -66,8 +50,8 @@ pub struct NodeAddress {
     /// Its value can be used to verify the node's certificate it presents during TLS negotiations.
     pub tls_certificate_hash: Vec<u8>,
 
-    /// A node's service IP addresses and ports.
-    pub service_endpoints: Vec<SocketAddrV4>,
+    /// A node's service endpoints, each either an IP address or a domain name, and a port.
+    pub service_endpoints: Vec<ServiceEndpoint>,
 
     /// A description of the node, up to 100 bytes.
     pub description: String,
@@ -84,11 +68,15 @@ impl FromProtobuf<services::NodeAddress> for NodeAddress {
         // `ip_address`/`portno` are deprecated, but lets handle them anyway.
         #[allow(deprecated)]
         if !pb.ip_address.is_empty() {
-            addresses.push(parse_socket_addr_v4(pb.ip_address, pb.portno)?);
+            addresses.push(ServiceEndpoint::from_protobuf(services::ServiceEndpoint {
+                ip_address_v4: pb.ip_address,
+                port: pb.portno,
+                domain_name: String::new(),
+            })?);
         }
 
         for address in pb.service_endpoint {
-            addresses.push(parse_socket_addr_v4(address.ip_address_v4, address.port)?);
+            addresses.push(ServiceEndpoint::from_protobuf(address)?);
         }
 
         let node_account_id = AccountId::from_protobuf(pb_getf!(pb, node_account_id)?)?;
@@ -108,15 +96,7 @@ impl ToProtobuf for NodeAddress {
     type Protobuf = services::NodeAddress;
 
     fn to_protobuf(&self) -> Self::Protobuf {
-        let service_endpoint = self
-            .service_endpoints
-            .iter()
-            .map(|it| services::ServiceEndpoint {
-                ip_address_v4: it.ip().octets().to_vec(),
-                port: i32::from(it.port()),
-                domain_name: "".to_owned(),
-            })
-            .collect();
+        let service_endpoint = self.service_endpoints.to_protobuf();
 
         services::NodeAddress {
             rsa_pub_key: hex::encode(&self.rsa_public_key),
@@ -0,0 +1,229 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::{
+    fs,
+    io,
+};
+
+use time::OffsetDateTime;
+
+use crate::{
+    atomic_file,
+    Error,
+    LedgerId,
+    NodeAddressBook,
+};
+
+/// A [`NodeAddressBook`] saved by a client, along with when it was saved and the network it's for.
+///
+/// See [`Client::set_address_book_store`](crate::Client::set_address_book_store).
+#[derive(Debug, Clone)]
+pub struct CachedAddressBook {
+    address_book: NodeAddressBook,
+    ledger_id: Option<LedgerId>,
+    saved_at: OffsetDateTime,
+}
+
+impl CachedAddressBook {
+    /// Create a new `CachedAddressBook` of `address_book` for the network `ledger_id`, saved now.
+    #[must_use]
+    pub fn new(address_book: NodeAddressBook, ledger_id: Option<LedgerId>) -> Self {
+        Self::with_saved_at(address_book, ledger_id, OffsetDateTime::now_utc())
+    }
+
+    /// Create a new `CachedAddressBook` of `address_book` for the network `ledger_id`, saved at `saved_at`.
+    ///
+    /// Stores use this to return an address book with the time it was originally saved.
+    #[must_use]
+    pub fn with_saved_at(
+        address_book: NodeAddressBook,
+        ledger_id: Option<LedgerId>,
+        saved_at: OffsetDateTime,
+    ) -> Self {
+        Self { address_book, ledger_id, saved_at }
+    }
+
+    /// Returns the cached address book.
+    #[must_use]
+    pub fn address_book(&self) -> &NodeAddressBook {
+        &self.address_book
+    }
+
+    /// Returns the ledger ID of the network the address book is for, if it was known when it was saved.
+    #[must_use]
+    pub fn ledger_id(&self) -> Option<&LedgerId> {
+        self.ledger_id.as_ref()
+    }
+
+    /// Returns the time the address book was saved.
+    #[must_use]
+    pub fn saved_at(&self) -> OffsetDateTime {
+        self.saved_at
+    }
+}
+
+/// Somewhere a client saves the latest [`NodeAddressBook`] it received, and loads it from on startup.
+///
+/// See [`Client::set_address_book_store`](crate::Client::set_address_book_store).
+pub trait AddressBookStore: Send + Sync {
+    /// Returns the address book stored for the network `ledger_id`, if there is one.
+    ///
+    /// # Errors
+    /// - [`Error::AddressBookStore`] if the address book can't be read.
+    fn load(&self, ledger_id: Option<&LedgerId>) -> crate::Result<Option<CachedAddressBook>>;
+
+    /// Stores `address_book`, replacing any previous one for the same network.
+    ///
+    /// # Errors
+    /// - [`Error::AddressBookStore`] if the address book can't be written.
+    fn save(&self, address_book: &CachedAddressBook) -> crate::Result<()>;
+}
+
+/// An [`AddressBookStore`] that keeps a file per network in a directory.
+///
+/// Each file holds the protobuf encoding of the address book (see [`NodeAddressBook::to_bytes`]),
+/// and the time it was saved is the time the file was last modified.
+#[derive(Debug, Clone)]
+pub struct FileAddressBookStore {
+    directory: PathBuf,
+}
+
+impl FileAddressBookStore {
+    /// Create a new `FileAddressBookStore` keeping its files in `directory`.
+    ///
+    /// The directory is created when the first address book is saved.
+    #[must_use]
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self { directory: directory.into() }
+    }
+
+    /// Returns the directory address books are kept in.
+    #[must_use]
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    fn path(&self, ledger_id: Option<&LedgerId>) -> PathBuf {
+        match ledger_id {
+            Some(ledger_id) => self.directory.join(format!("{ledger_id}.address-book")),
+            None => self.directory.join("unknown.address-book"),
+        }
+    }
+}
+
+impl AddressBookStore for FileAddressBookStore {
+    fn load(&self, ledger_id: Option<&LedgerId>) -> crate::Result<Option<CachedAddressBook>> {
+        let path = self.path(ledger_id);
+
+        let read = || -> io::Result<(Vec<u8>, OffsetDateTime)> {
+            let bytes = fs::read(&path)?;
+            let saved_at = fs::metadata(&path)?.modified()?;
+
+            Ok((bytes, saved_at.into()))
+        };
+
+        let (bytes, saved_at) = match read() {
+            Ok(it) => it,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(Error::AddressBookStore(e.into())),
+        };
+
+        Ok(Some(CachedAddressBook::with_saved_at(
+            NodeAddressBook::from_bytes(&bytes)?,
+            ledger_id.cloned(),
+            saved_at,
+        )))
+    }
+
+    fn save(&self, address_book: &CachedAddressBook) -> crate::Result<()> {
+        atomic_file::write(
+            &self.path(address_book.ledger_id()),
+            &address_book.address_book().to_bytes(),
+        )
+        .map_err(|e| Error::AddressBookStore(e.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::OffsetDateTime;
+
+    use super::{
+        AddressBookStore,
+        CachedAddressBook,
+        FileAddressBookStore,
+    };
+    use crate::{
+        AccountId,
+        LedgerId,
+        NodeAddress,
+        NodeAddressBook,
+    };
+
+    fn make_address_book() -> NodeAddressBook {
+        NodeAddressBook {
+            node_addresses: vec![NodeAddress {
+                node_id: 0,
+                rsa_public_key: Vec::new(),
+                node_account_id: AccountId::new(0, 0, 3),
+                tls_certificate_hash: Vec::new(),
+                service_endpoints: vec!["35.237.200.180:50211".parse().unwrap()],
+                description: String::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn file_store() {
+        let directory =
+            std::env::temp_dir().join(format!("hedera-address-book-test-{}", std::process::id()));
+
+        let store = FileAddressBookStore::new(&directory);
+        let mainnet = LedgerId::mainnet();
+
+        assert!(store.load(Some(&mainnet)).unwrap().is_none());
+
+        let before = OffsetDateTime::now_utc() - time::Duration::seconds(1);
+
+        store.save(&CachedAddressBook::new(make_address_book(), Some(mainnet.clone()))).unwrap();
+
+        // the file holds just the address book.
+        assert_eq!(
+            std::fs::read(directory.join("mainnet.address-book")).unwrap(),
+            make_address_book().to_bytes()
+        );
+
+        let loaded = store.load(Some(&mainnet)).unwrap().unwrap();
+        assert_eq!(loaded.address_book().to_bytes(), make_address_book().to_bytes());
+        assert_eq!(loaded.ledger_id(), Some(&mainnet));
+        assert!(loaded.saved_at() >= before);
+
+        // other networks have their own address books.
+        assert!(store.load(Some(&LedgerId::testnet())).unwrap().is_none());
+        assert!(store.load(None).unwrap().is_none());
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -19,6 +19,7 @@
  */
 
 use futures_core::future::BoxFuture;
+use rust_decimal::Decimal;
 use time::Duration;
 
 use crate::execute::execute;
@@ -130,11 +131,30 @@ where
     /// Defaults to the maximum payment amount configured on the client.
     ///
     /// Sets to `None` to allow unlimited payment amounts.
+    ///
+    /// This replaces a maximum set with [`max_payment_amount_usd_cents`](Self::max_payment_amount_usd_cents).
     pub fn max_payment_amount(&mut self, max: impl Into<Option<Hbar>>) -> &mut Self {
         self.payment.max_amount(max);
         self
     }
 
+    /// Returns the maximum payment allowable for this query, in cents (USD).
+    #[must_use]
+    pub fn get_max_payment_amount_usd_cents(&self) -> Option<Decimal> {
+        self.payment.get_max_amount_usd_cents()
+    }
+
+    /// Sets the maximum payment allowable for this query, in cents (USD).
+    ///
+    /// The maximum is converted to hbar each time the query is executed, at the client's cached
+    /// [`ExchangeRates`](crate::ExchangeRates), which are refreshed once the current rate expires.
+    ///
+    /// This replaces a maximum set with [`max_payment_amount`](Self::max_payment_amount).
+    pub fn max_payment_amount_usd_cents(&mut self, cents: Decimal) -> &mut Self {
+        self.payment.max_amount_usd_cents(cents);
+        self
+    }
+
     /// Returns the duration that the payment transaction is valid for, once finalized and signed.
     #[must_use]
     pub fn get_payment_transaction_valid_duration(&self) -> Option<Duration> {
@@ -260,17 +280,31 @@ where
             }
         }
 
+        if self.data.is_payment_required() && self.needs_exchange_rates(client) {
+            client.refresh_exchange_rates().await?;
+        }
+
         if self.payment.get_amount().is_none() && self.data.is_payment_required() {
             // should this inherit the timeout?
             // payment is required but none was specified, query the cost
             let cost = QueryCost::new(self).execute(client, None).await?;
 
-            if self.payment.get_max_amount().is_none() {
-                // N.B. This can still be `None`.
-                self.payment.max_amount(client.default_max_query_payment());
-            }
-
-            if let Some(max_amount) = self.payment.get_max_amount() {
+            let max_amount =
+                match (self.payment.get_max_amount(), self.payment.get_max_amount_usd_cents()) {
+                    (Some(max_amount), _) => Some(max_amount),
+                    // not stored, the rate may be different the next time this query is executed.
+                    (None, Some(cents)) => Some(client.usd_cents_to_hbar(cents)?),
+                    (None, None) => match client.default_max_query_payment() {
+                        Some(max_amount) => {
+                            self.payment.max_amount(max_amount);
+                            Some(max_amount)
+                        }
+                        // N.B. This can still be `None`.
+                        None => client.resolved_default_max_query_payment()?,
+                    },
+                };
+
+            if let Some(max_amount) = max_amount {
                 if cost > max_amount {
                     return Err(Error::MaxQueryPaymentExceeded {
                         query_cost: cost,
@@ -289,6 +323,19 @@ where
         execute(client, self, timeout).await
     }
 
+    /// Returns whether executing `self` converts a maximum in cents (USD) to hbar.
+    fn needs_exchange_rates(&self, client: &Client) -> bool {
+        let max_amount_in_cents = self.payment.get_amount().is_none()
+            && self.payment.get_max_amount().is_none()
+            && (self.payment.get_max_amount_usd_cents().is_some()
+                || client.default_max_query_payment_usd_cents().is_some());
+
+        let payment_fee_in_cents = self.payment.get_max_transaction_fee().is_none()
+            && client.default_max_transaction_fee_usd_cents().is_some();
+
+        max_amount_in_cents || payment_fee_in_cents
+    }
+
     /// Execute this query against the provided client of the Hedera network.
     // todo:
     #[allow(clippy::missing_errors_doc)]
@@ -20,6 +20,7 @@
 
 use hedera_proto::services;
 use hedera_proto::services::crypto_service_client::CryptoServiceClient;
+use rust_decimal::Decimal;
 use tonic::transport::Channel;
 
 use crate::transaction::{
@@ -44,6 +45,7 @@ pub type PaymentTransaction = Transaction<PaymentTransactionData>;
 pub struct PaymentTransactionData {
     amount: Option<Hbar>,
     max_amount: Option<Hbar>,
+    max_amount_usd_cents: Option<Decimal>,
 }
 
 impl PaymentTransaction {
@@ -61,7 +63,20 @@ impl PaymentTransaction {
     }
 
     pub(super) fn max_amount(&mut self, amount: impl Into<Option<Hbar>>) -> &mut Self {
-        self.data_mut().max_amount = amount.into();
+        let data = self.data_mut();
+        data.max_amount = amount.into();
+        data.max_amount_usd_cents = None;
+        self
+    }
+
+    pub(super) fn get_max_amount_usd_cents(&self) -> Option<Decimal> {
+        self.data().max_amount_usd_cents
+    }
+
+    pub(super) fn max_amount_usd_cents(&mut self, cents: Decimal) -> &mut Self {
+        let data = self.data_mut();
+        data.max_amount = None;
+        data.max_amount_usd_cents = Some(cents);
         self
     }
 }
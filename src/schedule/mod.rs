@@ -21,6 +21,7 @@
 mod schedulable_transaction_body;
 mod schedule_create_transaction;
 mod schedule_delete_transaction;
+mod schedule_flow;
 mod schedule_id;
 mod schedule_info;
 mod schedule_info_query;
@@ -30,6 +31,10 @@ pub use schedule_create_transaction::ScheduleCreateTransaction;
 pub(crate) use schedule_create_transaction::ScheduleCreateTransactionData;
 pub use schedule_delete_transaction::ScheduleDeleteTransaction;
 pub(crate) use schedule_delete_transaction::ScheduleDeleteTransactionData;
+pub use schedule_flow::{
+    ScheduleFlow,
+    ScheduleFlowStatus,
+};
 pub use schedule_id::ScheduleId;
 pub use schedule_info::ScheduleInfo;
 pub use schedule_info_query::ScheduleInfoQuery;
@@ -76,6 +76,16 @@ impl ScheduleCreateTransaction {
     //     self.data().scheduled_transaction.as_ref()
     // }
 
+    /// Returns the scheduled transaction's data as protobuf, if there is one.
+    pub(super) fn scheduled_transaction_data_protobuf(
+        &self,
+    ) -> Option<services::schedulable_transaction_body::Data> {
+        self.data()
+            .scheduled_transaction
+            .as_ref()
+            .map(|it| it.data.to_schedulable_transaction_data_protobuf())
+    }
+
     /// Sets the scheduled transaction.
     ///
     /// # Panics
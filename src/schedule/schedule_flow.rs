@@ -0,0 +1,614 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::time::Duration;
+
+use hedera_proto::services;
+use time::OffsetDateTime;
+use tokio::time::sleep;
+
+use super::{
+    ScheduleCreateTransaction,
+    ScheduleId,
+    ScheduleInfo,
+    ScheduleInfoQuery,
+    ScheduleSignTransaction,
+};
+use crate::signer::AnySigner;
+use crate::transaction::TransactionExecute;
+use crate::{
+    AccountId,
+    AccountInfoQuery,
+    Client,
+    Error,
+    FromProtobuf,
+    Key,
+    PrivateKey,
+    PublicKey,
+    Status,
+    Transaction,
+    TransactionReceipt,
+    TransactionReceiptQuery,
+};
+
+/// The state of a schedule after [`ScheduleFlow::submit`] created or adopted it, and signed it with the held keys.
+#[derive(Debug, Clone)]
+pub struct ScheduleFlowStatus {
+    /// The ID of the schedule.
+    pub schedule_id: ScheduleId,
+
+    /// The schedule, as of when it was last queried.
+    pub info: ScheduleInfo,
+
+    /// The keys the scheduled transaction requires signatures from, see [`ScheduleFlow::required_keys`].
+    pub required_keys: Vec<Key>,
+
+    /// The required keys that hadn't signed the schedule when it was last queried.
+    pub missing_signatories: Vec<Key>,
+}
+
+impl ScheduleFlowStatus {
+    /// Returns `true` if the scheduled transaction has been executed.
+    #[must_use]
+    pub fn is_executed(&self) -> bool {
+        self.info.executed_at.is_some()
+    }
+}
+
+/// Create a schedule, collect signatures for it, and wait for the scheduled transaction to execute.
+///
+/// The operation of this flow is as follows:
+/// 1. Execute a [`ScheduleCreateTransaction`] signed with every held key,
+///    or adopt the existing schedule if the network reports [`Status::IdenticalScheduleAlreadyCreated`].
+/// 2. Query the schedule and compare its signatories against the required keys.
+/// 3. Execute a [`ScheduleSignTransaction`] signed with every held key that hasn't signed the schedule yet.
+/// 4. Poll the schedule until it's executed, then return the scheduled transaction's receipt.
+///
+/// [`submit`](Self::submit) stops after step 3, returning the signatories the schedule is still missing.
+#[derive(Default, Debug)]
+pub struct ScheduleFlow {
+    node_account_ids: Option<Vec<AccountId>>,
+    schedule_create: ScheduleCreateTransaction,
+    required_keys: Vec<Key>,
+    signers: Vec<AnySigner>,
+    poll_interval: Option<Duration>,
+}
+
+impl ScheduleFlow {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+    // the network only evaluates expired schedules when it handles the next transaction, so give it a moment.
+    const EXPIRY_GRACE_PERIOD: time::Duration = time::Duration::seconds(10);
+
+    /// Create a new `ScheduleFlow`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the transaction to schedule.
+    ///
+    /// # Panics
+    /// panics if the transaction is not schedulable, see [`ScheduleCreateTransaction::scheduled_transaction`].
+    pub fn scheduled_transaction<D>(&mut self, transaction: Transaction<D>) -> &mut Self
+    where
+        D: TransactionExecute,
+    {
+        self.schedule_create.scheduled_transaction(transaction);
+
+        self
+    }
+
+    /// Returns the account IDs of the nodes the transactions and queries may be submitted to.
+    #[must_use]
+    pub fn get_node_account_ids(&self) -> Option<&[AccountId]> {
+        self.node_account_ids.as_deref()
+    }
+
+    /// Sets the account IDs of the nodes the transactions and queries may be submitted to.
+    pub fn node_account_ids(
+        &mut self,
+        node_account_ids: impl IntoIterator<Item = AccountId>,
+    ) -> &mut Self {
+        self.node_account_ids = Some(node_account_ids.into_iter().collect());
+
+        self
+    }
+
+    /// Returns the memo for the schedule entity.
+    #[must_use]
+    pub fn get_schedule_memo(&self) -> Option<&str> {
+        self.schedule_create.get_schedule_memo()
+    }
+
+    /// Sets the memo for the schedule entity.
+    pub fn schedule_memo(&mut self, memo: impl Into<String>) -> &mut Self {
+        self.schedule_create.schedule_memo(memo);
+
+        self
+    }
+
+    /// Returns the Hedera key which can be used to sign a `ScheduleDelete` and remove the schedule.
+    #[must_use]
+    pub fn get_admin_key(&self) -> Option<&Key> {
+        self.schedule_create.get_admin_key()
+    }
+
+    /// Sets the Hedera key which can be used to sign a `ScheduleDelete` and remove the schedule.
+    pub fn admin_key(&mut self, key: impl Into<Key>) -> &mut Self {
+        self.schedule_create.admin_key(key);
+
+        self
+    }
+
+    /// Returns the id of the account to be charged the service fee for the scheduled transaction.
+    #[must_use]
+    pub fn get_payer_account_id(&self) -> Option<AccountId> {
+        self.schedule_create.get_payer_account_id()
+    }
+
+    /// Sets the id of the account to be charged the service fee for the scheduled transaction.
+    pub fn payer_account_id(&mut self, id: AccountId) -> &mut Self {
+        self.schedule_create.payer_account_id(id);
+
+        self
+    }
+
+    /// Returns the timestamp for when the transaction should be evaluated for execution and then expire.
+    #[must_use]
+    pub fn get_expiration_time(&self) -> Option<OffsetDateTime> {
+        self.schedule_create.get_expiration_time()
+    }
+
+    /// Sets the timestamp for when the transaction should be evaluated for execution and then expire.
+    pub fn expiration_time(&mut self, time: OffsetDateTime) -> &mut Self {
+        self.schedule_create.expiration_time(time);
+
+        self
+    }
+
+    /// Returns `true` if the transaction will be evaluated at `expiration_time` instead
+    /// of when all the required signatures are received, `false` otherwise.
+    #[must_use]
+    pub fn get_wait_for_expiry(&self) -> bool {
+        self.schedule_create.get_wait_for_expiry()
+    }
+
+    /// Sets if the transaction will be evaluated for execution at `expiration_time` instead
+    /// of when all required signatures are received.
+    pub fn wait_for_expiry(&mut self, wait: bool) -> &mut Self {
+        self.schedule_create.wait_for_expiry(wait);
+
+        self
+    }
+
+    /// Returns the keys the scheduled transaction requires signatures from.
+    #[must_use]
+    pub fn get_required_keys(&self) -> &[Key] {
+        &self.required_keys
+    }
+
+    /// Sets the keys the scheduled transaction requires signatures from.
+    ///
+    /// These are only used to report which signatories are missing,
+    /// the network decides on its own whether the schedule has enough signatures.
+    ///
+    /// Defaults to the keys of the accounts the scheduled transaction needs signatures from,
+    /// as far as can be told from the transaction itself:
+    /// the payer, and for transfers, every account that sends hbar, tokens, or NFTs without an allowance.
+    /// Set these explicitly for any other keys the transaction requires, such as the admin key of an entity it updates.
+    pub fn required_keys(&mut self, keys: impl IntoIterator<Item = Key>) -> &mut Self {
+        self.required_keys = keys.into_iter().collect();
+
+        self
+    }
+
+    /// Returns how long to wait between queries for the state of the schedule.
+    #[must_use]
+    pub fn get_poll_interval(&self) -> Duration {
+        self.poll_interval.unwrap_or(Self::DEFAULT_POLL_INTERVAL)
+    }
+
+    /// Sets how long to wait between queries for the state of the schedule.
+    ///
+    /// Defaults to 2 seconds.
+    pub fn poll_interval(&mut self, interval: Duration) -> &mut Self {
+        self.poll_interval = Some(interval);
+
+        self
+    }
+
+    /// Adds a key to sign the schedule with.
+    pub fn sign(&mut self, key: PrivateKey) -> &mut Self {
+        self.sign_signer(AnySigner::PrivateKey(key))
+    }
+
+    /// Adds a signer to sign the schedule with.
+    pub fn sign_with<F: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static>(
+        &mut self,
+        public_key: PublicKey,
+        signer: F,
+    ) -> &mut Self {
+        self.sign_signer(AnySigner::arbitrary(Box::new(public_key), signer))
+    }
+
+    fn sign_signer(&mut self, signer: AnySigner) -> &mut Self {
+        if !self.signers.iter().any(|it| it.public_key() == signer.public_key()) {
+            self.signers.push(signer);
+        }
+
+        self
+    }
+
+    /// Returns the accounts that must sign the scheduled transaction, see [`required_keys`](Self::required_keys).
+    ///
+    /// `default_payer` is the account creating the schedule, which pays for the scheduled transaction unless a payer is set.
+    fn required_accounts(&self, default_payer: Option<AccountId>) -> crate::Result<Vec<AccountId>> {
+        use services::schedulable_transaction_body::Data;
+
+        let mut accounts = Vec::new();
+
+        accounts.extend(self.get_payer_account_id().or(default_payer));
+
+        if let Some(Data::CryptoTransfer(body)) =
+            self.schedule_create.scheduled_transaction_data_protobuf()
+        {
+            let transfers = body.transfers.map(|it| it.account_amounts).unwrap_or_default();
+
+            // approved transfers are signed by the spender, which is the payer.
+            let senders = transfers
+                .into_iter()
+                .chain(body.token_transfers.iter().flat_map(|it| it.transfers.clone()))
+                .filter(|it| it.amount < 0 && !it.is_approval)
+                .filter_map(|it| it.account_id)
+                .chain(
+                    body.token_transfers
+                        .iter()
+                        .flat_map(|it| &it.nft_transfers)
+                        .filter(|it| !it.is_approval)
+                        .filter_map(|it| it.sender_account_id.clone()),
+                );
+
+            for sender in senders {
+                accounts.push(AccountId::from_protobuf(sender)?);
+            }
+        }
+
+        let mut unique = Vec::with_capacity(accounts.len());
+        for account in accounts {
+            if !unique.contains(&account) {
+                unique.push(account);
+            }
+        }
+
+        Ok(unique)
+    }
+
+    async fn effective_required_keys(
+        &self,
+        client: &Client,
+        timeout: Option<Duration>,
+    ) -> crate::Result<Vec<Key>> {
+        if !self.required_keys.is_empty() {
+            return Ok(self.required_keys.clone());
+        }
+
+        let mut keys = Vec::new();
+
+        for account_id in self.required_accounts(client.get_operator_account_id())? {
+            let mut query = AccountInfoQuery::new();
+
+            query.account_id(account_id);
+
+            if let Some(node_account_ids) = &self.node_account_ids {
+                query.node_account_ids(node_account_ids.iter().copied());
+            }
+
+            keys.push(query.execute_with_optional_timeout(client, timeout).await?.key);
+        }
+
+        Ok(keys)
+    }
+
+    /// Creates or adopts the schedule, signs it, and waits for the scheduled transaction to execute.
+    ///
+    /// # Errors
+    /// - [`Error::ReceiptStatus`] if the schedule couldn't be created or the scheduled transaction failed.
+    /// - [`Error::ScheduleNotExecuted`] if the schedule expired or was deleted before it was executed.
+    pub async fn execute(&self, client: &Client) -> crate::Result<TransactionReceipt> {
+        self.execute_with_optional_timeout(client, None).await
+    }
+
+    /// Creates or adopts the schedule, signs it, and waits for the scheduled transaction to execute.
+    ///
+    /// # Errors
+    /// - [`Error::ReceiptStatus`] if the schedule couldn't be created or the scheduled transaction failed.
+    /// - [`Error::ScheduleNotExecuted`] if the schedule expired or was deleted before it was executed.
+    pub async fn execute_with_timeout(
+        &self,
+        client: &Client,
+        timeout_per_request: Duration,
+    ) -> crate::Result<TransactionReceipt> {
+        self.execute_with_optional_timeout(client, Some(timeout_per_request)).await
+    }
+
+    async fn execute_with_optional_timeout(
+        &self,
+        client: &Client,
+        timeout: Option<Duration>,
+    ) -> crate::Result<TransactionReceipt> {
+        let mut status = self.submit_with_optional_timeout(client, timeout).await?;
+
+        while !status.is_executed() {
+            let expired = status
+                .info
+                .expiration_time
+                .map_or(false, |it| OffsetDateTime::now_utc() > it + Self::EXPIRY_GRACE_PERIOD);
+
+            if status.info.deleted_at.is_some() || expired {
+                return Err(Error::ScheduleNotExecuted {
+                    schedule_id: status.schedule_id,
+                    missing_signatories: status.missing_signatories,
+                });
+            }
+
+            sleep(self.get_poll_interval()).await;
+
+            status.info = self.query_info(client, status.schedule_id, timeout).await?;
+            status.missing_signatories = status.info.missing_signatories(&status.required_keys);
+        }
+
+        let mut query = TransactionReceiptQuery::new();
+
+        query.transaction_id(status.info.scheduled_transaction_id);
+
+        if let Some(node_account_ids) = &self.node_account_ids {
+            query.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        query.execute_with_optional_timeout(client, timeout).await
+    }
+
+    /// Creates or adopts the schedule and signs it, without waiting for the scheduled transaction to execute.
+    ///
+    /// The returned status lists the signatories the schedule is still missing.
+    ///
+    /// # Errors
+    /// - [`Error::ReceiptStatus`] if the schedule couldn't be created.
+    pub async fn submit(&self, client: &Client) -> crate::Result<ScheduleFlowStatus> {
+        self.submit_with_optional_timeout(client, None).await
+    }
+
+    /// Creates or adopts the schedule and signs it, without waiting for the scheduled transaction to execute.
+    ///
+    /// The returned status lists the signatories the schedule is still missing.
+    ///
+    /// # Errors
+    /// - [`Error::ReceiptStatus`] if the schedule couldn't be created.
+    pub async fn submit_with_timeout(
+        &self,
+        client: &Client,
+        timeout_per_request: Duration,
+    ) -> crate::Result<ScheduleFlowStatus> {
+        self.submit_with_optional_timeout(client, Some(timeout_per_request)).await
+    }
+
+    async fn submit_with_optional_timeout(
+        &self,
+        client: &Client,
+        timeout: Option<Duration>,
+    ) -> crate::Result<ScheduleFlowStatus> {
+        let schedule_id = self.create_or_adopt(client, timeout).await?;
+
+        let required_keys = self.effective_required_keys(client, timeout).await?;
+
+        let mut info = self.query_info(client, schedule_id, timeout).await?;
+
+        let unsigned: Vec<_> = self
+            .signers
+            .iter()
+            .filter(|it| !info.signatories.keys.contains(&Key::Single(it.public_key())))
+            .cloned()
+            .collect();
+
+        if info.executed_at.is_none() && info.deleted_at.is_none() && !unsigned.is_empty() {
+            let mut transaction = ScheduleSignTransaction::new();
+
+            transaction.schedule_id(schedule_id);
+
+            if let Some(node_account_ids) = &self.node_account_ids {
+                transaction.node_account_ids(node_account_ids.iter().copied());
+            }
+
+            for signer in unsigned {
+                transaction.sign_signer(signer);
+            }
+
+            transaction
+                .execute_with_optional_timeout(client, timeout)
+                .await?
+                .get_receipt_query()
+                .execute_with_optional_timeout(client, timeout)
+                .await?;
+
+            info = self.query_info(client, schedule_id, timeout).await?;
+        }
+
+        let missing_signatories = info.missing_signatories(&required_keys);
+
+        Ok(ScheduleFlowStatus { schedule_id, info, required_keys, missing_signatories })
+    }
+
+    async fn create_or_adopt(
+        &self,
+        client: &Client,
+        timeout: Option<Duration>,
+    ) -> crate::Result<ScheduleId> {
+        let mut transaction = self.schedule_create.clone();
+
+        if let Some(node_account_ids) = &self.node_account_ids {
+            transaction.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        for signer in &self.signers {
+            transaction.sign_signer(signer.clone());
+        }
+
+        let response = transaction.execute_with_optional_timeout(client, timeout).await?;
+
+        let receipt = response
+            .get_receipt_query()
+            .validate_status(false)
+            .execute_with_optional_timeout(client, timeout)
+            .await?;
+
+        match receipt.status {
+            Status::Success | Status::IdenticalScheduleAlreadyCreated => {}
+            status => {
+                return Err(Error::ReceiptStatus {
+                    status,
+                    transaction_id: Some(Box::new(response.transaction_id)),
+                })
+            }
+        }
+
+        receipt.schedule_id.ok_or_else(|| {
+            Error::from_protobuf("receipt for `ScheduleCreateTransaction` had no schedule ID")
+        })
+    }
+
+    async fn query_info(
+        &self,
+        client: &Client,
+        schedule_id: ScheduleId,
+        timeout: Option<Duration>,
+    ) -> crate::Result<ScheduleInfo> {
+        let mut query = ScheduleInfoQuery::new();
+
+        query.schedule_id(schedule_id);
+
+        if let Some(node_account_ids) = &self.node_account_ids {
+            query.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        query.execute_with_optional_timeout(client, timeout).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::transaction::test_helpers::unused_private_key;
+    use crate::{
+        AccountDeleteTransaction,
+        AccountId,
+        Hbar,
+        Key,
+        NftId,
+        PrivateKey,
+        ScheduleFlow,
+        TokenId,
+        TransferTransaction,
+    };
+
+    #[test]
+    fn get_set() {
+        let mut transfer = TransferTransaction::new();
+        transfer
+            .hbar_transfer(AccountId::new(0, 0, 2), Hbar::new(-1))
+            .hbar_transfer(AccountId::new(0, 0, 3), Hbar::new(1));
+
+        let mut flow = ScheduleFlow::new();
+        flow.scheduled_transaction(transfer)
+            .schedule_memo("memo")
+            .wait_for_expiry(true)
+            .poll_interval(Duration::from_millis(500))
+            .node_account_ids([AccountId::new(0, 0, 3)]);
+
+        assert_eq!(flow.get_schedule_memo(), Some("memo"));
+        assert_eq!(flow.get_wait_for_expiry(), true);
+        assert_eq!(flow.get_poll_interval(), Duration::from_millis(500));
+        assert_eq!(flow.get_node_account_ids(), Some(&[AccountId::new(0, 0, 3)][..]));
+    }
+
+    #[test]
+    fn required_accounts_of_transfer() -> crate::Result<()> {
+        let mut transfer = TransferTransaction::new();
+        transfer
+            .hbar_transfer(AccountId::new(0, 0, 2), Hbar::new(-1))
+            .hbar_transfer(AccountId::new(0, 0, 3), Hbar::new(1))
+            .token_transfer(TokenId::new(0, 0, 7), AccountId::new(0, 0, 4), -10)
+            .token_transfer(TokenId::new(0, 0, 7), AccountId::new(0, 0, 3), 10)
+            .approved_hbar_transfer(AccountId::new(0, 0, 5), Hbar::new(-1))
+            .hbar_transfer(AccountId::new(0, 0, 3), Hbar::new(1))
+            .nft_transfer(
+                NftId { token_id: TokenId::new(0, 0, 8), serial: 1 },
+                AccountId::new(0, 0, 6),
+                AccountId::new(0, 0, 3),
+            );
+
+        let mut flow = ScheduleFlow::new();
+        flow.scheduled_transaction(transfer);
+
+        assert_eq!(
+            flow.required_accounts(Some(AccountId::new(0, 0, 1001)))?,
+            [
+                AccountId::new(0, 0, 1001),
+                AccountId::new(0, 0, 2),
+                AccountId::new(0, 0, 4),
+                AccountId::new(0, 0, 6)
+            ]
+        );
+
+        flow.payer_account_id(AccountId::new(0, 0, 2));
+
+        assert_eq!(
+            flow.required_accounts(Some(AccountId::new(0, 0, 1001)))?,
+            [AccountId::new(0, 0, 2), AccountId::new(0, 0, 4), AccountId::new(0, 0, 6)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn required_accounts_of_other_transactions() -> crate::Result<()> {
+        let mut flow = ScheduleFlow::new();
+        flow.scheduled_transaction(AccountDeleteTransaction::new());
+
+        assert_eq!(
+            flow.required_accounts(Some(AccountId::new(0, 0, 1001)))?,
+            [AccountId::new(0, 0, 1001)]
+        );
+        assert!(flow.required_accounts(None)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn explicit_required_keys() {
+        let key = PrivateKey::generate_ed25519().public_key();
+
+        let mut flow = ScheduleFlow::new();
+        flow.sign(unused_private_key()).required_keys([Key::Single(key)]);
+
+        assert_eq!(flow.get_required_keys(), [Key::Single(key)]);
+    }
+}
@@ -108,11 +108,25 @@ impl ScheduleInfo {
                 operator: None,
                 is_frozen: true,
                 regenerate_transaction_id: Some(false),
+                batch_key: None,
+                custom_fee_limits: Vec::new(),
             },
             Vec::new(),
         ))
     }
 
+    /// Returns the keys in `required_keys` that aren't satisfied by the [`signatories`](Self::signatories) so far.
+    ///
+    /// A [`KeyList`] is satisfied when at least `threshold` (or all, if there's no threshold) of its keys are.
+    #[must_use]
+    pub fn missing_signatories(&self, required_keys: &[Key]) -> Vec<Key> {
+        required_keys
+            .iter()
+            .filter(|key| !key.is_satisfied_by(&self.signatories.keys))
+            .cloned()
+            .collect()
+    }
+
     /// Convert `self` to a protobuf-encoded [`Vec<u8>`].
     #[must_use]
     pub fn to_bytes(&self) -> Vec<u8> {
@@ -226,7 +240,10 @@ mod tests {
     use crate::transaction::ToSchedulableTransactionDataProtobuf;
     use crate::{
         AccountDeleteTransaction,
+        Key,
+        KeyList,
         LedgerId,
+        PrivateKey,
         ScheduleInfo,
         TransactionId,
     };
@@ -639,4 +656,20 @@ mod tests {
         "#]]
         .assert_debug_eq(&make_deleted_info().to_protobuf());
     }
+
+    #[test]
+    fn missing_signatories() {
+        let signed = unused_private_key().public_key();
+        let unsigned = PrivateKey::generate_ed25519().public_key();
+
+        let info = make_info();
+
+        let one_of_two = KeyList { keys: vec![signed.into(), unsigned.into()], threshold: Some(1) };
+        let all_of_two = KeyList::from([signed, unsigned]);
+
+        let required: [Key; 4] =
+            [signed.into(), unsigned.into(), one_of_two.into(), all_of_two.clone().into()];
+
+        assert_eq!(info.missing_signatories(&required), [Key::from(unsigned), all_of_two.into()]);
+    }
 }
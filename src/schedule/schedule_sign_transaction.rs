@@ -71,7 +71,7 @@ impl TransactionExecute for ScheduleSignTransactionData {
         channel: Channel,
         request: services::Transaction,
     ) -> BoxGrpcFuture<'_, services::TransactionResponse> {
-        Box::pin(async { ScheduleServiceClient::new(channel).delete_schedule(request).await })
+        Box::pin(async { ScheduleServiceClient::new(channel).sign_schedule(request).await })
     }
 }
 
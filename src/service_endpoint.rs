@@ -18,10 +18,16 @@
  * ‍
  */
 
+use std::fmt;
 use std::net::{
+    IpAddr,
     Ipv4Addr,
+    Ipv6Addr,
+    SocketAddr,
     SocketAddrV4,
+    SocketAddrV6,
 };
+use std::str::FromStr;
 
 use hedera_proto::services;
 
@@ -31,77 +37,240 @@ use crate::{
     FromProtobuf,
 };
 
-fn parse_socket_addr_v4(ip: Vec<u8>, port: i32) -> crate::Result<SocketAddrV4> {
-    let octets: Result<[u8; 4], _> = ip.try_into();
-    let octets = octets.map_err(|v| {
-        Error::from_protobuf(format!("expected 4 byte ip address, got `{}` bytes", v.len()))
-    })?;
+fn parse_ip_address(ip: &[u8]) -> crate::Result<IpAddr> {
+    if let Ok(octets) = <[u8; 4]>::try_from(ip) {
+        return Ok(IpAddr::from(octets));
+    }
 
-    let port = u16::try_from(port).map_err(|_| {
-        Error::from_protobuf(format!(
-            "expected 16 bit non-negative port number, but the port was actually `{port}`",
-        ))
-    })?;
+    if let Ok(octets) = <[u8; 16]>::try_from(ip) {
+        return Ok(IpAddr::from(octets));
+    }
 
-    Ok(SocketAddrV4::new(octets.into(), port))
+    Err(Error::from_protobuf(format!("expected 4 or 16 byte ip address, got `{}` bytes", ip.len())))
 }
 
-fn validate_domain_name(domain_name: String) -> crate::Result<()> {
+/// Checks that `domain_name` is a fully qualified domain name (RFC 1035, section 2.3.1), optionally ending in a `.`.
+///
+/// Returns why it isn't one otherwise.
+pub(crate) fn validate_domain_name(domain_name: &str) -> Result<(), &'static str> {
     if domain_name.len() > 253 {
-        return Err(Error::from_protobuf("Domain name exceeds 253 characters"));
+        return Err("is longer than 253 characters");
     }
 
-    // Check for valid characters and format
-    if !domain_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.') {
-        return Err(Error::from_protobuf("Invalid characters in domain name"));
+    let labels: Vec<_> = domain_name.strip_suffix('.').unwrap_or(domain_name).split('.').collect();
+
+    if labels.len() < 2 {
+        return Err("must be fully qualified");
     }
 
-    // Check for valid domain name format (simplified)
-    if !domain_name.contains('.') || domain_name.starts_with('.') || domain_name.ends_with('.') {
-        return Err(Error::from_protobuf("Invalid domain name format"));
+    let valid_label = |label: &str| {
+        (1..=63).contains(&label.len())
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+    };
+
+    if !labels.iter().all(|it| valid_label(it)) {
+        return Err("isn't a valid domain name");
+    }
+
+    // a name made of numbers is an IP address that was put in the wrong field.
+    if labels.iter().all(|label| label.chars().all(|c| c.is_ascii_digit())) {
+        return Err("is an IP address, so it must be set as one");
     }
 
     Ok(())
 }
 
-/// Contains the IP address, the port, and the domain name representing a service endpoint of
-/// a Node in a network. Used to reach the Hedera API and submit transactions
-/// to the network.
-#[derive(Debug, Clone, PartialEq)]
+/// The host part of a [`ServiceEndpoint`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EndpointAddress {
+    /// An IPv4 or IPv6 address.
+    Ip(IpAddr),
+
+    /// The fully qualified domain (DNS) name of the node.
+    ///
+    /// This value MUST NOT be more than 253 characters.
+    DomainName(String),
+
+    /// Both an IP address and a domain name, as some address books list them.
+    ///
+    /// The network only accepts one or the other in node transactions, this is kept so that
+    /// decoding an address book doesn't lose either. The IP address is used to dial the node.
+    IpAndDomainName {
+        /// The IP address of the node.
+        ip: IpAddr,
+
+        /// The domain name of the node.
+        domain_name: String,
+    },
+}
+
+impl fmt::Display for EndpointAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ip(ip) | Self::IpAndDomainName { ip, .. } => ip.fmt(f),
+            Self::DomainName(name) => f.write_str(name),
+        }
+    }
+}
+
+impl From<IpAddr> for EndpointAddress {
+    fn from(ip: IpAddr) -> Self {
+        Self::Ip(ip)
+    }
+}
+
+impl From<Ipv4Addr> for EndpointAddress {
+    fn from(ip: Ipv4Addr) -> Self {
+        Self::Ip(ip.into())
+    }
+}
+
+impl From<Ipv6Addr> for EndpointAddress {
+    fn from(ip: Ipv6Addr) -> Self {
+        Self::Ip(ip.into())
+    }
+}
+
+impl From<String> for EndpointAddress {
+    /// Parses `host` as an IP address, falling back to treating it as a domain name.
+    fn from(host: String) -> Self {
+        match host.parse::<IpAddr>() {
+            Ok(ip) => Self::Ip(ip),
+            Err(_) => Self::DomainName(host),
+        }
+    }
+}
+
+impl From<&str> for EndpointAddress {
+    /// Parses `host` as an IP address, falling back to treating it as a domain name.
+    fn from(host: &str) -> Self {
+        host.to_owned().into()
+    }
+}
+
+/// The address and port of a service endpoint of a Node in a network.
+///
+/// Used to reach the Hedera API and submit transactions to the network,
+/// or for nodes to gossip with each other.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ServiceEndpoint {
-    /// The 4-byte IPv4 address of the endpoint encoded in left to right order
-    pub ip_address_v4: Option<Ipv4Addr>,
+    /// The IP address or domain name of the endpoint.
+    pub address: EndpointAddress,
 
-    /// The port of the service endpoint
-    pub port: i32,
+    /// The port of the service endpoint.
+    pub port: u16,
+}
 
-    /// A node domain name.<br/>
-    /// This MUST be the fully qualified domain(DNS) name of the node.<br/>
-    /// This value MUST NOT be more than 253 characters.
-    /// domain_name and ipAddressV4 are mutually exclusive.
-    /// When the `domain_name` field is set, the `ipAddressV4` field MUST NOT be set.<br/>
-    /// When the `ipAddressV4` field is set, the `domain_name` field MUST NOT be set.
-    pub domain_name: String,
+impl ServiceEndpoint {
+    /// Create a new `ServiceEndpoint` for the given address and port.
+    pub fn new(address: impl Into<EndpointAddress>, port: u16) -> Self {
+        Self { address: address.into(), port }
+    }
+
+    /// Returns the IP address of this endpoint, if it has one.
+    #[must_use]
+    pub fn ip(&self) -> Option<IpAddr> {
+        match &self.address {
+            EndpointAddress::Ip(ip) | EndpointAddress::IpAndDomainName { ip, .. } => Some(*ip),
+            EndpointAddress::DomainName(_) => None,
+        }
+    }
+
+    /// Returns the domain name of this endpoint, if it has one.
+    #[must_use]
+    pub fn domain_name(&self) -> Option<&str> {
+        match &self.address {
+            EndpointAddress::Ip(_) => None,
+            EndpointAddress::DomainName(name)
+            | EndpointAddress::IpAndDomainName { domain_name: name, .. } => Some(name),
+        }
+    }
 }
 
-impl FromProtobuf<services::ServiceEndpoint> for ServiceEndpoint {
-    fn from_protobuf(pb: services::ServiceEndpoint) -> crate::Result<Self> {
-        let mut port = pb.port;
-        if pb.port == 0 || pb.port == 50111 {
-            port = 50211;
+impl fmt::Display for ServiceEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.address {
+            EndpointAddress::Ip(IpAddr::V6(ip))
+            | EndpointAddress::IpAndDomainName { ip: IpAddr::V6(ip), .. } => {
+                write!(f, "[{ip}]:{}", self.port)
+            }
+            address => write!(f, "{address}:{}", self.port),
         }
+    }
+}
+
+impl FromStr for ServiceEndpoint {
+    type Err = Error;
 
-        let socket_addr_v4 = parse_socket_addr_v4(pb.ip_address_v4, port)?;
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let (host, port) = match s.strip_prefix('[') {
+            Some(rest) => rest.split_once("]:"),
+            None => s.rsplit_once(':'),
+        }
+        .ok_or_else(|| Error::basic_parse(format!("expected `host:port`, got `{s}`")))?;
+
+        let port = port.parse().map_err(Error::basic_parse)?;
+
+        // a bracketed host is always an IPv6 address.
+        if s.starts_with('[') {
+            let ip: Ipv6Addr = host.parse().map_err(Error::basic_parse)?;
+            return Ok(Self::new(ip, port));
+        }
+
+        Ok(Self::new(host, port))
+    }
+}
+
+impl From<SocketAddr> for ServiceEndpoint {
+    fn from(addr: SocketAddr) -> Self {
+        Self::new(addr.ip(), addr.port())
+    }
+}
+
+impl From<SocketAddrV4> for ServiceEndpoint {
+    fn from(addr: SocketAddrV4) -> Self {
+        Self::new(*addr.ip(), addr.port())
+    }
+}
+
+impl From<SocketAddrV6> for ServiceEndpoint {
+    fn from(addr: SocketAddrV6) -> Self {
+        Self::new(*addr.ip(), addr.port())
+    }
+}
+
+impl FromProtobuf<services::ServiceEndpoint> for ServiceEndpoint {
+    fn from_protobuf(pb: services::ServiceEndpoint) -> crate::Result<Self> {
+        let port = u16::try_from(pb.port).map_err(|_| {
+            Error::from_protobuf(format!(
+                "expected 16 bit non-negative port number, but the port was actually `{}`",
+                pb.port
+            ))
+        })?;
 
         if !pb.domain_name.is_empty() {
-            validate_domain_name(pb.domain_name.clone())?;
+            validate_domain_name(&pb.domain_name).map_err(|reason| {
+                Error::from_protobuf(format!("domain name `{}` {reason}", pb.domain_name))
+            })?;
         }
 
-        Ok(Self {
-            ip_address_v4: Some(socket_addr_v4.ip().to_owned()),
-            port: socket_addr_v4.port() as i32,
-            domain_name: pb.domain_name,
-        })
+        let address = match (pb.ip_address_v4.is_empty(), pb.domain_name.is_empty()) {
+            (false, true) => EndpointAddress::Ip(parse_ip_address(&pb.ip_address_v4)?),
+            (true, false) => EndpointAddress::DomainName(pb.domain_name),
+            (false, false) => EndpointAddress::IpAndDomainName {
+                ip: parse_ip_address(&pb.ip_address_v4)?,
+                domain_name: pb.domain_name,
+            },
+            (true, true) => {
+                return Err(Error::from_protobuf(
+                    "service endpoint has neither an ip address nor a domain name",
+                ))
+            }
+        };
+
+        Ok(Self { address, port })
     }
 }
 
@@ -109,10 +278,157 @@ impl ToProtobuf for ServiceEndpoint {
     type Protobuf = services::ServiceEndpoint;
 
     fn to_protobuf(&self) -> Self::Protobuf {
-        services::ServiceEndpoint {
-            ip_address_v4: self.ip_address_v4.unwrap().octets().to_vec(),
-            port: self.port,
-            domain_name: self.domain_name.clone(),
+        // services only accept IPv4 addresses, but the field itself is just bytes.
+        let ip_address_v4 = match self.ip() {
+            Some(IpAddr::V4(ip)) => ip.octets().to_vec(),
+            Some(IpAddr::V6(ip)) => ip.octets().to_vec(),
+            None => Vec::new(),
+        };
+
+        let domain_name = self.domain_name().unwrap_or_default().to_owned();
+
+        services::ServiceEndpoint { ip_address_v4, port: i32::from(self.port), domain_name }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{
+        Ipv4Addr,
+        Ipv6Addr,
+    };
+
+    use hedera_proto::services;
+
+    use crate::protobuf::ToProtobuf;
+    use crate::{
+        EndpointAddress,
+        FromProtobuf,
+        ServiceEndpoint,
+    };
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            "127.0.0.1:50211".parse::<ServiceEndpoint>().unwrap(),
+            ServiceEndpoint::new(Ipv4Addr::LOCALHOST, 50211)
+        );
+        assert_eq!(
+            "[::1]:50211".parse::<ServiceEndpoint>().unwrap(),
+            ServiceEndpoint::new(Ipv6Addr::LOCALHOST, 50211)
+        );
+        assert_eq!(
+            "node0.example.com:50211".parse::<ServiceEndpoint>().unwrap(),
+            ServiceEndpoint::new(
+                EndpointAddress::DomainName("node0.example.com".to_owned()),
+                50211
+            )
+        );
+
+        assert!("127.0.0.1".parse::<ServiceEndpoint>().is_err());
+        assert!("[node0.example.com]:50211".parse::<ServiceEndpoint>().is_err());
+        assert!("127.0.0.1:65536".parse::<ServiceEndpoint>().is_err());
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for s in ["127.0.0.1:50211", "[2001:db8::1]:50212", "node0.example.com:443"] {
+            assert_eq!(s.parse::<ServiceEndpoint>().unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn from_protobuf_domain_name() {
+        let endpoint = ServiceEndpoint::from_protobuf(services::ServiceEndpoint {
+            ip_address_v4: Vec::new(),
+            port: 50211,
+            domain_name: "node0.example.com".to_owned(),
+        })
+        .unwrap();
+
+        assert_eq!(endpoint.domain_name(), Some("node0.example.com"));
+        assert_eq!(endpoint.ip(), None);
+    }
+
+    #[test]
+    fn from_protobuf_validates_domain_name() {
+        let decode = |domain_name: &str| {
+            ServiceEndpoint::from_protobuf(services::ServiceEndpoint {
+                ip_address_v4: Vec::new(),
+                port: 50211,
+                domain_name: domain_name.to_owned(),
+            })
+        };
+
+        assert!(decode("node0.example.com.").is_ok());
+
+        assert!(decode("localhost").is_err());
+        assert!(decode("node_0.example.com").is_err());
+        assert!(decode("10.0.0.1").is_err());
+    }
+
+    #[test]
+    fn protobuf_round_trip_keeps_port() {
+        for port in [0, 50111, 50211] {
+            let pb = services::ServiceEndpoint {
+                ip_address_v4: vec![127, 0, 0, 1],
+                port: i32::from(port),
+                domain_name: String::new(),
+            };
+
+            let endpoint = ServiceEndpoint::from_protobuf(pb.clone()).unwrap();
+
+            assert_eq!(endpoint, ServiceEndpoint::new(Ipv4Addr::LOCALHOST, port));
+            assert_eq!(endpoint.to_protobuf(), pb);
+        }
+    }
+
+    #[test]
+    fn protobuf_round_trip_keeps_ip_and_domain_name() {
+        let pb = services::ServiceEndpoint {
+            ip_address_v4: vec![35, 237, 200, 180],
+            port: 50211,
+            domain_name: "node0.example.com".to_owned(),
+        };
+
+        let endpoint = ServiceEndpoint::from_protobuf(pb.clone()).unwrap();
+
+        assert_eq!(endpoint.ip(), Some(Ipv4Addr::new(35, 237, 200, 180).into()));
+        assert_eq!(endpoint.domain_name(), Some("node0.example.com"));
+        assert_eq!(endpoint.to_protobuf(), pb);
+    }
+
+    #[test]
+    fn from_protobuf_ipv6() {
+        let ip = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        let endpoint = ServiceEndpoint::from_protobuf(services::ServiceEndpoint {
+            ip_address_v4: ip.octets().to_vec(),
+            port: 50211,
+            domain_name: String::new(),
+        })
+        .unwrap();
+
+        assert_eq!(endpoint, ServiceEndpoint::new(ip, 50211));
+    }
+
+    #[test]
+    fn from_protobuf_empty() {
+        assert!(ServiceEndpoint::from_protobuf(services::ServiceEndpoint {
+            ip_address_v4: Vec::new(),
+            port: 50211,
+            domain_name: String::new(),
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn to_from_protobuf() {
+        for endpoint in [
+            ServiceEndpoint::new(Ipv4Addr::new(35, 237, 200, 180), 50211),
+            ServiceEndpoint::new(Ipv6Addr::LOCALHOST, 50212),
+            ServiceEndpoint::new("node0.example.com", 443),
+        ] {
+            assert_eq!(ServiceEndpoint::from_protobuf(endpoint.to_protobuf()).unwrap(), endpoint);
         }
     }
 }
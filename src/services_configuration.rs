@@ -0,0 +1,164 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use hedera_proto::services;
+
+use crate::protobuf::{
+    FromProtobuf,
+    ToProtobuf,
+};
+
+/// A list of named settings of the network, such as its
+/// [application properties](crate::FileId::APPLICATION_PROPERTIES) or [API permissions](crate::FileId::API_PERMISSIONS).
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ServicesConfiguration {
+    /// The settings, in the order they appear in the file.
+    ///
+    /// A setting can appear more than once, in which case the last value wins, which is also how the network reads them.
+    pub settings: Vec<Setting>,
+}
+
+impl ServicesConfiguration {
+    /// Create a new `ServicesConfiguration` from protobuf-encoded `bytes`.
+    ///
+    /// # Errors
+    /// - [`Error::FromProtobuf`](crate::Error::FromProtobuf) if decoding the bytes fails to produce a valid protobuf.
+    /// - [`Error::FromProtobuf`](crate::Error::FromProtobuf) if decoding the protobuf fails.
+    pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
+        FromProtobuf::from_bytes(bytes)
+    }
+
+    /// Convert `self` to a protobuf-encoded [`Vec<u8>`].
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        ToProtobuf::to_bytes(self)
+    }
+
+    /// Returns the value of the last setting named `name`, if there is one.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.settings.iter().rev().find(|it| it.name == name).map(|it| it.value.as_str())
+    }
+}
+
+impl FromProtobuf<services::ServicesConfigurationList> for ServicesConfiguration {
+    fn from_protobuf(pb: services::ServicesConfigurationList) -> crate::Result<Self> {
+        Ok(Self { settings: Vec::from_protobuf(pb.name_value)? })
+    }
+}
+
+impl ToProtobuf for ServicesConfiguration {
+    type Protobuf = services::ServicesConfigurationList;
+
+    fn to_protobuf(&self) -> Self::Protobuf {
+        services::ServicesConfigurationList { name_value: self.settings.to_protobuf() }
+    }
+}
+
+/// A single setting of a [`ServicesConfiguration`].
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Setting {
+    /// The name of the setting.
+    pub name: String,
+
+    /// The value of the setting.
+    pub value: String,
+
+    /// Binary data that goes with the setting, usually empty.
+    pub data: Vec<u8>,
+}
+
+impl FromProtobuf<services::Setting> for Setting {
+    fn from_protobuf(pb: services::Setting) -> crate::Result<Self> {
+        Ok(Self { name: pb.name, value: pb.value, data: pb.data })
+    }
+}
+
+impl ToProtobuf for Setting {
+    type Protobuf = services::Setting;
+
+    fn to_protobuf(&self) -> Self::Protobuf {
+        services::Setting {
+            name: self.name.clone(),
+            value: self.value.clone(),
+            data: self.data.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hedera_proto::services;
+    use prost::Message;
+
+    use crate::{
+        ServicesConfiguration,
+        Setting,
+    };
+
+    fn setting(name: &str, value: &str) -> services::Setting {
+        services::Setting { name: name.to_owned(), value: value.to_owned(), data: Vec::new() }
+    }
+
+    #[test]
+    fn from_bytes() {
+        let bytes = services::ServicesConfigurationList {
+            name_value: vec![
+                setting("ledger.transfers.maxLen", "10"),
+                setting("contracts.maxGasPerSec", "15000000"),
+                setting("ledger.transfers.maxLen", "20"),
+            ],
+        }
+        .encode_to_vec();
+
+        let configuration = ServicesConfiguration::from_bytes(&bytes).unwrap();
+
+        assert_eq!(configuration.settings.len(), 3);
+        assert_eq!(configuration.get("ledger.transfers.maxLen"), Some("20"));
+        assert_eq!(configuration.get("contracts.maxGasPerSec"), Some("15000000"));
+        assert_eq!(configuration.get("tokens.maxPerAccount"), None);
+
+        // duplicates and order survive a round trip.
+        assert_eq!(configuration.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn to_from_bytes() {
+        let configuration = ServicesConfiguration {
+            settings: vec![
+                Setting {
+                    name: "freeze".to_owned(),
+                    value: "2-58".to_owned(),
+                    ..Default::default()
+                },
+                Setting {
+                    name: "cryptoCreate".to_owned(),
+                    value: "0-*".to_owned(),
+                    data: vec![1, 2, 3],
+                },
+            ],
+        };
+
+        assert_eq!(
+            ServicesConfiguration::from_bytes(&configuration.to_bytes()).unwrap(),
+            configuration
+        );
+    }
+}
@@ -20,13 +20,22 @@
 
 mod freeze_transaction;
 mod freeze_type;
+mod network_upgrade_flow;
 mod system_delete_transaction;
+mod system_file;
+mod system_file_query;
 mod system_undelete_transaction;
 
 pub use freeze_transaction::FreezeTransaction;
 pub(crate) use freeze_transaction::FreezeTransactionData;
 pub use freeze_type::FreezeType;
+pub use network_upgrade_flow::NetworkUpgradeFlow;
 pub use system_delete_transaction::SystemDeleteTransaction;
 pub(crate) use system_delete_transaction::SystemDeleteTransactionData;
+pub use system_file::{
+    SystemFile,
+    SystemFileKind,
+};
+pub use system_file_query::SystemFileQuery;
 pub use system_undelete_transaction::SystemUndeleteTransaction;
 pub(crate) use system_undelete_transaction::SystemUndeleteTransactionData;
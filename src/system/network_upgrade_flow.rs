@@ -0,0 +1,645 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::time::Duration;
+
+use futures_core::future::BoxFuture;
+use sha2::{
+    Digest,
+    Sha384,
+};
+use time::OffsetDateTime;
+
+use crate::signer::AnySigner;
+use crate::{
+    AccountId,
+    Client,
+    Error,
+    FileId,
+    FileUploadFlow,
+    FreezeTransaction,
+    FreezeType,
+    PrivateKey,
+    PublicKey,
+    Transaction,
+    TransactionResponse,
+};
+
+/// Upload a network software upgrade package, prepare the network for it, and schedule the upgrade.
+///
+/// The operation of this flow is as follows:
+/// 1. Upload the package to the [upgrade file](Self::file_id) in chunks (via a [`FileUploadFlow`]),
+///    and, if [`verify`](Self::verify) is set, check that the SHA-384 hash of the file matches the package.
+/// 2. Have the nodes stage the package with a [`FreezeTransaction`] of type [`FreezeType::PrepareUpgrade`].
+/// 3. If a [`start_time`](Self::start_time) is set, schedule the upgrade with a [`FreezeTransaction`]
+///    of type [`FreezeType::FreezeUpgrade`].
+///
+/// The payer of every transaction must be allowed to update the upgrade file and to freeze the network,
+/// such as the treasury (`0.0.2`) or freeze admin (`0.0.58`) account.
+#[derive(Debug)]
+pub struct NetworkUpgradeFlow {
+    node_account_ids: Option<Vec<AccountId>>,
+    file_id: Option<FileId>,
+    package: Vec<u8>,
+    chunk_size: Option<usize>,
+    max_concurrency: Option<usize>,
+    start_time: Option<OffsetDateTime>,
+    verify: bool,
+    signers: Vec<AnySigner>,
+}
+
+/// The network operations [`NetworkUpgradeFlow`] is made of.
+trait UpgradeNetwork: Sync {
+    /// Returns the upgrade file in the network's shard and realm.
+    fn upgrade_file_id(&self) -> FileId;
+
+    /// Uploads the contents of `upload`.
+    fn upload(&self, upload: FileUploadFlow) -> BoxFuture<'_, crate::Result<()>>;
+
+    /// Executes `transaction` and waits for its receipt.
+    fn freeze(
+        &self,
+        transaction: FreezeTransaction,
+    ) -> BoxFuture<'_, crate::Result<TransactionResponse>>;
+}
+
+struct ClientUpgradeNetwork<'a> {
+    client: &'a Client,
+    timeout: Option<Duration>,
+}
+
+impl UpgradeNetwork for ClientUpgradeNetwork<'_> {
+    fn upgrade_file_id(&self) -> FileId {
+        FileId::upgrade_for(self.client.shard(), self.client.realm())
+    }
+
+    fn upload(&self, upload: FileUploadFlow) -> BoxFuture<'_, crate::Result<()>> {
+        Box::pin(async move {
+            upload.execute_with_optional_timeout(self.client, self.timeout).await?;
+
+            Ok(())
+        })
+    }
+
+    fn freeze(
+        &self,
+        mut transaction: FreezeTransaction,
+    ) -> BoxFuture<'_, crate::Result<TransactionResponse>> {
+        Box::pin(async move {
+            let response =
+                transaction.execute_with_optional_timeout(self.client, self.timeout).await?;
+
+            response
+                .get_receipt_query()
+                .execute_with_optional_timeout(self.client, self.timeout)
+                .await?;
+
+            Ok(response)
+        })
+    }
+}
+
+impl Default for NetworkUpgradeFlow {
+    fn default() -> Self {
+        Self {
+            node_account_ids: None,
+            file_id: None,
+            package: Vec::new(),
+            chunk_size: None,
+            max_concurrency: None,
+            start_time: None,
+            verify: true,
+            signers: Vec::new(),
+        }
+    }
+}
+
+impl NetworkUpgradeFlow {
+    /// Create a new `NetworkUpgradeFlow`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the account IDs of the nodes the transactions may be submitted to.
+    #[must_use]
+    pub fn get_node_account_ids(&self) -> Option<&[AccountId]> {
+        self.node_account_ids.as_deref()
+    }
+
+    /// Sets the account IDs of the nodes the transactions may be submitted to.
+    ///
+    /// Defaults to the full list of nodes configured on the client.
+    pub fn node_account_ids(
+        &mut self,
+        node_account_ids: impl IntoIterator<Item = AccountId>,
+    ) -> &mut Self {
+        self.node_account_ids = Some(node_account_ids.into_iter().collect());
+
+        self
+    }
+
+    /// Returns the file the package is uploaded to, if it was set.
+    #[must_use]
+    pub fn get_file_id(&self) -> Option<FileId> {
+        self.file_id
+    }
+
+    /// Sets the file the package is uploaded to.
+    ///
+    /// Defaults to the [upgrade file](FileId::UPGRADE) in the client's shard and realm.
+    pub fn file_id(&mut self, file_id: impl Into<FileId>) -> &mut Self {
+        self.file_id = Some(file_id.into());
+
+        self
+    }
+
+    /// Returns the upgrade package.
+    #[must_use]
+    pub fn get_package(&self) -> &[u8] {
+        &self.package
+    }
+
+    /// Sets the upgrade package, the contents of the ZIP file to upgrade the network with.
+    pub fn package(&mut self, package: impl Into<Vec<u8>>) -> &mut Self {
+        self.package = package.into();
+
+        self
+    }
+
+    /// Returns the SHA-384 hash of the upgrade package, which the nodes check the uploaded file against.
+    #[must_use]
+    pub fn file_hash(&self) -> Vec<u8> {
+        Sha384::digest(&self.package).to_vec()
+    }
+
+    /// Returns the most bytes of the package that are uploaded by a single transaction.
+    #[must_use]
+    pub fn get_chunk_size(&self) -> Option<usize> {
+        self.chunk_size
+    }
+
+    /// Sets the most bytes of the package that are uploaded by a single transaction.
+    ///
+    /// Defaults to the default of [`FileUploadFlow::chunk_size`].
+    ///
+    /// # Panics
+    /// - If `chunk_size` is zero.
+    pub fn chunk_size(&mut self, chunk_size: usize) -> &mut Self {
+        assert!(chunk_size > 0, "chunk size must be greater than zero");
+
+        self.chunk_size = Some(chunk_size);
+
+        self
+    }
+
+    /// Returns the most append transactions that wait for their receipts at the same time.
+    #[must_use]
+    pub fn get_max_concurrency(&self) -> Option<usize> {
+        self.max_concurrency
+    }
+
+    /// Sets the most append transactions that wait for their receipts at the same time.
+    ///
+    /// Defaults to the default of [`FileUploadFlow::max_concurrency`].
+    ///
+    /// # Panics
+    /// - If `max_concurrency` is zero.
+    pub fn max_concurrency(&mut self, max_concurrency: usize) -> &mut Self {
+        assert!(max_concurrency > 0, "max concurrency must be greater than zero");
+
+        self.max_concurrency = Some(max_concurrency);
+
+        self
+    }
+
+    /// Returns the time the network freezes to upgrade, if one was set.
+    #[must_use]
+    pub fn get_start_time(&self) -> Option<OffsetDateTime> {
+        self.start_time
+    }
+
+    /// Sets the time the network freezes to upgrade.
+    ///
+    /// Without a start time, the package is only uploaded and prepared,
+    /// and the upgrade has to be scheduled separately with [`make_freeze_upgrade_transaction`](Self::make_freeze_upgrade_transaction).
+    ///
+    /// The start time must still be in the future once the package is prepared,
+    /// the flow fails without scheduling the upgrade otherwise.
+    pub fn start_time(&mut self, start_time: OffsetDateTime) -> &mut Self {
+        self.start_time = Some(start_time);
+
+        self
+    }
+
+    /// Returns whether the uploaded file is fetched and its hash compared to the package's.
+    #[must_use]
+    pub fn get_verify(&self) -> bool {
+        self.verify
+    }
+
+    /// Sets whether the uploaded file is fetched and its hash compared to the package's,
+    /// before the network is prepared for the upgrade.
+    ///
+    /// Defaults to `true`.
+    pub fn verify(&mut self, verify: bool) -> &mut Self {
+        self.verify = verify;
+
+        self
+    }
+
+    /// Adds a key to sign every transaction with, usually one of the upgrade file's keys.
+    pub fn sign(&mut self, key: PrivateKey) -> &mut Self {
+        self.sign_signer(AnySigner::PrivateKey(key))
+    }
+
+    /// Adds a signer to sign every transaction with, usually for one of the upgrade file's keys.
+    pub fn sign_with<F: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static>(
+        &mut self,
+        public_key: PublicKey,
+        signer: F,
+    ) -> &mut Self {
+        self.sign_signer(AnySigner::arbitrary(Box::new(public_key), signer))
+    }
+
+    fn sign_signer(&mut self, signer: AnySigner) -> &mut Self {
+        if !self.signers.iter().any(|it| it.public_key() == signer.public_key()) {
+            self.signers.push(signer);
+        }
+
+        self
+    }
+
+    /// Returns the transaction that has the nodes stage the package uploaded to `file_id`.
+    #[must_use]
+    pub fn make_prepare_upgrade_transaction(&self, file_id: FileId) -> FreezeTransaction {
+        let mut transaction = FreezeTransaction::new();
+
+        transaction
+            .freeze_type(FreezeType::PrepareUpgrade)
+            .file_id(file_id)
+            .file_hash(self.file_hash());
+
+        self.prepare(&mut transaction);
+
+        transaction
+    }
+
+    /// Returns the transaction that schedules the upgrade with the package uploaded to `file_id`,
+    /// freezing the network at `start_time`.
+    #[must_use]
+    pub fn make_freeze_upgrade_transaction(
+        &self,
+        file_id: FileId,
+        start_time: OffsetDateTime,
+    ) -> FreezeTransaction {
+        let mut transaction = FreezeTransaction::new();
+
+        transaction
+            .freeze_type(FreezeType::FreezeUpgrade)
+            .start_time(start_time)
+            .file_id(file_id)
+            .file_hash(self.file_hash());
+
+        self.prepare(&mut transaction);
+
+        transaction
+    }
+
+    /// Uploads and prepares the package, and schedules the upgrade if a start time is set.
+    ///
+    /// Returns the response of the last transaction,
+    /// the [`FreezeUpgrade`](FreezeType::FreezeUpgrade) if it was scheduled,
+    /// or else the [`PrepareUpgrade`](FreezeType::PrepareUpgrade).
+    ///
+    /// # Errors
+    /// - [`Error::UpgradeStartTimePassed`] if the [start time](Self::start_time) isn't in the future,
+    ///   before uploading the package or before scheduling the upgrade.
+    /// - [`Error::FileContentsMismatch`] if the uploaded file doesn't have the same hash as the package.
+    /// - [`Error::ReceiptStatus`] if a transaction failed.
+    /// - Any other error from executing a transaction, querying its receipt, or fetching the file.
+    pub async fn execute(&self, client: &Client) -> crate::Result<TransactionResponse> {
+        self.execute_with_optional_timeout(client, None).await
+    }
+
+    /// Uploads and prepares the package, and schedules the upgrade if a start time is set.
+    ///
+    /// Returns the response of the last transaction,
+    /// the [`FreezeUpgrade`](FreezeType::FreezeUpgrade) if it was scheduled,
+    /// or else the [`PrepareUpgrade`](FreezeType::PrepareUpgrade).
+    ///
+    /// # Errors
+    /// - [`Error::UpgradeStartTimePassed`] if the [start time](Self::start_time) isn't in the future,
+    ///   before uploading the package or before scheduling the upgrade.
+    /// - [`Error::FileContentsMismatch`] if the uploaded file doesn't have the same hash as the package.
+    /// - [`Error::ReceiptStatus`] if a transaction failed.
+    /// - Any other error from executing a transaction, querying its receipt, or fetching the file.
+    pub async fn execute_with_timeout(
+        &self,
+        client: &Client,
+        timeout_per_transaction: Duration,
+    ) -> crate::Result<TransactionResponse> {
+        self.execute_with_optional_timeout(client, Some(timeout_per_transaction)).await
+    }
+
+    async fn execute_with_optional_timeout(
+        &self,
+        client: &Client,
+        timeout: Option<Duration>,
+    ) -> crate::Result<TransactionResponse> {
+        self.execute_on(&ClientUpgradeNetwork { client, timeout }).await
+    }
+
+    async fn execute_on(
+        &self,
+        network: &impl UpgradeNetwork,
+    ) -> crate::Result<TransactionResponse> {
+        self.check_start_time()?;
+
+        let file_id = self.file_id.unwrap_or_else(|| network.upgrade_file_id());
+
+        let mut upload = FileUploadFlow::new();
+
+        upload.file_id(file_id).contents(self.package.clone()).verify(self.verify);
+
+        if let Some(node_account_ids) = &self.node_account_ids {
+            upload.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        if let Some(chunk_size) = self.chunk_size {
+            upload.chunk_size(chunk_size);
+        }
+
+        if let Some(max_concurrency) = self.max_concurrency {
+            upload.max_concurrency(max_concurrency);
+        }
+
+        for signer in &self.signers {
+            upload.sign_signer(signer.clone());
+        }
+
+        network.upload(upload).await?;
+
+        let response = network.freeze(self.make_prepare_upgrade_transaction(file_id)).await?;
+
+        let Some(start_time) = self.start_time else {
+            return Ok(response);
+        };
+
+        // uploading a large package can take long enough for the start time to pass.
+        self.check_start_time()?;
+
+        network.freeze(self.make_freeze_upgrade_transaction(file_id, start_time)).await
+    }
+
+    fn check_start_time(&self) -> crate::Result<()> {
+        match self.start_time {
+            Some(start_time) if start_time <= OffsetDateTime::now_utc() => {
+                Err(Error::UpgradeStartTimePassed { start_time })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn prepare<D>(&self, transaction: &mut Transaction<D>) {
+        if let Some(node_account_ids) = &self.node_account_ids {
+            transaction.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        for signer in &self.signers {
+            transaction.sign_signer(signer.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use futures_core::future::BoxFuture;
+    use hex_literal::hex;
+    use parking_lot::Mutex;
+    use time::{
+        Duration,
+        OffsetDateTime,
+    };
+
+    use super::UpgradeNetwork;
+    use crate::{
+        AccountId,
+        Error,
+        FileId,
+        FileUploadFlow,
+        FreezeTransaction,
+        FreezeType,
+        NetworkUpgradeFlow,
+        TransactionHash,
+        TransactionId,
+        TransactionResponse,
+    };
+
+    const PACKAGE: &[u8] = b"upgrade package";
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Call {
+        Upload(Option<FileId>, Vec<u8>),
+        Freeze(FreezeType, Option<FileId>, Option<OffsetDateTime>),
+    }
+
+    /// A network that records the operations of the flow, and fails the upload if `fail_upload` is set.
+    #[derive(Default)]
+    struct FakeNetwork {
+        fail_upload: bool,
+        calls: Mutex<Vec<Call>>,
+    }
+
+    impl UpgradeNetwork for FakeNetwork {
+        fn upgrade_file_id(&self) -> FileId {
+            FileId::UPGRADE
+        }
+
+        fn upload(&self, upload: FileUploadFlow) -> BoxFuture<'_, crate::Result<()>> {
+            Box::pin(async move {
+                if self.fail_upload {
+                    return Err(Error::GrpcStatus(tonic::Status::unavailable("node is down")));
+                }
+
+                self.calls
+                    .lock()
+                    .push(Call::Upload(upload.get_file_id(), upload.get_contents().to_vec()));
+
+                Ok(())
+            })
+        }
+
+        fn freeze(
+            &self,
+            transaction: FreezeTransaction,
+        ) -> BoxFuture<'_, crate::Result<TransactionResponse>> {
+            Box::pin(async move {
+                self.calls.lock().push(Call::Freeze(
+                    transaction.get_freeze_type(),
+                    transaction.get_file_id(),
+                    transaction.get_start_time(),
+                ));
+
+                Ok(TransactionResponse {
+                    node_account_id: AccountId::new(0, 0, 3),
+                    transaction_id: TransactionId::generate(AccountId::new(0, 0, 2)),
+                    transaction_hash: TransactionHash([0; 48]),
+                    validate_status: true,
+                    inner_transaction_ids: Vec::new(),
+                })
+            })
+        }
+    }
+
+    #[test]
+    fn file_hash() {
+        let mut flow = NetworkUpgradeFlow::new();
+        flow.package(PACKAGE);
+
+        assert_eq!(
+            flow.file_hash(),
+            hex!(
+                "6b4783bb6a02ac77d579a3d232093242a5cc46fc2e9f9262b79cb90d5ac2f7c3"
+                "1eed9dfe3b01e4f38cd02539b4dbb322"
+            )
+        );
+    }
+
+    #[test]
+    fn prepare_upgrade_transaction() {
+        let mut flow = NetworkUpgradeFlow::new();
+        flow.package(PACKAGE).node_account_ids([AccountId::new(0, 0, 3)]);
+
+        let transaction = flow.make_prepare_upgrade_transaction(FileId::UPGRADE);
+
+        assert_eq!(transaction.get_freeze_type(), FreezeType::PrepareUpgrade);
+        assert_eq!(transaction.get_file_id(), Some(FileId::UPGRADE));
+        assert_eq!(transaction.get_file_hash(), Some(flow.file_hash().as_slice()));
+        assert_eq!(transaction.get_start_time(), None);
+        assert_eq!(transaction.get_node_account_ids(), Some([AccountId::new(0, 0, 3)].as_slice()));
+    }
+
+    #[test]
+    fn freeze_upgrade_transaction() {
+        let start_time = OffsetDateTime::from_unix_timestamp(1_554_158_542).unwrap();
+
+        let mut flow = NetworkUpgradeFlow::new();
+        flow.package(PACKAGE);
+
+        let transaction = flow.make_freeze_upgrade_transaction(FileId::UPGRADE, start_time);
+
+        assert_eq!(transaction.get_freeze_type(), FreezeType::FreezeUpgrade);
+        assert_eq!(transaction.get_file_id(), Some(FileId::UPGRADE));
+        assert_eq!(transaction.get_file_hash(), Some(flow.file_hash().as_slice()));
+        assert_eq!(transaction.get_start_time(), Some(start_time));
+    }
+
+    #[test]
+    fn get_set_file_id() {
+        let mut flow = NetworkUpgradeFlow::new();
+        flow.file_id(FileId::upgrade_for(1, 2));
+
+        assert_eq!(flow.get_file_id(), Some(FileId::new(1, 2, 150)));
+    }
+
+    #[test]
+    fn get_set_start_time() {
+        let start_time = OffsetDateTime::from_unix_timestamp(1_554_158_542).unwrap();
+
+        let mut flow = NetworkUpgradeFlow::new();
+        flow.start_time(start_time);
+
+        assert_eq!(flow.get_start_time(), Some(start_time));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_chunk_size() {
+        NetworkUpgradeFlow::new().chunk_size(0);
+    }
+
+    #[tokio::test]
+    async fn upload_prepare_freeze() {
+        let start_time = OffsetDateTime::now_utc() + Duration::hours(1);
+
+        let mut flow = NetworkUpgradeFlow::new();
+        flow.package(PACKAGE).start_time(start_time);
+
+        let network = FakeNetwork::default();
+
+        flow.execute_on(&network).await.unwrap();
+
+        assert_eq!(
+            *network.calls.lock(),
+            [
+                Call::Upload(Some(FileId::UPGRADE), PACKAGE.to_vec()),
+                Call::Freeze(FreezeType::PrepareUpgrade, Some(FileId::UPGRADE), None),
+                Call::Freeze(FreezeType::FreezeUpgrade, Some(FileId::UPGRADE), Some(start_time)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn upload_prepare_without_start_time() {
+        let mut flow = NetworkUpgradeFlow::new();
+        flow.package(PACKAGE).file_id(FileId::upgrade_for(1, 2));
+
+        let network = FakeNetwork::default();
+
+        flow.execute_on(&network).await.unwrap();
+
+        assert_eq!(
+            *network.calls.lock(),
+            [
+                Call::Upload(Some(FileId::new(1, 2, 150)), PACKAGE.to_vec()),
+                Call::Freeze(FreezeType::PrepareUpgrade, Some(FileId::new(1, 2, 150)), None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_upload_stops() {
+        let mut flow = NetworkUpgradeFlow::new();
+        flow.package(PACKAGE).start_time(OffsetDateTime::now_utc() + Duration::hours(1));
+
+        let network = FakeNetwork { fail_upload: true, ..Default::default() };
+
+        assert_matches!(flow.execute_on(&network).await, Err(Error::GrpcStatus(_)));
+        assert!(network.calls.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn start_time_passed() {
+        let start_time = OffsetDateTime::now_utc() - Duration::minutes(1);
+
+        let mut flow = NetworkUpgradeFlow::new();
+        flow.package(PACKAGE).start_time(start_time);
+
+        let network = FakeNetwork::default();
+
+        assert_matches!(
+            flow.execute_on(&network).await,
+            Err(Error::UpgradeStartTimePassed { start_time: it }) if it == start_time
+        );
+        assert!(network.calls.lock().is_empty());
+    }
+}
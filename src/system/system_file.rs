@@ -0,0 +1,288 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use crate::{
+    Error,
+    ExchangeRates,
+    FeeSchedules,
+    FileId,
+    FileUploadFlow,
+    NodeAddressBook,
+    ServicesConfiguration,
+    ThrottleDefinitions,
+};
+
+/// The system files of a network that have a [typed model](SystemFile).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum SystemFileKind {
+    /// The [node address book](FileId::NODE_DETAILS) with every node's full details.
+    NodeDetails,
+
+    /// The public [node address book](FileId::ADDRESS_BOOK).
+    AddressBook,
+
+    /// The [fee schedules](FileId::FEE_SCHEDULE).
+    FeeSchedules,
+
+    /// The [exchange rates](FileId::EXCHANGE_RATES).
+    ExchangeRates,
+
+    /// The [application properties](FileId::APPLICATION_PROPERTIES).
+    ApplicationProperties,
+
+    /// The [API permissions](FileId::API_PERMISSIONS).
+    ApiPermissions,
+
+    /// The [throttle definitions](FileId::THROTTLE_DEFINITIONS).
+    ThrottleDefinitions,
+}
+
+impl SystemFileKind {
+    /// Returns the file of this kind for a network in `shard.realm`.
+    #[must_use]
+    pub const fn file_id_for(self, shard: u64, realm: u64) -> FileId {
+        match self {
+            Self::NodeDetails => FileId::node_details_for(shard, realm),
+            Self::AddressBook => FileId::address_book_for(shard, realm),
+            Self::FeeSchedules => FileId::fee_schedule_for(shard, realm),
+            Self::ExchangeRates => FileId::exchange_rates_for(shard, realm),
+            Self::ApplicationProperties => FileId::application_properties_for(shard, realm),
+            Self::ApiPermissions => FileId::api_permissions_for(shard, realm),
+            Self::ThrottleDefinitions => FileId::throttle_definitions_for(shard, realm),
+        }
+    }
+
+    /// Returns the kind of system file `file_id` is, if it's one with a typed model.
+    #[must_use]
+    pub fn from_file_id(file_id: FileId) -> Option<Self> {
+        let kind = match file_id.num {
+            101 => Self::NodeDetails,
+            102 => Self::AddressBook,
+            111 => Self::FeeSchedules,
+            112 => Self::ExchangeRates,
+            121 => Self::ApplicationProperties,
+            122 => Self::ApiPermissions,
+            123 => Self::ThrottleDefinitions,
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+}
+
+/// The decoded contents of a system file.
+///
+/// See [`SystemFileQuery`](crate::SystemFileQuery) to fetch one,
+/// and [`make_upload_flow`](Self::make_upload_flow) to replace one.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum SystemFile {
+    /// The [node address book](FileId::NODE_DETAILS) with every node's full details.
+    NodeDetails(NodeAddressBook),
+
+    /// The public [node address book](FileId::ADDRESS_BOOK).
+    AddressBook(NodeAddressBook),
+
+    /// The [fee schedules](FileId::FEE_SCHEDULE).
+    FeeSchedules(FeeSchedules),
+
+    /// The [exchange rates](FileId::EXCHANGE_RATES).
+    ExchangeRates(ExchangeRates),
+
+    /// The [application properties](FileId::APPLICATION_PROPERTIES).
+    ApplicationProperties(ServicesConfiguration),
+
+    /// The [API permissions](FileId::API_PERMISSIONS).
+    ApiPermissions(ServicesConfiguration),
+
+    /// The [throttle definitions](FileId::THROTTLE_DEFINITIONS).
+    ThrottleDefinitions(ThrottleDefinitions),
+}
+
+impl SystemFile {
+    /// Decode the contents of a system file of the given `kind` from `bytes`.
+    ///
+    /// # Errors
+    /// - [`Error::FromProtobuf`](crate::Error::FromProtobuf) if decoding the bytes fails to produce a valid protobuf.
+    /// - [`Error::FromProtobuf`](crate::Error::FromProtobuf) if decoding the protobuf fails.
+    pub fn from_bytes(kind: SystemFileKind, bytes: &[u8]) -> crate::Result<Self> {
+        let file = match kind {
+            SystemFileKind::NodeDetails => Self::NodeDetails(NodeAddressBook::from_bytes(bytes)?),
+            SystemFileKind::AddressBook => Self::AddressBook(NodeAddressBook::from_bytes(bytes)?),
+            SystemFileKind::FeeSchedules => Self::FeeSchedules(FeeSchedules::from_bytes(bytes)?),
+            SystemFileKind::ExchangeRates => Self::ExchangeRates(ExchangeRates::from_bytes(bytes)?),
+            SystemFileKind::ApplicationProperties => {
+                Self::ApplicationProperties(ServicesConfiguration::from_bytes(bytes)?)
+            }
+            SystemFileKind::ApiPermissions => {
+                Self::ApiPermissions(ServicesConfiguration::from_bytes(bytes)?)
+            }
+            SystemFileKind::ThrottleDefinitions => {
+                Self::ThrottleDefinitions(ThrottleDefinitions::from_bytes(bytes)?)
+            }
+        };
+
+        Ok(file)
+    }
+
+    /// Convert `self` to the protobuf-encoded contents of its file.
+    ///
+    /// # Errors
+    /// - [`Error::SystemFileNotEncodable`] for address books, whose deprecated fields
+    ///   (such as each node's stake and memo) aren't kept by [`NodeAddressBook`].
+    pub fn to_bytes(&self) -> crate::Result<Vec<u8>> {
+        let bytes = match self {
+            Self::NodeDetails(_) | Self::AddressBook(_) => {
+                return Err(Error::SystemFileNotEncodable { kind: self.kind() })
+            }
+            Self::FeeSchedules(it) => it.to_bytes(),
+            Self::ExchangeRates(it) => it.to_bytes(),
+            Self::ApplicationProperties(it) | Self::ApiPermissions(it) => it.to_bytes(),
+            Self::ThrottleDefinitions(it) => it.to_bytes(),
+        };
+
+        Ok(bytes)
+    }
+
+    /// Returns the kind of system file this is.
+    #[must_use]
+    pub fn kind(&self) -> SystemFileKind {
+        match self {
+            Self::NodeDetails(_) => SystemFileKind::NodeDetails,
+            Self::AddressBook(_) => SystemFileKind::AddressBook,
+            Self::FeeSchedules(_) => SystemFileKind::FeeSchedules,
+            Self::ExchangeRates(_) => SystemFileKind::ExchangeRates,
+            Self::ApplicationProperties(_) => SystemFileKind::ApplicationProperties,
+            Self::ApiPermissions(_) => SystemFileKind::ApiPermissions,
+            Self::ThrottleDefinitions(_) => SystemFileKind::ThrottleDefinitions,
+        }
+    }
+
+    /// Returns a flow that replaces the contents of this file, for a network in `shard.realm`, with `self`.
+    ///
+    /// The payer of the flow must be an account that's allowed to update the file,
+    /// and its keys must [sign](FileUploadFlow::sign) the flow.
+    ///
+    /// Address books can't be uploaded, the network manages them through node transactions
+    /// such as [`NodeUpdateTransaction`](crate::NodeUpdateTransaction).
+    ///
+    /// # Errors
+    /// - [`Error::SystemFileNotEncodable`] for address books.
+    pub fn make_upload_flow(&self, shard: u64, realm: u64) -> crate::Result<FileUploadFlow> {
+        let mut flow = FileUploadFlow::new();
+
+        flow.file_id(self.kind().file_id_for(shard, realm)).contents(self.to_bytes()?);
+
+        Ok(flow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use hex_literal::hex;
+
+    use crate::{
+        AccountId,
+        Error,
+        FileId,
+        ServicesConfiguration,
+        Setting,
+        SystemFile,
+        SystemFileKind,
+    };
+
+    #[test]
+    fn file_id_round_trip() {
+        for kind in [
+            SystemFileKind::NodeDetails,
+            SystemFileKind::AddressBook,
+            SystemFileKind::FeeSchedules,
+            SystemFileKind::ExchangeRates,
+            SystemFileKind::ApplicationProperties,
+            SystemFileKind::ApiPermissions,
+            SystemFileKind::ThrottleDefinitions,
+        ] {
+            assert_eq!(SystemFileKind::from_file_id(kind.file_id_for(0, 0)), Some(kind));
+        }
+
+        assert_eq!(SystemFileKind::from_file_id(FileId::UPGRADE), None);
+    }
+
+    #[test]
+    fn exchange_rates() {
+        let bytes =
+            hex!("0a1008b0ea0110b6b4231a0608f0bade9006121008b0ea01108cef231a060880d7de9006");
+
+        let file = SystemFile::from_bytes(SystemFileKind::ExchangeRates, &bytes).unwrap();
+
+        let SystemFile::ExchangeRates(rates) = &file else {
+            panic!("expected exchange rates, got {file:?}");
+        };
+
+        assert_eq!(rates.current_rate.cents, 580150);
+        assert_eq!(file.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn upload_flow() {
+        let configuration = ServicesConfiguration {
+            settings: vec![Setting {
+                name: "freeze".to_owned(),
+                value: "2-58".to_owned(),
+                ..Default::default()
+            }],
+        };
+
+        let file = SystemFile::ApiPermissions(configuration);
+        let flow = file.make_upload_flow(0, 0).unwrap();
+
+        assert_eq!(flow.get_file_id(), Some(FileId::API_PERMISSIONS));
+        assert_eq!(flow.get_contents(), file.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn node_details_are_not_encodable() {
+        // one node, with the deprecated `ip_address`, `portno`, `memo` and `stake` fields set.
+        let bytes = hex!(
+            "0a690a0423edc8b410a388031a05302e302e3322303330383230316132333030643036303932613836"
+            "343838366637306430313031303130353030303338323031386630302800320218033a046162313242"
+            "0a0a0423edc8b410a388034a066e6f64652030508094ebdc03"
+        );
+
+        let file = SystemFile::from_bytes(SystemFileKind::NodeDetails, &bytes).unwrap();
+
+        let SystemFile::NodeDetails(book) = &file else {
+            panic!("expected node details, got {file:?}");
+        };
+
+        assert_eq!(book.node_addresses.len(), 1);
+        assert_eq!(book.node_addresses[0].node_account_id, AccountId::new(0, 0, 3));
+        assert_eq!(book.node_addresses[0].description, "node 0");
+
+        // encoding it again would drop the deprecated fields.
+        assert_matches!(
+            file.to_bytes(),
+            Err(Error::SystemFileNotEncodable { kind: SystemFileKind::NodeDetails })
+        );
+        assert_matches!(file.make_upload_flow(0, 0), Err(Error::SystemFileNotEncodable { .. }));
+    }
+}
@@ -0,0 +1,108 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::time::Duration;
+
+use crate::{
+    AccountId,
+    Client,
+    FileContentsQuery,
+    SystemFile,
+    SystemFileKind,
+};
+
+/// Fetch a system file of the network, and decode it into its [typed model](SystemFile).
+#[derive(Debug, Clone)]
+pub struct SystemFileQuery {
+    kind: SystemFileKind,
+    node_account_ids: Option<Vec<AccountId>>,
+}
+
+impl SystemFileQuery {
+    /// Create a new `SystemFileQuery` that fetches the system file of the given `kind`.
+    #[must_use]
+    pub fn new(kind: SystemFileKind) -> Self {
+        Self { kind, node_account_ids: None }
+    }
+
+    /// Returns the kind of system file to fetch.
+    #[must_use]
+    pub fn get_kind(&self) -> SystemFileKind {
+        self.kind
+    }
+
+    /// Returns the account IDs of the nodes the query may be submitted to.
+    #[must_use]
+    pub fn get_node_account_ids(&self) -> Option<&[AccountId]> {
+        self.node_account_ids.as_deref()
+    }
+
+    /// Sets the account IDs of the nodes the query may be submitted to.
+    ///
+    /// Defaults to the full list of nodes configured on the client.
+    pub fn node_account_ids(
+        &mut self,
+        node_account_ids: impl IntoIterator<Item = AccountId>,
+    ) -> &mut Self {
+        self.node_account_ids = Some(node_account_ids.into_iter().collect());
+
+        self
+    }
+
+    /// Fetch the file in the client's shard and realm, and decode it.
+    ///
+    /// # Errors
+    /// - [`Error::FromProtobuf`](crate::Error::FromProtobuf) if the contents of the file can't be decoded.
+    /// - Any error from executing the [`FileContentsQuery`].
+    pub async fn execute(&self, client: &Client) -> crate::Result<SystemFile> {
+        self.execute_with_optional_timeout(client, None).await
+    }
+
+    /// Fetch the file in the client's shard and realm, and decode it.
+    ///
+    /// # Errors
+    /// - [`Error::FromProtobuf`](crate::Error::FromProtobuf) if the contents of the file can't be decoded.
+    /// - Any error from executing the [`FileContentsQuery`].
+    pub async fn execute_with_timeout(
+        &self,
+        client: &Client,
+        timeout: Duration,
+    ) -> crate::Result<SystemFile> {
+        self.execute_with_optional_timeout(client, Some(timeout)).await
+    }
+
+    async fn execute_with_optional_timeout(
+        &self,
+        client: &Client,
+        timeout: Option<Duration>,
+    ) -> crate::Result<SystemFile> {
+        let mut query = FileContentsQuery::new();
+
+        query.file_id(self.kind.file_id_for(client.shard(), client.realm()));
+
+        if let Some(node_account_ids) = &self.node_account_ids {
+            query.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        let contents = query.execute_with_optional_timeout(client, timeout).await?.contents;
+
+        SystemFile::from_bytes(self.kind, &contents)
+    }
+}
@@ -0,0 +1,200 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use hedera_proto::services;
+
+use crate::protobuf::{
+    FromProtobuf,
+    ToProtobuf,
+};
+use crate::{
+    Error,
+    RequestType,
+};
+
+/// The throttles the network applies to transactions and queries.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ThrottleDefinitions {
+    /// Every throttle bucket of the network.
+    pub buckets: Vec<ThrottleBucket>,
+}
+
+impl ThrottleDefinitions {
+    /// Create a new `ThrottleDefinitions` from protobuf-encoded `bytes`.
+    ///
+    /// # Errors
+    /// - [`Error::FromProtobuf`](crate::Error::FromProtobuf) if decoding the bytes fails to produce a valid protobuf.
+    /// - [`Error::FromProtobuf`](crate::Error::FromProtobuf) if decoding the protobuf fails.
+    pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
+        FromProtobuf::from_bytes(bytes)
+    }
+
+    /// Convert `self` to a protobuf-encoded [`Vec<u8>`].
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        ToProtobuf::to_bytes(self)
+    }
+}
+
+impl FromProtobuf<services::ThrottleDefinitions> for ThrottleDefinitions {
+    fn from_protobuf(pb: services::ThrottleDefinitions) -> crate::Result<Self> {
+        Ok(Self { buckets: Vec::from_protobuf(pb.throttle_buckets)? })
+    }
+}
+
+impl ToProtobuf for ThrottleDefinitions {
+    type Protobuf = services::ThrottleDefinitions;
+
+    fn to_protobuf(&self) -> Self::Protobuf {
+        services::ThrottleDefinitions { throttle_buckets: self.buckets.to_protobuf() }
+    }
+}
+
+/// A named throttle, shared by the operations of each of its groups.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ThrottleBucket {
+    /// The name of the bucket.
+    pub name: String,
+
+    /// The period, in milliseconds, that bursts of operations are smoothed over.
+    pub burst_period_ms: u64,
+
+    /// The groups of operations throttled by the bucket.
+    pub groups: Vec<ThrottleGroup>,
+}
+
+impl FromProtobuf<services::ThrottleBucket> for ThrottleBucket {
+    fn from_protobuf(pb: services::ThrottleBucket) -> crate::Result<Self> {
+        Ok(Self {
+            name: pb.name,
+            burst_period_ms: pb.burst_period_ms,
+            groups: Vec::from_protobuf(pb.throttle_groups)?,
+        })
+    }
+}
+
+impl ToProtobuf for ThrottleBucket {
+    type Protobuf = services::ThrottleBucket;
+
+    fn to_protobuf(&self) -> Self::Protobuf {
+        services::ThrottleBucket {
+            name: self.name.clone(),
+            burst_period_ms: self.burst_period_ms,
+            throttle_groups: self.groups.to_protobuf(),
+        }
+    }
+}
+
+/// Operations that share a rate limit within a [`ThrottleBucket`].
+///
+/// Decoding a group with operations this SDK doesn't know fails, instead of dropping them,
+/// so that re-encoding a [`ThrottleDefinitions`] can't silently unthrottle them.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ThrottleGroup {
+    /// The operations in the group.
+    pub operations: Vec<RequestType>,
+
+    /// The most operations of the group the network accepts per second, in thousandths of an operation.
+    pub milli_ops_per_sec: u64,
+}
+
+impl FromProtobuf<services::ThrottleGroup> for ThrottleGroup {
+    fn from_protobuf(pb: services::ThrottleGroup) -> crate::Result<Self> {
+        Ok(Self {
+            operations: pb
+                .operations
+                .into_iter()
+                .map(|operation| {
+                    let operation =
+                        services::HederaFunctionality::try_from(operation).map_err(|_| {
+                            Error::from_protobuf(format!(
+                                "unknown throttle group operation `{operation}`"
+                            ))
+                        })?;
+
+                    RequestType::from_protobuf(operation)
+                })
+                .collect::<crate::Result<_>>()?,
+            milli_ops_per_sec: pb.milli_ops_per_sec,
+        })
+    }
+}
+
+impl ToProtobuf for ThrottleGroup {
+    type Protobuf = services::ThrottleGroup;
+
+    fn to_protobuf(&self) -> Self::Protobuf {
+        services::ThrottleGroup {
+            operations: self.operations.iter().map(|it| it.to_protobuf() as i32).collect(),
+            milli_ops_per_sec: self.milli_ops_per_sec,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use hedera_proto::services;
+    use prost::Message;
+
+    use crate::{
+        Error,
+        RequestType,
+        ThrottleBucket,
+        ThrottleDefinitions,
+        ThrottleGroup,
+    };
+
+    #[test]
+    fn to_from_bytes() {
+        let definitions = ThrottleDefinitions {
+            buckets: vec![ThrottleBucket {
+                name: "ThroughputLimits".to_owned(),
+                burst_period_ms: 1000,
+                groups: vec![ThrottleGroup {
+                    operations: vec![RequestType::CryptoTransfer, RequestType::CryptoCreate],
+                    milli_ops_per_sec: 10_000_000,
+                }],
+            }],
+        };
+
+        assert_eq!(ThrottleDefinitions::from_bytes(&definitions.to_bytes()).unwrap(), definitions);
+    }
+
+    #[test]
+    fn unknown_operation() {
+        let bytes = services::ThrottleDefinitions {
+            throttle_buckets: vec![services::ThrottleBucket {
+                name: "ThroughputLimits".to_owned(),
+                burst_period_ms: 1000,
+                throttle_groups: vec![services::ThrottleGroup {
+                    operations: vec![
+                        services::HederaFunctionality::CryptoTransfer as i32,
+                        1_000_000,
+                    ],
+                    milli_ops_per_sec: 10_000_000,
+                }],
+            }],
+        }
+        .encode_to_vec();
+
+        assert_matches!(ThrottleDefinitions::from_bytes(&bytes), Err(Error::FromProtobuf(_)));
+    }
+}
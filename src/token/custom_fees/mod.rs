@@ -29,9 +29,12 @@ use crate::{
     TokenId,
 };
 
+mod simulator;
 #[cfg(test)]
 mod tests;
 
+pub use simulator::CustomFeeSimulator;
+
 /// Any `CustomFee`.
 ///
 /// See the documentation for [`CustomFee`] and [`Fee`].
@@ -131,6 +134,28 @@ impl From<FixedFee> for AnyCustomFee {
     }
 }
 
+// note: `FixedCustomFee` is the protobuf used where only fixed fees are allowed, such as on topics.
+impl FromProtobuf<services::FixedCustomFee> for FixedFee {
+    fn from_protobuf(pb: services::FixedCustomFee) -> crate::Result<Self> {
+        Ok(Self {
+            fee: FixedFeeData::from_protobuf(pb_getf!(pb, fixed_fee)?)?,
+            fee_collector_account_id: Option::from_protobuf(pb.fee_collector_account_id)?,
+            all_collectors_are_exempt: false,
+        })
+    }
+}
+
+impl ToProtobuf for FixedFee {
+    type Protobuf = services::FixedCustomFee;
+
+    fn to_protobuf(&self) -> Self::Protobuf {
+        services::FixedCustomFee {
+            fixed_fee: Some(self.fee.to_protobuf()),
+            fee_collector_account_id: self.fee_collector_account_id.to_protobuf(),
+        }
+    }
+}
+
 impl From<FractionalFee> for AnyCustomFee {
     fn from(v: FractionalFee) -> Self {
         Self {
@@ -256,6 +281,37 @@ impl ToProtobuf for FixedFeeData {
     }
 }
 
+/// The maximum custom fees a payer is willing to pay for a transaction, such as a
+/// [`TopicMessageSubmitTransaction`](crate::TopicMessageSubmitTransaction) to a topic with custom fees.
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct CustomFeeLimit {
+    /// The account paying the fees, if unset, the limit applies to the transaction's payer.
+    pub account_id: Option<AccountId>,
+
+    /// The maximum amount of each denomination the payer is willing to pay.
+    pub fees: Vec<FixedFeeData>,
+}
+
+impl FromProtobuf<services::CustomFeeLimit> for CustomFeeLimit {
+    fn from_protobuf(pb: services::CustomFeeLimit) -> crate::Result<Self> {
+        Ok(Self {
+            account_id: Option::from_protobuf(pb.account_id)?,
+            fees: Vec::from_protobuf(pb.fees)?,
+        })
+    }
+}
+
+impl ToProtobuf for CustomFeeLimit {
+    type Protobuf = services::CustomFeeLimit;
+
+    fn to_protobuf(&self) -> Self::Protobuf {
+        services::CustomFeeLimit {
+            account_id: self.account_id.to_protobuf(),
+            fees: self.fees.to_protobuf(),
+        }
+    }
+}
+
 /// A fraction of the transferred units of a token to assess as a fee. The amount assessed will never
 /// be less than the given `minimum_amount`, and never greater than the given `maximum_amount`.  The
 /// denomination is always units of the token to which this fractional fee is attached.
@@ -0,0 +1,464 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::collections::HashMap;
+
+use super::{
+    AnyCustomFee,
+    Fee,
+    FeeAssessmentMethod,
+    FixedFeeData,
+    FractionalFeeData,
+};
+use crate::transfer_transaction::TokenTransfer;
+use crate::{
+    AccountId,
+    AssessedCustomFee,
+    TokenId,
+    TokenInfo,
+    TransferTransaction,
+};
+
+/// Predicts the custom fees a [`TransferTransaction`] will be charged, without sending anything to the network.
+///
+/// The simulator needs the custom fees of every token in the transfer, usually from [`TokenInfo::custom_fees`].
+/// Tokens it doesn't know about are assumed to have no custom fees.
+///
+/// Only fees caused by the transaction's own transfers are assessed,
+/// fees caused by paying other custom fees aren't.
+///
+/// # Examples
+///
+/// ```
+/// # use hedera::{AccountId, CustomFeeSimulator, FixedFee, FixedFeeData, Hbar, TokenId, TransferTransaction};
+/// let token_id = TokenId::new(0, 0, 1000);
+/// let collector = AccountId::new(0, 0, 98);
+///
+/// let mut transaction = TransferTransaction::new();
+/// transaction
+///     .token_transfer(token_id, AccountId::new(0, 0, 1001), -10)
+///     .token_transfer(token_id, AccountId::new(0, 0, 1002), 10);
+///
+/// let fees = CustomFeeSimulator::new()
+///     .token_custom_fees(
+///         token_id,
+///         [FixedFee {
+///             fee: FixedFeeData::from_hbar(Hbar::new(1)),
+///             fee_collector_account_id: Some(collector),
+///             all_collectors_are_exempt: false,
+///         }
+///         .into()],
+///     )
+///     .simulate(&transaction);
+///
+/// assert_eq!(fees.len(), 1);
+/// assert_eq!(fees[0].amount, Hbar::new(1).to_tinybars());
+/// assert_eq!(fees[0].payer_account_id_list, [AccountId::new(0, 0, 1001)]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CustomFeeSimulator {
+    custom_fees: HashMap<TokenId, Vec<AnyCustomFee>>,
+}
+
+impl CustomFeeSimulator {
+    /// Create a new simulator that doesn't know about any tokens.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the custom fees of the token `token_id`.
+    pub fn token_custom_fees(
+        &mut self,
+        token_id: TokenId,
+        custom_fees: impl IntoIterator<Item = AnyCustomFee>,
+    ) -> &mut Self {
+        self.custom_fees.insert(token_id, custom_fees.into_iter().collect());
+        self
+    }
+
+    /// Sets the custom fees of the token described by `info`.
+    pub fn token_info(&mut self, info: &TokenInfo) -> &mut Self {
+        self.token_custom_fees(info.token_id, info.custom_fees.iter().cloned())
+    }
+
+    /// Returns the custom fees that `transaction` is expected to be charged, in the order they're assessed.
+    #[must_use]
+    pub fn simulate(&self, transaction: &TransferTransaction) -> Vec<AssessedCustomFee> {
+        let data = transaction.data();
+
+        let mut assessed = Vec::new();
+
+        for token_transfer in data.token_transfers() {
+            let Some(fees) = self.custom_fees.get(&token_transfer.token_id) else {
+                continue;
+            };
+
+            if token_transfer.nft_transfers.is_empty() {
+                assess_fungible(token_transfer, fees, &mut assessed);
+            } else {
+                assess_nft(transaction, token_transfer, fees, &mut assessed);
+            }
+        }
+
+        assessed
+    }
+}
+
+fn assess_fungible(
+    token_transfer: &TokenTransfer,
+    fees: &[AnyCustomFee],
+    assessed: &mut Vec<AssessedCustomFee>,
+) {
+    let senders = token_transfer.transfers.iter().filter(|it| it.amount < 0);
+
+    for sender in senders {
+        for fee in fees {
+            if is_exempt(sender.account_id, fee, fees) {
+                continue;
+            }
+
+            match &fee.fee {
+                Fee::Fixed(fixed) => assessed.push(assess_fixed(fixed, fee, sender.account_id)),
+                Fee::Fractional(fractional) => {
+                    // an exclusive fee is charged on top of the transfer,
+                    // an inclusive fee is taken out of what the receivers get.
+                    let payer_account_id_list = match fractional.assessment_method {
+                        FeeAssessmentMethod::Exclusive => Vec::from([sender.account_id]),
+                        FeeAssessmentMethod::Inclusive => token_transfer
+                            .transfers
+                            .iter()
+                            .filter(|it| {
+                                it.amount > 0 && Some(it.account_id) != fee.fee_collector_account_id
+                            })
+                            .map(|it| it.account_id)
+                            .collect(),
+                    };
+
+                    assessed.push(AssessedCustomFee {
+                        amount: fractional_amount(fractional, -sender.amount),
+                        token_id: Some(token_transfer.token_id),
+                        fee_collector_account_id: fee.fee_collector_account_id,
+                        payer_account_id_list,
+                    });
+                }
+                // royalty fees only apply to NFTs.
+                Fee::Royalty(_) => {}
+            }
+        }
+    }
+}
+
+fn assess_nft(
+    transaction: &TransferTransaction,
+    token_transfer: &TokenTransfer,
+    fees: &[AnyCustomFee],
+    assessed: &mut Vec<AssessedCustomFee>,
+) {
+    // royalties are charged once per sender, on all the value they received.
+    let mut royalties_paid: Vec<AccountId> = Vec::new();
+
+    for nft_transfer in &token_transfer.nft_transfers {
+        let sender = nft_transfer.sender;
+
+        for fee in fees {
+            if is_exempt(sender, fee, fees) {
+                continue;
+            }
+
+            match &fee.fee {
+                Fee::Fixed(fixed) => assessed.push(assess_fixed(fixed, fee, sender)),
+                // fractional fees only apply to fungible tokens.
+                Fee::Fractional(_) => {}
+                Fee::Royalty(royalty) => {
+                    if royalties_paid.contains(&sender) {
+                        continue;
+                    }
+
+                    let exchanged = fungible_value_received(transaction, sender);
+
+                    if exchanged.is_empty() {
+                        // the receiver pays the fallback fee, and the fee's collector is still exempt.
+                        if let Some(fallback) = &royalty.fallback_fee {
+                            if !is_exempt(nft_transfer.receiver, fee, fees) {
+                                assessed.push(assess_fixed(fallback, fee, nft_transfer.receiver));
+                            }
+                        }
+
+                        continue;
+                    }
+
+                    royalties_paid.push(sender);
+
+                    for (token_id, amount) in exchanged {
+                        assessed.push(AssessedCustomFee {
+                            amount: fraction_of(amount, royalty.numerator, royalty.denominator),
+                            token_id,
+                            fee_collector_account_id: fee.fee_collector_account_id,
+                            payer_account_id_list: Vec::from([sender]),
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the hbar (`None`) and fungible token credits that `account_id` receives in `transaction`.
+fn fungible_value_received(
+    transaction: &TransferTransaction,
+    account_id: AccountId,
+) -> Vec<(Option<TokenId>, i64)> {
+    let data = transaction.data();
+
+    let hbar = data
+        .transfers()
+        .iter()
+        .filter(|it| it.account_id == account_id && it.amount > 0)
+        .map(|it| (None, it.amount));
+
+    let tokens =
+        data.token_transfers().iter().filter(|it| it.nft_transfers.is_empty()).flat_map(|token| {
+            token
+                .transfers
+                .iter()
+                .filter(|it| it.account_id == account_id && it.amount > 0)
+                .map(|it| (Some(token.token_id), it.amount))
+        });
+
+    hbar.chain(tokens).collect()
+}
+
+fn assess_fixed(fixed: &FixedFeeData, fee: &AnyCustomFee, payer: AccountId) -> AssessedCustomFee {
+    AssessedCustomFee {
+        amount: fixed.amount,
+        token_id: fixed.denominating_token_id,
+        fee_collector_account_id: fee.fee_collector_account_id,
+        payer_account_id_list: Vec::from([payer]),
+    }
+}
+
+/// A fee's own collector never pays it, and when `all_collectors_are_exempt` is set,
+/// neither does the collector of any other fee of the same token.
+fn is_exempt(payer: AccountId, fee: &AnyCustomFee, fees: &[AnyCustomFee]) -> bool {
+    if fee.fee_collector_account_id == Some(payer) {
+        return true;
+    }
+
+    fee.all_collectors_are_exempt
+        && fees.iter().any(|other| other.fee_collector_account_id == Some(payer))
+}
+
+fn fractional_amount(fee: &FractionalFeeData, units: i64) -> i64 {
+    let amount = fraction_of(units, fee.numerator, fee.denominator).max(fee.minimum_amount);
+
+    match fee.maximum_amount {
+        0 => amount,
+        maximum => amount.min(maximum),
+    }
+}
+
+/// `amount * numerator / denominator`, rounded down.
+fn fraction_of(amount: i64, numerator: u64, denominator: u64) -> i64 {
+    if denominator == 0 {
+        return 0;
+    }
+
+    let value = i128::from(amount) * i128::from(numerator) / i128::from(denominator);
+
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AccountId,
+        AnyCustomFee,
+        AssessedCustomFee,
+        CustomFeeSimulator,
+        FeeAssessmentMethod,
+        FixedFee,
+        FixedFeeData,
+        FractionalFee,
+        FractionalFeeData,
+        Hbar,
+        RoyaltyFee,
+        RoyaltyFeeData,
+        TokenId,
+        TransferTransaction,
+    };
+
+    const TOKEN: TokenId = TokenId::new(0, 0, 1000);
+    const NFT: TokenId = TokenId::new(0, 0, 2000);
+    const ALICE: AccountId = AccountId::new(0, 0, 1001);
+    const BOB: AccountId = AccountId::new(0, 0, 1002);
+    const COLLECTOR: AccountId = AccountId::new(0, 0, 98);
+    const OTHER_COLLECTOR: AccountId = AccountId::new(0, 0, 99);
+
+    fn fractional(minimum_amount: i64, maximum_amount: i64) -> AnyCustomFee {
+        fractional_with(minimum_amount, maximum_amount, FeeAssessmentMethod::Exclusive)
+    }
+
+    fn fractional_with(
+        minimum_amount: i64,
+        maximum_amount: i64,
+        assessment_method: FeeAssessmentMethod,
+    ) -> AnyCustomFee {
+        FractionalFee {
+            fee: FractionalFeeData {
+                denominator: 10,
+                numerator: 1,
+                minimum_amount,
+                maximum_amount,
+                assessment_method,
+            },
+            fee_collector_account_id: Some(COLLECTOR),
+            all_collectors_are_exempt: false,
+        }
+        .into()
+    }
+
+    fn royalty(fallback_fee: Option<FixedFeeData>) -> AnyCustomFee {
+        RoyaltyFee {
+            fee: RoyaltyFeeData { denominator: 20, numerator: 1, fallback_fee },
+            fee_collector_account_id: Some(COLLECTOR),
+            all_collectors_are_exempt: false,
+        }
+        .into()
+    }
+
+    fn fungible_transfer(amount: i64) -> TransferTransaction {
+        let mut tx = TransferTransaction::new();
+        tx.token_transfer(TOKEN, ALICE, -amount).token_transfer(TOKEN, BOB, amount);
+        tx
+    }
+
+    #[test]
+    fn fixed_fee() {
+        let fee = FixedFee {
+            fee: FixedFeeData { amount: 5, denominating_token_id: Some(TOKEN) },
+            fee_collector_account_id: Some(COLLECTOR),
+            all_collectors_are_exempt: false,
+        };
+
+        let assessed = CustomFeeSimulator::new()
+            .token_custom_fees(TOKEN, [fee.into()])
+            .simulate(&fungible_transfer(100));
+
+        assert_eq!(
+            assessed,
+            [AssessedCustomFee {
+                amount: 5,
+                token_id: Some(TOKEN),
+                fee_collector_account_id: Some(COLLECTOR),
+                payer_account_id_list: Vec::from([ALICE]),
+            }]
+        );
+    }
+
+    #[test]
+    fn fractional_fee_clamps() {
+        let mut simulator = CustomFeeSimulator::new();
+
+        simulator.token_custom_fees(TOKEN, [fractional(0, 0)]);
+        assert_eq!(simulator.simulate(&fungible_transfer(1000))[0].amount, 100);
+
+        simulator.token_custom_fees(TOKEN, [fractional(50, 0)]);
+        assert_eq!(simulator.simulate(&fungible_transfer(100))[0].amount, 50);
+
+        simulator.token_custom_fees(TOKEN, [fractional(0, 30)]);
+        assert_eq!(simulator.simulate(&fungible_transfer(1000))[0].amount, 30);
+    }
+
+    #[test]
+    fn fractional_fee_assessment_method() {
+        let mut simulator = CustomFeeSimulator::new();
+
+        simulator.token_custom_fees(TOKEN, [fractional(0, 0)]);
+        assert_eq!(simulator.simulate(&fungible_transfer(1000))[0].payer_account_id_list, [ALICE]);
+
+        simulator.token_custom_fees(TOKEN, [fractional_with(0, 0, FeeAssessmentMethod::Inclusive)]);
+        assert_eq!(simulator.simulate(&fungible_transfer(1000))[0].payer_account_id_list, [BOB]);
+    }
+
+    #[test]
+    fn collectors_are_exempt() {
+        let mut exempt = fractional(0, 0);
+        exempt.fee_collector_account_id = Some(OTHER_COLLECTOR);
+        exempt.all_collectors_are_exempt = true;
+
+        let mut tx = TransferTransaction::new();
+        tx.token_transfer(TOKEN, COLLECTOR, -100).token_transfer(TOKEN, BOB, 100);
+
+        // `COLLECTOR` is exempt from its own fee, and from `exempt` because all collectors are exempt.
+        let assessed = CustomFeeSimulator::new()
+            .token_custom_fees(TOKEN, [fractional(0, 0), exempt])
+            .simulate(&tx);
+
+        assert_eq!(assessed, []);
+    }
+
+    #[test]
+    fn royalty_fee() {
+        let mut tx = TransferTransaction::new();
+        tx.nft_transfer(NFT.nft(1), ALICE, BOB)
+            .hbar_transfer(BOB, Hbar::from_tinybars(-1000))
+            .hbar_transfer(ALICE, Hbar::from_tinybars(1000));
+
+        let assessed = CustomFeeSimulator::new()
+            .token_custom_fees(NFT, [royalty(Some(FixedFeeData::from_hbar(Hbar::new(1))))])
+            .simulate(&tx);
+
+        assert_eq!(
+            assessed,
+            [AssessedCustomFee {
+                amount: 50,
+                token_id: None,
+                fee_collector_account_id: Some(COLLECTOR),
+                payer_account_id_list: Vec::from([ALICE]),
+            }]
+        );
+    }
+
+    #[test]
+    fn royalty_fallback_fee() {
+        let mut tx = TransferTransaction::new();
+        tx.nft_transfer(NFT.nft(1), ALICE, BOB);
+
+        let assessed = CustomFeeSimulator::new()
+            .token_custom_fees(NFT, [royalty(Some(FixedFeeData::from_hbar(Hbar::new(1))))])
+            .simulate(&tx);
+
+        assert_eq!(
+            assessed,
+            [AssessedCustomFee {
+                amount: Hbar::new(1).to_tinybars(),
+                token_id: None,
+                fee_collector_account_id: Some(COLLECTOR),
+                payer_account_id_list: Vec::from([BOB]),
+            }]
+        );
+    }
+
+    #[test]
+    fn unknown_token_has_no_fees() {
+        assert_eq!(CustomFeeSimulator::new().simulate(&fungible_transfer(100)), []);
+    }
+}
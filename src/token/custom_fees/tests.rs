@@ -4,7 +4,9 @@ use hedera_proto::services;
 use crate::token::custom_fees::{
     AnyCustomFee,
     CustomFee,
+    CustomFeeLimit,
     Fee,
+    FixedFee,
     FixedFeeData,
     FractionalFeeData,
     RoyaltyFeeData,
@@ -206,3 +208,38 @@ fn royalty_fee_can_be_created_from_protobuf() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn fixed_custom_fee_can_convert_to_protobuf() -> anyhow::Result<()> {
+    let fixed_fee = FixedFee {
+        fee: FixedFeeData { denominating_token_id: Some(TokenId::from(2)), amount: 1000 },
+        fee_collector_account_id: Some(AccountId::from(1)),
+        all_collectors_are_exempt: false,
+    };
+
+    let fixed_fee_proto = fixed_fee.to_protobuf();
+
+    assert_eq!(fixed_fee_proto.fixed_fee, Some(fixed_fee.fee.to_protobuf()));
+    assert_eq!(fixed_fee_proto.fee_collector_account_id, Some(AccountId::from(1).to_protobuf()));
+
+    assert_eq!(FixedFee::from_protobuf(fixed_fee_proto)?, fixed_fee);
+
+    Ok(())
+}
+
+#[test]
+fn custom_fee_limit_can_convert_to_protobuf() -> anyhow::Result<()> {
+    let limit = CustomFeeLimit {
+        account_id: Some(AccountId::from(3)),
+        fees: Vec::from([FixedFeeData { denominating_token_id: None, amount: 500 }]),
+    };
+
+    let limit_proto = limit.to_protobuf();
+
+    assert_eq!(limit_proto.account_id, Some(AccountId::from(3).to_protobuf()));
+    assert_eq!(limit_proto.fees.len(), 1);
+
+    assert_eq!(CustomFeeLimit::from_protobuf(limit_proto)?, limit);
+
+    Ok(())
+}
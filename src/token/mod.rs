@@ -21,6 +21,7 @@
 mod assessed_custom_fee;
 mod custom_fees;
 mod nft_id;
+mod token_airdrop_flow;
 mod token_airdrop_transaction;
 mod token_associate_transaction;
 mod token_association;
@@ -36,7 +37,9 @@ mod token_grant_kyc_transaction;
 mod token_id;
 mod token_info;
 mod token_info_query;
+mod token_key_update_flow;
 mod token_key_validation_type;
+mod token_mint_flow;
 mod token_mint_transaction;
 mod token_nft_info;
 mod token_nft_info_query;
@@ -44,6 +47,7 @@ mod token_nft_transfer;
 mod token_pause_transaction;
 mod token_reject_flow;
 mod token_reject_transaction;
+mod token_relationship;
 mod token_revoke_kyc_transaction;
 mod token_supply_type;
 mod token_type;
@@ -57,6 +61,8 @@ pub use assessed_custom_fee::AssessedCustomFee;
 pub use custom_fees::{
     AnyCustomFee,
     CustomFee,
+    CustomFeeLimit,
+    CustomFeeSimulator,
     Fee,
     FeeAssessmentMethod,
     FixedFee,
@@ -67,6 +73,7 @@ pub use custom_fees::{
     RoyaltyFeeData,
 };
 pub use nft_id::NftId;
+pub use token_airdrop_flow::TokenAirdropFlow;
 pub use token_airdrop_transaction::{
     TokenAirdropTransaction,
     TokenAirdropTransactionData,
@@ -118,7 +125,12 @@ pub use token_info_query::{
     TokenInfoQuery,
     TokenInfoQueryData,
 };
+pub use token_key_update_flow::{
+    TokenKeyType,
+    TokenKeyUpdateFlow,
+};
 pub use token_key_validation_type::TokenKeyValidation;
+pub use token_mint_flow::TokenMintFlow;
 pub use token_mint_transaction::{
     TokenMintTransaction,
     TokenMintTransactionData,
@@ -138,6 +150,7 @@ pub use token_reject_transaction::{
     TokenRejectTransaction,
     TokenRejectTransactionData,
 };
+pub use token_relationship::TokenRelationship;
 pub use token_revoke_kyc_transaction::{
     TokenRevokeKycTransaction,
     TokenRevokeKycTransactionData,
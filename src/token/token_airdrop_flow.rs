@@ -0,0 +1,1116 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use futures_core::future::BoxFuture;
+use time::OffsetDateTime;
+use tokio::time::sleep;
+
+use super::token_mint_flow::MAX_TRANSACTION_LIFETIME;
+use super::{
+    NftId,
+    TokenAirdropTransaction,
+    TokenCancelAirdropTransaction,
+    TokenId,
+};
+#[cfg(feature = "mirror-rest")]
+use crate::mirror_rest;
+use crate::pending_airdrop_id::PendingAirdropId;
+use crate::signer::AnySigner;
+use crate::{
+    AccountId,
+    Client,
+    Error,
+    PendingAirdropRecord,
+    PrivateKey,
+    PublicKey,
+    Status,
+    TransactionId,
+    TransactionRecord,
+    TransactionRecordQuery,
+};
+
+/// Airdrop tokens to any number of recipients, and cancel the airdrops that weren't claimed.
+///
+/// The operation of this flow is as follows:
+/// 1. Pack the airdrops into as few [`TokenAirdropTransaction`]s as possible,
+///    keeping each under [`max_transfers_per_transaction`](Self::max_transfers_per_transaction) transfers.
+/// 2. Execute the transactions one after another, collecting the [`PendingAirdropRecord`]s from their records.
+///
+/// Once the airdrops are done, [`cancel_pending_airdrops`](Self::cancel_pending_airdrops)
+/// cancels the pending airdrops with [`TokenCancelAirdropTransaction`]s.
+///
+/// If the flow fails part way through, calling `execute` again only executes the remaining transactions.
+/// A transaction whose outcome is unknown (for example because its record timed out) is checked first,
+/// using the mirror node (with the `mirror-rest` feature) once the network no longer has its record.
+#[derive(Default, Debug)]
+pub struct TokenAirdropFlow {
+    node_account_ids: Option<Vec<AccountId>>,
+    sender: Option<AccountId>,
+    airdrops: Vec<Airdrop>,
+    max_transfers_per_transaction: Option<usize>,
+    signers: Vec<AnySigner>,
+    batches: Option<Vec<Vec<Airdrop>>>,
+    executed: usize,
+    // the transaction for `batches[executed]`, if it was sent but we don't know if it reached consensus.
+    submitted: Option<TransactionId>,
+    pending_airdrops: Vec<PendingAirdropRecord>,
+    // the first token transfer whose combined amount was out of range, reported by `execute`.
+    overflow: Option<(TokenId, AccountId)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Airdrop {
+    Token { token_id: TokenId, receiver: AccountId, amount: i64 },
+    Nft { nft_id: NftId, receiver: AccountId },
+}
+
+/// What happened to a transaction that was sent, but whose record we never got.
+#[derive(Debug, Clone)]
+enum SubmittedState {
+    NotExecuted,
+    Executed(Vec<PendingAirdropRecord>),
+}
+
+/// The network operations [`TokenAirdropFlow::execute`] is made of.
+trait AirdropNetwork: Sync {
+    fn operator_account_id(&self) -> Option<AccountId>;
+
+    /// Executes `transaction` and returns its record.
+    fn airdrop(
+        &self,
+        transaction: TokenAirdropTransaction,
+    ) -> BoxFuture<'_, crate::Result<TransactionRecord>>;
+
+    /// Returns the record of `transaction_id` without validating its status.
+    fn record(
+        &self,
+        transaction_id: TransactionId,
+    ) -> BoxFuture<'_, crate::Result<TransactionRecord>>;
+
+    /// Looks `transaction_id`, which airdropped `batch` from `sender`, up on the mirror node.
+    ///
+    /// Returns `None` if the mirror node has no record of it, or the `mirror-rest` feature is disabled.
+    fn mirror_record<'a>(
+        &'a self,
+        sender: AccountId,
+        batch: &'a [Airdrop],
+        transaction_id: TransactionId,
+    ) -> BoxFuture<'a, crate::Result<Option<SubmittedState>>>;
+}
+
+struct ClientAirdropNetwork<'a> {
+    client: &'a Client,
+    node_account_ids: Option<Vec<AccountId>>,
+    timeout: Option<Duration>,
+}
+
+#[cfg(feature = "mirror-rest")]
+#[derive(serde_derive::Deserialize)]
+struct MirrorTransaction {
+    result: String,
+}
+
+#[cfg(feature = "mirror-rest")]
+#[derive(serde_derive::Deserialize)]
+struct MirrorAirdrop {
+    #[serde(default)]
+    amount: Option<u64>,
+    receiver_id: String,
+    serial_number: Option<u64>,
+    token_id: String,
+}
+
+impl AirdropNetwork for ClientAirdropNetwork<'_> {
+    fn operator_account_id(&self) -> Option<AccountId> {
+        self.client.get_operator_account_id()
+    }
+
+    fn airdrop(
+        &self,
+        mut transaction: TokenAirdropTransaction,
+    ) -> BoxFuture<'_, crate::Result<TransactionRecord>> {
+        Box::pin(async move {
+            transaction
+                .execute_with_optional_timeout(self.client, self.timeout)
+                .await?
+                .get_record_query()
+                .execute_with_optional_timeout(self.client, self.timeout)
+                .await
+        })
+    }
+
+    fn record(
+        &self,
+        transaction_id: TransactionId,
+    ) -> BoxFuture<'_, crate::Result<TransactionRecord>> {
+        Box::pin(async move {
+            let mut query = TransactionRecordQuery::new();
+
+            query.transaction_id(transaction_id);
+
+            if let Some(node_account_ids) = &self.node_account_ids {
+                query.node_account_ids(node_account_ids.iter().copied());
+            }
+
+            query
+                .validate_status(false)
+                .execute_with_optional_timeout(self.client, self.timeout)
+                .await
+        })
+    }
+
+    #[cfg(not(feature = "mirror-rest"))]
+    fn mirror_record<'a>(
+        &'a self,
+        _sender: AccountId,
+        _batch: &'a [Airdrop],
+        _transaction_id: TransactionId,
+    ) -> BoxFuture<'a, crate::Result<Option<SubmittedState>>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    #[cfg(feature = "mirror-rest")]
+    fn mirror_record<'a>(
+        &'a self,
+        sender: AccountId,
+        batch: &'a [Airdrop],
+        transaction_id: TransactionId,
+    ) -> BoxFuture<'a, crate::Result<Option<SubmittedState>>> {
+        Box::pin(async move {
+            let path = format!(
+                "/api/v1/transactions/{}",
+                mirror_rest::transaction_id_path_segment(&transaction_id)
+            );
+
+            let Some(transactions) = mirror_rest::get_all_if_found::<MirrorTransaction>(
+                self.client,
+                &path,
+                "transactions",
+            )
+            .await?
+            else {
+                return Ok(None);
+            };
+
+            if transactions.is_empty() {
+                return Ok(None);
+            }
+
+            // duplicates submitted to other nodes show up as well, with their own result.
+            if !transactions.iter().any(|it| it.result == "SUCCESS") {
+                return Ok(Some(SubmittedState::NotExecuted));
+            }
+
+            // the mirror node doesn't list the pending airdrops a transaction created,
+            // so take the outstanding ones from `sender` that this batch could have created.
+            let airdrops = mirror_rest::get_all::<MirrorAirdrop>(
+                self.client,
+                &format!("/api/v1/accounts/{sender}/airdrops/outstanding"),
+                "airdrops",
+            )
+            .await?;
+
+            let pending = airdrops
+                .into_iter()
+                .filter_map(|it| {
+                    let receiver_id: AccountId = it.receiver_id.parse().ok()?;
+                    let token_id: TokenId = it.token_id.parse().ok()?;
+
+                    let record = match it.serial_number {
+                        Some(serial) => PendingAirdropRecord {
+                            pending_airdrop_id: PendingAirdropId::new_nft_id(
+                                sender,
+                                receiver_id,
+                                token_id.nft(serial),
+                            ),
+                            pending_airdrop_value: None,
+                        },
+                        None => PendingAirdropRecord {
+                            pending_airdrop_id: PendingAirdropId::new_token_id(
+                                sender,
+                                receiver_id,
+                                token_id,
+                            ),
+                            pending_airdrop_value: it.amount,
+                        },
+                    };
+
+                    let in_batch = batch.iter().any(|airdrop| match *airdrop {
+                        Airdrop::Token { token_id, receiver, .. } => {
+                            record.pending_airdrop_id.token_id == Some(token_id)
+                                && receiver == receiver_id
+                        }
+                        Airdrop::Nft { nft_id, receiver } => {
+                            record.pending_airdrop_id.nft_id == Some(nft_id)
+                                && receiver == receiver_id
+                        }
+                    });
+
+                    in_batch.then_some(record)
+                })
+                .collect();
+
+            Ok(Some(SubmittedState::Executed(pending)))
+        })
+    }
+}
+
+impl Airdrop {
+    fn token_id(&self) -> TokenId {
+        match self {
+            Self::Token { token_id, .. } => *token_id,
+            Self::Nft { nft_id, .. } => nft_id.token_id,
+        }
+    }
+}
+
+impl TokenAirdropFlow {
+    /// The most transfers the network accepts in a single [`TokenAirdropTransaction`].
+    pub const MAX_TRANSFERS_PER_TRANSACTION: usize = 10;
+
+    /// The most pending airdrops the network accepts in a single [`TokenCancelAirdropTransaction`].
+    pub const MAX_PENDING_AIRDROPS_PER_CANCEL: usize = 10;
+
+    /// Create a new `TokenAirdropFlow`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn require_not_started(&self) {
+        assert!(
+            self.batches.is_none(),
+            "`TokenAirdropFlow` can't be changed after it has started airdropping"
+        );
+    }
+
+    /// Returns the account IDs of the nodes the transactions may be submitted to.
+    #[must_use]
+    pub fn get_node_account_ids(&self) -> Option<&[AccountId]> {
+        self.node_account_ids.as_deref()
+    }
+
+    /// Sets the account IDs of the nodes the transactions may be submitted to.
+    pub fn node_account_ids(
+        &mut self,
+        node_account_ids: impl IntoIterator<Item = AccountId>,
+    ) -> &mut Self {
+        self.node_account_ids = Some(node_account_ids.into_iter().collect());
+
+        self
+    }
+
+    /// Returns the account that sends the airdropped tokens.
+    #[must_use]
+    pub fn get_sender(&self) -> Option<AccountId> {
+        self.sender
+    }
+
+    /// Sets the account that sends the airdropped tokens.
+    ///
+    /// Defaults to the client's operator.
+    ///
+    /// # Panics
+    /// - If the flow has already started airdropping.
+    pub fn sender(&mut self, sender: AccountId) -> &mut Self {
+        self.require_not_started();
+        self.sender = Some(sender);
+
+        self
+    }
+
+    /// Adds an airdrop of `amount` of `token_id` to `receiver`.
+    ///
+    /// Airdrops of the same token to the same receiver are combined.
+    /// If the combined amount is out of range, [`execute`](Self::execute) fails with
+    /// [`Error::TokenAmountOverflow`].
+    ///
+    /// # Panics
+    /// - If the flow has already started airdropping.
+    pub fn token_transfer(
+        &mut self,
+        token_id: TokenId,
+        receiver: AccountId,
+        amount: i64,
+    ) -> &mut Self {
+        self.require_not_started();
+
+        let existing = self.airdrops.iter_mut().find_map(|it| match it {
+            Airdrop::Token { token_id: id, receiver: to, amount }
+                if *id == token_id && *to == receiver =>
+            {
+                Some(amount)
+            }
+            _ => None,
+        });
+
+        // the sender's debit is the negated amount, so `i64::MIN` is out of range too.
+        let combined = match &existing {
+            Some(existing) => existing.checked_add(amount),
+            None => Some(amount),
+        }
+        .filter(|it| *it != i64::MIN);
+
+        match (existing, combined) {
+            (_, None) => {
+                self.overflow.get_or_insert((token_id, receiver));
+            }
+            (Some(existing), Some(combined)) => *existing = combined,
+            (None, Some(amount)) => {
+                self.airdrops.push(Airdrop::Token { token_id, receiver, amount });
+            }
+        }
+
+        self
+    }
+
+    /// Adds an airdrop of the NFT `nft_id` to `receiver`.
+    ///
+    /// # Panics
+    /// - If the flow has already started airdropping.
+    pub fn nft_transfer(&mut self, nft_id: NftId, receiver: AccountId) -> &mut Self {
+        self.require_not_started();
+        self.airdrops.push(Airdrop::Nft { nft_id, receiver });
+
+        self
+    }
+
+    /// Returns the most transfers that are packed into a single transaction.
+    #[must_use]
+    pub fn get_max_transfers_per_transaction(&self) -> usize {
+        self.max_transfers_per_transaction.unwrap_or(Self::MAX_TRANSFERS_PER_TRANSACTION)
+    }
+
+    /// Sets the most transfers that are packed into a single transaction.
+    ///
+    /// Each receiver of a token, each NFT, and the sender's debit of each token count as one transfer.
+    ///
+    /// Defaults to [`MAX_TRANSFERS_PER_TRANSACTION`](Self::MAX_TRANSFERS_PER_TRANSACTION).
+    ///
+    /// # Panics
+    /// - If `max` is less than 2, since a token airdrop needs both a debit and a credit.
+    /// - If the flow has already started airdropping.
+    pub fn max_transfers_per_transaction(&mut self, max: usize) -> &mut Self {
+        assert!(max >= 2, "a transaction must fit at least 2 transfers");
+
+        self.require_not_started();
+        self.max_transfers_per_transaction = Some(max);
+
+        self
+    }
+
+    /// Adds a key to sign the transactions with, usually the sender's key.
+    pub fn sign(&mut self, key: PrivateKey) -> &mut Self {
+        self.sign_signer(AnySigner::PrivateKey(key))
+    }
+
+    /// Adds a signer to sign the transactions with, usually for the sender's key.
+    pub fn sign_with<F: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static>(
+        &mut self,
+        public_key: PublicKey,
+        signer: F,
+    ) -> &mut Self {
+        self.sign_signer(AnySigner::arbitrary(Box::new(public_key), signer))
+    }
+
+    fn sign_signer(&mut self, signer: AnySigner) -> &mut Self {
+        if !self.signers.iter().any(|it| it.public_key() == signer.public_key()) {
+            self.signers.push(signer);
+        }
+
+        self
+    }
+
+    /// Returns the number of transactions the airdrops are packed into.
+    #[must_use]
+    pub fn transaction_count(&self) -> usize {
+        match &self.batches {
+            Some(batches) => batches.len(),
+            None => self.plan().len(),
+        }
+    }
+
+    /// Returns the pending airdrops created by the executed transactions that haven't been cancelled.
+    #[must_use]
+    pub fn get_pending_airdrops(&self) -> &[PendingAirdropRecord] {
+        &self.pending_airdrops
+    }
+
+    // Greedily fills each transaction, airdrops of the same token are kept together
+    // so that they share the sender's debit.
+    fn plan(&self) -> Vec<Vec<Airdrop>> {
+        let max = self.get_max_transfers_per_transaction();
+
+        let mut token_ids: Vec<TokenId> = Vec::new();
+
+        for airdrop in &self.airdrops {
+            if !token_ids.contains(&airdrop.token_id()) {
+                token_ids.push(airdrop.token_id());
+            }
+        }
+
+        let mut batches = Vec::new();
+        let mut batch = Vec::new();
+        let mut used = 0;
+        let mut debited = HashSet::new();
+
+        for token_id in token_ids {
+            for airdrop in self.airdrops.iter().filter(|it| it.token_id() == token_id) {
+                let cost = match airdrop {
+                    Airdrop::Token { .. } if !debited.contains(&token_id) => 2,
+                    Airdrop::Token { .. } | Airdrop::Nft { .. } => 1,
+                };
+
+                if used + cost > max {
+                    batches.push(std::mem::take(&mut batch));
+                    used = 0;
+                    debited.clear();
+                }
+
+                if let Airdrop::Token { .. } = airdrop {
+                    used += usize::from(debited.insert(token_id));
+                }
+
+                used += 1;
+                batch.push(*airdrop);
+            }
+        }
+
+        if !batch.is_empty() {
+            batches.push(batch);
+        }
+
+        batches
+    }
+
+    fn make_airdrop_transaction(
+        &self,
+        sender: AccountId,
+        batch: &[Airdrop],
+        transaction_id: TransactionId,
+    ) -> TokenAirdropTransaction {
+        let mut transaction = TokenAirdropTransaction::new();
+
+        for airdrop in batch {
+            match *airdrop {
+                Airdrop::Token { token_id, receiver, amount } => {
+                    transaction
+                        .token_transfer(token_id, sender, -amount)
+                        .token_transfer(token_id, receiver, amount);
+                }
+                Airdrop::Nft { nft_id, receiver } => {
+                    transaction.nft_transfer(nft_id, sender, receiver);
+                }
+            }
+        }
+
+        transaction.transaction_id(transaction_id).regenerate_transaction_id(false);
+
+        if let Some(node_account_ids) = &self.node_account_ids {
+            transaction.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        for signer in &self.signers {
+            transaction.sign_signer(signer.clone());
+        }
+
+        transaction
+    }
+
+    fn collect_pending_airdrops(&mut self, pending_airdrops: Vec<PendingAirdropRecord>) {
+        for pending in pending_airdrops {
+            // an airdrop to a receiver that already has a pending airdrop updates the existing one.
+            self.pending_airdrops.retain(|it| it.pending_airdrop_id != pending.pending_airdrop_id);
+            self.pending_airdrops.push(pending);
+        }
+    }
+
+    /// Executes every airdrop transaction that hasn't been executed yet.
+    ///
+    /// Returns the pending airdrops created by the executed transactions.
+    ///
+    /// # Errors
+    /// - [`Error::TokenAmountOverflow`] if the combined amount of a token transfer is out of range.
+    /// - [`Error::NoPayerAccountOrTransactionId`] if the client has no operator.
+    /// - [`Error::ReceiptStatus`] if an airdrop transaction failed.
+    /// - Any other error from executing an airdrop transaction or querying its record.
+    ///
+    /// In all of these cases the executed transactions are kept, and calling `execute` again resumes the flow.
+    pub async fn execute(&mut self, client: &Client) -> crate::Result<&[PendingAirdropRecord]> {
+        self.execute_with_optional_timeout(client, None).await
+    }
+
+    /// Executes every airdrop transaction that hasn't been executed yet.
+    ///
+    /// Returns the pending airdrops created by the executed transactions.
+    ///
+    /// # Errors
+    /// - [`Error::TokenAmountOverflow`] if the combined amount of a token transfer is out of range.
+    /// - [`Error::NoPayerAccountOrTransactionId`] if the client has no operator.
+    /// - [`Error::ReceiptStatus`] if an airdrop transaction failed.
+    /// - Any other error from executing an airdrop transaction or querying its record.
+    ///
+    /// In all of these cases the executed transactions are kept, and calling `execute` again resumes the flow.
+    pub async fn execute_with_timeout(
+        &mut self,
+        client: &Client,
+        timeout_per_transaction: Duration,
+    ) -> crate::Result<&[PendingAirdropRecord]> {
+        self.execute_with_optional_timeout(client, Some(timeout_per_transaction)).await
+    }
+
+    async fn execute_with_optional_timeout(
+        &mut self,
+        client: &Client,
+        timeout: Option<Duration>,
+    ) -> crate::Result<&[PendingAirdropRecord]> {
+        let network = ClientAirdropNetwork {
+            client,
+            node_account_ids: self.node_account_ids.clone(),
+            timeout,
+        };
+
+        self.execute_on(&network).await
+    }
+
+    async fn execute_on(
+        &mut self,
+        network: &impl AirdropNetwork,
+    ) -> crate::Result<&[PendingAirdropRecord]> {
+        if let Some((token_id, receiver)) = self.overflow {
+            return Err(Error::TokenAmountOverflow { token_id, receiver });
+        }
+
+        let payer = network.operator_account_id().ok_or(Error::NoPayerAccountOrTransactionId)?;
+        let sender = self.sender.unwrap_or(payer);
+
+        let batches = match self.batches.take() {
+            Some(batches) => batches,
+            None => self.plan(),
+        };
+
+        let result = self.execute_batches(network, payer, sender, &batches).await;
+
+        self.batches = Some(batches);
+
+        result?;
+
+        Ok(&self.pending_airdrops)
+    }
+
+    async fn execute_batches(
+        &mut self,
+        network: &impl AirdropNetwork,
+        payer: AccountId,
+        sender: AccountId,
+        batches: &[Vec<Airdrop>],
+    ) -> crate::Result<()> {
+        // find out what happened to the transaction that was in flight when the flow last failed.
+        if let Some(transaction_id) = self.submitted {
+            let state =
+                resolve_submitted(network, sender, &batches[self.executed], transaction_id).await?;
+
+            self.submitted = None;
+
+            if let SubmittedState::Executed(pending_airdrops) = state {
+                self.collect_pending_airdrops(pending_airdrops);
+                self.executed += 1;
+            }
+        }
+
+        while let Some(batch) = batches.get(self.executed) {
+            // generate the ID up front, so that the transaction can be resolved if we lose track of it.
+            let transaction_id = TransactionId::generate(payer);
+
+            self.submitted = Some(transaction_id);
+
+            let transaction = self.make_airdrop_transaction(sender, batch, transaction_id);
+
+            let record = match network.airdrop(transaction).await {
+                Ok(record) => record,
+                Err(error) => {
+                    // the transaction was rejected, so nothing was airdropped.
+                    if let Error::TransactionPreCheckStatus { .. } | Error::ReceiptStatus { .. } =
+                        error
+                    {
+                        self.submitted = None;
+                    }
+
+                    return Err(error);
+                }
+            };
+
+            self.submitted = None;
+            self.collect_pending_airdrops(record.pending_airdrop_records);
+            self.executed += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Waits until `deadline`, then cancels every pending airdrop that hasn't been claimed.
+    ///
+    /// Returns the IDs of the cancelled airdrops.
+    ///
+    /// # Errors
+    /// - [`Error::ReceiptStatus`] if a cancel transaction failed for any reason other than the airdrop being claimed.
+    /// - Any other error from executing a cancel transaction or querying its receipt.
+    pub async fn cancel_pending_airdrops_after(
+        &mut self,
+        client: &Client,
+        deadline: OffsetDateTime,
+    ) -> crate::Result<Vec<PendingAirdropId>> {
+        if let Ok(remaining) = Duration::try_from(deadline - OffsetDateTime::now_utc()) {
+            sleep(remaining).await;
+        }
+
+        self.cancel_pending_airdrops(client).await
+    }
+
+    /// Cancels every pending airdrop that hasn't been claimed,
+    /// in batches of [`MAX_PENDING_AIRDROPS_PER_CANCEL`](Self::MAX_PENDING_AIRDROPS_PER_CANCEL).
+    ///
+    /// Airdrops that were claimed in the meantime are skipped.
+    ///
+    /// Returns the IDs of the cancelled airdrops.
+    ///
+    /// # Errors
+    /// - [`Error::ReceiptStatus`] if a cancel transaction failed for any reason other than the airdrop being claimed.
+    /// - Any other error from executing a cancel transaction or querying its receipt.
+    ///
+    /// In all of these cases the airdrops that were resolved are forgotten,
+    /// and calling `cancel_pending_airdrops` again cancels the rest.
+    pub async fn cancel_pending_airdrops(
+        &mut self,
+        client: &Client,
+    ) -> crate::Result<Vec<PendingAirdropId>> {
+        let mut cancelled = Vec::new();
+
+        while !self.pending_airdrops.is_empty() {
+            let count =
+                usize::min(self.pending_airdrops.len(), Self::MAX_PENDING_AIRDROPS_PER_CANCEL);
+
+            let ids: Vec<_> =
+                self.pending_airdrops[..count].iter().map(|it| it.pending_airdrop_id).collect();
+
+            match self.cancel(client, ids.clone()).await {
+                Ok(()) => {
+                    self.pending_airdrops.drain(..count);
+                    cancelled.extend(ids);
+                }
+
+                // at least one of the airdrops was claimed, so fall back to cancelling them one at a time.
+                Err(Error::ReceiptStatus { status: Status::InvalidPendingAirdropId, .. }) => {
+                    for id in ids {
+                        match self.cancel(client, Vec::from([id])).await {
+                            Ok(()) => cancelled.push(id),
+                            Err(Error::ReceiptStatus {
+                                status: Status::InvalidPendingAirdropId,
+                                ..
+                            }) => {}
+                            Err(error) => return Err(error),
+                        }
+
+                        self.pending_airdrops.retain(|it| it.pending_airdrop_id != id);
+                    }
+                }
+
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(cancelled)
+    }
+
+    async fn cancel(&self, client: &Client, ids: Vec<PendingAirdropId>) -> crate::Result<()> {
+        let mut transaction = TokenCancelAirdropTransaction::new();
+
+        transaction.pending_airdrop_ids(ids);
+
+        if let Some(node_account_ids) = &self.node_account_ids {
+            transaction.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        for signer in &self.signers {
+            transaction.sign_signer(signer.clone());
+        }
+
+        transaction.execute(client).await?.get_receipt(client).await?;
+
+        Ok(())
+    }
+}
+
+async fn resolve_submitted(
+    network: &impl AirdropNetwork,
+    sender: AccountId,
+    batch: &[Airdrop],
+    transaction_id: TransactionId,
+) -> crate::Result<SubmittedState> {
+    let error = match network.record(transaction_id).await {
+        Ok(record) if record.receipt.status == Status::Success => {
+            return Ok(SubmittedState::Executed(record.pending_airdrop_records))
+        }
+        Ok(_) => return Ok(SubmittedState::NotExecuted),
+        Err(error) => error,
+    };
+
+    if !is_record_not_found(&error) {
+        return Err(error);
+    }
+
+    // the network forgets records after a few minutes, but the mirror node keeps them.
+    if let Some(state) = network.mirror_record(sender, batch, transaction_id).await? {
+        return Ok(state);
+    }
+
+    // neither knows about the transaction, which can't change once it's too old to reach consensus.
+    let expired = OffsetDateTime::now_utc() - transaction_id.valid_start > MAX_TRANSACTION_LIFETIME;
+
+    if expired {
+        Ok(SubmittedState::NotExecuted)
+    } else {
+        Err(error)
+    }
+}
+
+fn is_record_not_found(error: &Error) -> bool {
+    match error {
+        Error::TimedOut(error) => is_record_not_found(error),
+        Error::QueryPreCheckStatus { status, .. } => {
+            matches!(status, Status::RecordNotFound | Status::ReceiptNotFound)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    };
+
+    use assert_matches::assert_matches;
+    use futures_core::future::BoxFuture;
+    use parking_lot::Mutex;
+    use time::{
+        Duration,
+        OffsetDateTime,
+    };
+
+    use super::{
+        Airdrop,
+        AirdropNetwork,
+        SubmittedState,
+    };
+    use crate::pending_airdrop_id::PendingAirdropId;
+    use crate::{
+        AccountId,
+        Error,
+        PendingAirdropRecord,
+        Status,
+        TokenAirdropFlow,
+        TokenAirdropTransaction,
+        TokenId,
+        TransactionId,
+        TransactionReceipt,
+        TransactionRecord,
+    };
+
+    const TOKEN_A: TokenId = TokenId::new(0, 0, 1000);
+    const TOKEN_B: TokenId = TokenId::new(0, 0, 2000);
+
+    const PAYER: AccountId = AccountId::new(0, 0, 5005);
+
+    fn receiver(num: u64) -> AccountId {
+        AccountId::new(0, 0, 5000 + num)
+    }
+
+    fn pending_airdrop(num: u64) -> PendingAirdropRecord {
+        PendingAirdropRecord {
+            pending_airdrop_id: PendingAirdropId::new_token_id(PAYER, receiver(num), TOKEN_A),
+            pending_airdrop_value: Some(1),
+        }
+    }
+
+    fn record(
+        status: Status,
+        pending_airdrop_records: Vec<PendingAirdropRecord>,
+    ) -> TransactionRecord {
+        TransactionRecord {
+            receipt: TransactionReceipt { status, ..crate::transaction_receipt::make_receipt() },
+            pending_airdrop_records,
+            ..crate::transaction_record::make_record(None, None)
+        }
+    }
+
+    /// A network that airdrops successfully, except for every airdrop after the first `airdrops_before_failure`.
+    struct FakeNetwork {
+        airdrops_before_failure: AtomicUsize,
+        airdropped: Mutex<Vec<TransactionId>>,
+        record: Option<TransactionRecord>,
+        mirror: Option<SubmittedState>,
+    }
+
+    impl FakeNetwork {
+        fn new() -> Self {
+            Self {
+                airdrops_before_failure: AtomicUsize::new(usize::MAX),
+                airdropped: Mutex::new(Vec::new()),
+                record: None,
+                mirror: None,
+            }
+        }
+    }
+
+    impl AirdropNetwork for FakeNetwork {
+        fn operator_account_id(&self) -> Option<AccountId> {
+            Some(PAYER)
+        }
+
+        fn airdrop(
+            &self,
+            transaction: TokenAirdropTransaction,
+        ) -> BoxFuture<'_, crate::Result<TransactionRecord>> {
+            Box::pin(async move {
+                let fail = self
+                    .airdrops_before_failure
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |it| it.checked_sub(1))
+                    .is_err();
+
+                if fail {
+                    return Err(Error::GrpcStatus(tonic::Status::unavailable("node is down")));
+                }
+
+                self.airdropped.lock().push(transaction.get_transaction_id().unwrap());
+
+                Ok(record(Status::Success, Vec::new()))
+            })
+        }
+
+        fn record(
+            &self,
+            transaction_id: TransactionId,
+        ) -> BoxFuture<'_, crate::Result<TransactionRecord>> {
+            Box::pin(async move {
+                self.record.clone().ok_or(Error::QueryPreCheckStatus {
+                    status: Status::RecordNotFound,
+                    transaction_id: Box::new(transaction_id),
+                })
+            })
+        }
+
+        fn mirror_record<'a>(
+            &'a self,
+            _sender: AccountId,
+            _batch: &'a [Airdrop],
+            _transaction_id: TransactionId,
+        ) -> BoxFuture<'a, crate::Result<Option<SubmittedState>>> {
+            Box::pin(async move { Ok(self.mirror.clone()) })
+        }
+    }
+
+    /// A flow of one batch, whose transaction was sent at `valid_start` without us finding out what happened to it.
+    fn submitted_at(valid_start: OffsetDateTime) -> TokenAirdropFlow {
+        let mut flow = TokenAirdropFlow::new();
+
+        flow.token_transfer(TOKEN_A, receiver(1), 1);
+
+        flow.batches = Some(flow.plan());
+        flow.submitted =
+            Some(TransactionId { account_id: PAYER, valid_start, nonce: None, scheduled: false });
+
+        flow
+    }
+
+    #[test]
+    fn get_set() {
+        let mut flow = TokenAirdropFlow::new();
+
+        flow.sender(AccountId::new(0, 0, 3)).max_transfers_per_transaction(5);
+
+        assert_eq!(flow.get_sender(), Some(AccountId::new(0, 0, 3)));
+        assert_eq!(flow.get_max_transfers_per_transaction(), 5);
+        assert!(flow.get_pending_airdrops().is_empty());
+    }
+
+    #[test]
+    fn combines_transfers_to_same_receiver() {
+        let mut flow = TokenAirdropFlow::new();
+
+        flow.token_transfer(TOKEN_A, receiver(1), 10).token_transfer(TOKEN_A, receiver(1), 5);
+
+        assert_eq!(
+            flow.airdrops,
+            [Airdrop::Token { token_id: TOKEN_A, receiver: receiver(1), amount: 15 }]
+        );
+    }
+
+    #[test]
+    fn combined_transfer_overflow() {
+        let mut flow = TokenAirdropFlow::new();
+
+        flow.token_transfer(TOKEN_A, receiver(1), i64::MAX).token_transfer(TOKEN_A, receiver(1), 1);
+
+        assert_eq!(
+            flow.airdrops,
+            [Airdrop::Token { token_id: TOKEN_A, receiver: receiver(1), amount: i64::MAX }]
+        );
+        assert_eq!(flow.overflow, Some((TOKEN_A, receiver(1))));
+    }
+
+    #[test]
+    fn transfer_of_min_amount_overflows() {
+        let mut flow = TokenAirdropFlow::new();
+
+        flow.token_transfer(TOKEN_A, receiver(1), i64::MIN);
+
+        assert!(flow.airdrops.is_empty());
+        assert_eq!(flow.overflow, Some((TOKEN_A, receiver(1))));
+    }
+
+    #[test]
+    fn plan_shares_sender_debit() {
+        let mut flow = TokenAirdropFlow::new();
+
+        for num in 0..5000 {
+            flow.token_transfer(TOKEN_A, receiver(num), 1);
+        }
+
+        let plan = flow.plan();
+
+        // 9 receivers and the sender's debit per transaction.
+        assert_eq!(plan.len(), 556);
+        assert!(plan[..555].iter().all(|batch| batch.len() == 9));
+        assert_eq!(plan[555].len(), 5);
+    }
+
+    #[test]
+    fn plan_groups_tokens() {
+        let mut flow = TokenAirdropFlow::new();
+
+        for num in 0..6 {
+            flow.token_transfer(TOKEN_A, receiver(num), 1)
+                .nft_transfer(TOKEN_B.nft(num + 1), receiver(num));
+        }
+
+        let plan = flow.plan();
+
+        // 7 transfers of `TOKEN_A` and 3 NFTs, then the last 3 NFTs.
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0].len(), 9);
+        assert!(plan[0][..6].iter().all(|it| it.token_id() == TOKEN_A));
+        assert_eq!(plan[1].len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "a transaction must fit at least 2 transfers")]
+    fn max_transfers_too_small() {
+        TokenAirdropFlow::new().max_transfers_per_transaction(1);
+    }
+
+    #[tokio::test]
+    async fn execute_airdrops_every_batch() {
+        let network = FakeNetwork::new();
+        let mut flow = TokenAirdropFlow::new();
+
+        for num in 0..12 {
+            flow.token_transfer(TOKEN_A, receiver(num), 1);
+        }
+
+        flow.execute_on(&network).await.unwrap();
+
+        assert_eq!(network.airdropped.lock().len(), 2);
+        assert_eq!(flow.executed, 2);
+        assert_eq!(flow.submitted, None);
+    }
+
+    #[tokio::test]
+    async fn resume_uses_record_of_submitted_batch() {
+        let network = FakeNetwork {
+            record: Some(record(Status::Success, Vec::from([pending_airdrop(1)]))),
+            ..FakeNetwork::new()
+        };
+
+        let mut flow = submitted_at(OffsetDateTime::now_utc());
+
+        let pending = flow.execute_on(&network).await.unwrap();
+
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].pending_airdrop_id, pending_airdrop(1).pending_airdrop_id);
+        assert!(network.airdropped.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn resume_falls_back_to_mirror_node_after_record_expires() {
+        let network = FakeNetwork {
+            mirror: Some(SubmittedState::Executed(Vec::from([pending_airdrop(1)]))),
+            ..FakeNetwork::new()
+        };
+
+        let mut flow = submitted_at(OffsetDateTime::now_utc() - Duration::minutes(10));
+
+        let pending = flow.execute_on(&network).await.unwrap();
+
+        assert_eq!(pending.len(), 1);
+        assert!(network.airdropped.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn resume_reairdrops_batch_that_never_reached_consensus() {
+        let network = FakeNetwork::new();
+        let mut flow = TokenAirdropFlow::new();
+
+        flow.token_transfer(TOKEN_A, receiver(1), 1);
+
+        network.airdrops_before_failure.store(0, Ordering::Relaxed);
+
+        let error = flow.execute_on(&network).await.unwrap_err();
+
+        assert_matches!(error, Error::GrpcStatus(_));
+        assert!(flow.submitted.is_some());
+
+        // the record expired and the mirror node never saw the transaction.
+        flow.submitted.as_mut().unwrap().valid_start -= Duration::minutes(10);
+
+        network.airdrops_before_failure.store(usize::MAX, Ordering::Relaxed);
+
+        flow.execute_on(&network).await.unwrap();
+
+        assert_eq!(network.airdropped.lock().len(), 1);
+        assert_eq!(flow.executed, 1);
+    }
+
+    #[tokio::test]
+    async fn resume_waits_while_submitted_batch_can_reach_consensus() {
+        let network = FakeNetwork::new();
+        let mut flow = submitted_at(OffsetDateTime::now_utc());
+
+        let error = flow.execute_on(&network).await.unwrap_err();
+
+        assert_matches!(error, Error::QueryPreCheckStatus { status: Status::RecordNotFound, .. });
+        assert!(flow.submitted.is_some());
+        assert!(network.airdropped.lock().is_empty());
+    }
+}
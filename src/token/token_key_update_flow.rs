@@ -0,0 +1,650 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::fmt;
+use std::time::Duration;
+
+use super::{
+    TokenId,
+    TokenInfo,
+    TokenInfoQuery,
+    TokenKeyValidation,
+    TokenUpdateTransaction,
+};
+use crate::signer::AnySigner;
+use crate::{
+    AccountId,
+    Client,
+    Error,
+    Key,
+    KeyList,
+    PrivateKey,
+    PublicKey,
+    TransactionReceipt,
+};
+
+/// A key of a token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKeyType {
+    /// The key which can perform update/delete operations on the token.
+    Admin,
+
+    /// The key which can grant or revoke KYC of an account for the token's transactions.
+    Kyc,
+
+    /// The key which can sign to freeze or unfreeze an account for token transactions.
+    Freeze,
+
+    /// The key which can wipe the token balance of an account.
+    Wipe,
+
+    /// The key which can change the supply of a token.
+    Supply,
+
+    /// The key which can change the token's custom fee schedule.
+    FeeSchedule,
+
+    /// The key which can pause and unpause the token.
+    Pause,
+
+    /// The key which can change the metadata of the token and its NFTs.
+    Metadata,
+}
+
+impl fmt::Display for TokenKeyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Admin => "admin",
+            Self::Kyc => "KYC",
+            Self::Freeze => "freeze",
+            Self::Wipe => "wipe",
+            Self::Supply => "supply",
+            Self::FeeSchedule => "fee schedule",
+            Self::Pause => "pause",
+            Self::Metadata => "metadata",
+        })
+    }
+}
+
+impl TokenKeyType {
+    fn get(self, info: &TokenInfo) -> Option<&Key> {
+        match self {
+            Self::Admin => info.admin_key.as_ref(),
+            Self::Kyc => info.kyc_key.as_ref(),
+            Self::Freeze => info.freeze_key.as_ref(),
+            Self::Wipe => info.wipe_key.as_ref(),
+            Self::Supply => info.supply_key.as_ref(),
+            Self::FeeSchedule => info.fee_schedule_key.as_ref(),
+            Self::Pause => info.pause_key.as_ref(),
+            Self::Metadata => info.metadata_key.as_ref(),
+        }
+    }
+
+    fn set(self, transaction: &mut TokenUpdateTransaction, key: Key) {
+        match self {
+            Self::Admin => transaction.admin_key(key),
+            Self::Kyc => transaction.kyc_key(key),
+            Self::Freeze => transaction.freeze_key(key),
+            Self::Wipe => transaction.wipe_key(key),
+            Self::Supply => transaction.supply_key(key),
+            Self::FeeSchedule => transaction.fee_schedule_key(key),
+            Self::Pause => transaction.pause_key(key),
+            Self::Metadata => transaction.metadata_key(key),
+        };
+    }
+}
+
+/// Change the keys of a token in a single [`TokenUpdateTransaction`], following [HIP-540](https://hips.hedera.com/hip/hip-540).
+///
+/// The operation of this flow is as follows:
+/// 1. Query the token's [`TokenInfo`], and refuse changes that are invalid for the token or would lock it.
+/// 2. Execute a [`TokenUpdateTransaction`] with every change.
+/// 3. Query the token's info again, and check that every key was changed.
+///
+/// Removing a key other than the admin key sets it to [`dead_key`](Self::dead_key), which disables it for good.
+/// Removing the admin key makes the token immutable, which must be allowed with [`allow_immutable`](Self::allow_immutable).
+#[derive(Default, Debug)]
+pub struct TokenKeyUpdateFlow {
+    node_account_ids: Option<Vec<AccountId>>,
+    token_id: Option<TokenId>,
+    changes: Vec<(TokenKeyType, Option<Key>)>,
+    key_verification_mode: TokenKeyValidation,
+    use_admin_key: Option<bool>,
+    allow_immutable: bool,
+    signers: Vec<AnySigner>,
+}
+
+impl TokenKeyUpdateFlow {
+    /// Create a new `TokenKeyUpdateFlow`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the all zeros Ed25519 key, which nobody can sign with.
+    ///
+    /// # Panics
+    /// - Never, the all zeros key is always a valid Ed25519 key.
+    #[must_use]
+    pub fn dead_key() -> Key {
+        Key::Single(PublicKey::from_bytes_ed25519(&[0; 32]).unwrap())
+    }
+
+    /// Returns the account IDs of the nodes the transactions may be submitted to.
+    #[must_use]
+    pub fn get_node_account_ids(&self) -> Option<&[AccountId]> {
+        self.node_account_ids.as_deref()
+    }
+
+    /// Sets the account IDs of the nodes the transactions may be submitted to.
+    pub fn node_account_ids(
+        &mut self,
+        node_account_ids: impl IntoIterator<Item = AccountId>,
+    ) -> &mut Self {
+        self.node_account_ids = Some(node_account_ids.into_iter().collect());
+
+        self
+    }
+
+    /// Returns the token to change the keys of.
+    #[must_use]
+    pub fn get_token_id(&self) -> Option<TokenId> {
+        self.token_id
+    }
+
+    /// Sets the token to change the keys of.
+    pub fn token_id(&mut self, token_id: impl Into<TokenId>) -> &mut Self {
+        self.token_id = Some(token_id.into());
+
+        self
+    }
+
+    fn change(&mut self, key_type: TokenKeyType, key: Option<Key>) -> &mut Self {
+        self.changes.retain(|(it, _)| *it != key_type);
+        self.changes.push((key_type, key));
+
+        self
+    }
+
+    /// Replaces the `key_type` key of the token with `key`.
+    pub fn replace_key(&mut self, key_type: TokenKeyType, key: impl Into<Key>) -> &mut Self {
+        self.change(key_type, Some(key.into()))
+    }
+
+    /// Removes the `key_type` key of the token for good.
+    pub fn remove_key(&mut self, key_type: TokenKeyType) -> &mut Self {
+        self.change(key_type, None)
+    }
+
+    /// Returns the key verification mode of the update.
+    #[must_use]
+    pub fn get_key_verification_mode(&self) -> TokenKeyValidation {
+        self.key_verification_mode
+    }
+
+    /// Sets the key verification mode of the update.
+    ///
+    /// With [`TokenKeyValidation::FullValidation`] every new key must sign the update as well.
+    pub fn key_verification_mode(&mut self, mode: TokenKeyValidation) -> &mut Self {
+        self.key_verification_mode = mode;
+
+        self
+    }
+
+    /// Sets whether the update is authorized by the token's admin key, or by each key being changed.
+    ///
+    /// Defaults to using the admin key when the token has one.
+    pub fn use_admin_key(&mut self, use_admin_key: bool) -> &mut Self {
+        self.use_admin_key = Some(use_admin_key);
+
+        self
+    }
+
+    /// Returns whether removing the admin key, making the token immutable, is allowed.
+    #[must_use]
+    pub fn get_allow_immutable(&self) -> bool {
+        self.allow_immutable
+    }
+
+    /// Sets whether removing the admin key, making the token immutable, is allowed.
+    pub fn allow_immutable(&mut self, allow: bool) -> &mut Self {
+        self.allow_immutable = allow;
+
+        self
+    }
+
+    /// Adds a key to sign the update with.
+    pub fn sign(&mut self, key: PrivateKey) -> &mut Self {
+        self.signers.push(AnySigner::PrivateKey(key));
+
+        self
+    }
+
+    /// Adds a signer to sign the update with.
+    pub fn sign_with<F: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static>(
+        &mut self,
+        public_key: PublicKey,
+        signer: F,
+    ) -> &mut Self {
+        self.signers.push(AnySigner::arbitrary(Box::new(public_key), signer));
+
+        self
+    }
+
+    // The key the token will have once `change` is applied.
+    fn new_key(key_type: TokenKeyType, change: Option<&Key>) -> Option<Key> {
+        match (key_type, change) {
+            (_, Some(key)) => Some(key.clone()),
+            (TokenKeyType::Admin, None) => None,
+            (_, None) => Some(Self::dead_key()),
+        }
+    }
+
+    /// Returns the keys that must sign the update of the token described by `info`.
+    ///
+    /// # Errors
+    /// - [`Error::TokenKeyChangeRefused`] if a change is invalid for the token, or would lock it.
+    pub fn required_keys(&self, info: &TokenInfo) -> crate::Result<Vec<Key>> {
+        let token_id = info.token_id;
+        let dead_key = Self::dead_key();
+
+        let refuse = |key_type, reason| Error::TokenKeyChangeRefused { token_id, key_type, reason };
+
+        let use_admin_key = self.use_admin_key.unwrap_or(info.admin_key.is_some());
+
+        let mut required = Vec::new();
+
+        if use_admin_key {
+            let Some(admin_key) = &info.admin_key else {
+                return Err(refuse(TokenKeyType::Admin, "the token is immutable"));
+            };
+
+            required.push(admin_key.clone());
+        }
+
+        for (key_type, change) in &self.changes {
+            let key_type = *key_type;
+
+            let Some(current) = key_type.get(info) else {
+                return Err(refuse(key_type, "keys can't be added to a token after it's created"));
+            };
+
+            match (key_type, change) {
+                (TokenKeyType::Admin, Some(key)) if *key == dead_key => {
+                    return Err(refuse(
+                        key_type,
+                        "the token could never be updated again, remove the admin key instead",
+                    ));
+                }
+                (TokenKeyType::Admin, None) if !self.allow_immutable => {
+                    return Err(refuse(
+                        key_type,
+                        "removing the admin key makes the token immutable",
+                    ));
+                }
+                (TokenKeyType::Admin, _) if !use_admin_key => {
+                    return Err(refuse(key_type, "the admin key can only be changed by itself"));
+                }
+                (TokenKeyType::Pause, None) if info.pause_status == Some(true) => {
+                    return Err(refuse(
+                        key_type,
+                        "the token is paused and could never be unpaused",
+                    ));
+                }
+                (TokenKeyType::Freeze, None) if info.default_freeze_status == Some(true) => {
+                    return Err(refuse(
+                        key_type,
+                        "accounts are frozen by default and could never be unfrozen",
+                    ));
+                }
+                _ => {}
+            }
+
+            if !use_admin_key {
+                if *current == dead_key {
+                    return Err(refuse(key_type, "the key was removed and can't sign"));
+                }
+
+                if !required.contains(current) {
+                    required.push(current.clone());
+                }
+            }
+
+            // the admin key must always sign for its replacement, other keys only under full validation.
+            let new_key_signs = key_type == TokenKeyType::Admin
+                || self.key_verification_mode == TokenKeyValidation::FullValidation;
+
+            if let Some(key) = change {
+                if new_key_signs && *key != dead_key && !required.contains(key) {
+                    required.push(key.clone());
+                }
+            }
+        }
+
+        Ok(required)
+    }
+
+    // the keys in `required` that the signers of this flow and the operator don't satisfy.
+    fn missing_signatories(&self, required: &[Key], operator_key: Option<PublicKey>) -> Vec<Key> {
+        let signatories: Vec<Key> = self
+            .signers
+            .iter()
+            .map(AnySigner::public_key)
+            .chain(operator_key)
+            .map(Key::from)
+            .collect();
+
+        required.iter().filter(|key| !key.is_satisfied_by(&signatories)).cloned().collect()
+    }
+
+    fn make_transaction(&self, token_id: TokenId) -> TokenUpdateTransaction {
+        let mut transaction = TokenUpdateTransaction::new();
+
+        transaction.token_id(token_id).key_verification_mode(self.key_verification_mode);
+
+        for (key_type, change) in &self.changes {
+            // an empty key list is the sentinel for removing the admin key.
+            let key = Self::new_key(*key_type, change.as_ref())
+                .unwrap_or_else(|| Key::KeyList(KeyList::new()));
+
+            key_type.set(&mut transaction, key);
+        }
+
+        if let Some(node_account_ids) = &self.node_account_ids {
+            transaction.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        for signer in &self.signers {
+            transaction.sign_signer(signer.clone());
+        }
+
+        transaction
+    }
+
+    async fn query_info(
+        &self,
+        client: &Client,
+        token_id: TokenId,
+        timeout: Option<Duration>,
+    ) -> crate::Result<TokenInfo> {
+        let mut query = TokenInfoQuery::new();
+
+        query.token_id(token_id);
+
+        if let Some(node_account_ids) = &self.node_account_ids {
+            query.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        query.execute_with_optional_timeout(client, timeout).await
+    }
+
+    /// Changes the keys of the token, and checks that they were changed.
+    ///
+    /// # Errors
+    /// - [`Error::TokenKeyChangeRefused`] if a change is invalid for the token, or would lock it.
+    /// - [`Error::TokenKeyChangeUnsigned`] if the signers and the operator don't satisfy every required key,
+    ///   nothing is submitted in this case.
+    /// - [`Error::TokenKeysNotUpdated`] if the token's keys don't match the changes after the update.
+    /// - Any error from querying the token's info or executing the update.
+    ///
+    /// # Panics
+    /// - If the token ID isn't set.
+    pub async fn execute(&self, client: &Client) -> crate::Result<TransactionReceipt> {
+        self.execute_with_optional_timeout(client, None).await
+    }
+
+    /// Changes the keys of the token, and checks that they were changed.
+    ///
+    /// # Errors
+    /// - [`Error::TokenKeyChangeRefused`] if a change is invalid for the token, or would lock it.
+    /// - [`Error::TokenKeyChangeUnsigned`] if the signers and the operator don't satisfy every required key,
+    ///   nothing is submitted in this case.
+    /// - [`Error::TokenKeysNotUpdated`] if the token's keys don't match the changes after the update.
+    /// - Any error from querying the token's info or executing the update.
+    ///
+    /// # Panics
+    /// - If the token ID isn't set.
+    pub async fn execute_with_timeout(
+        &self,
+        client: &Client,
+        timeout_per_transaction: Duration,
+    ) -> crate::Result<TransactionReceipt> {
+        self.execute_with_optional_timeout(client, Some(timeout_per_transaction)).await
+    }
+
+    async fn execute_with_optional_timeout(
+        &self,
+        client: &Client,
+        timeout: Option<Duration>,
+    ) -> crate::Result<TransactionReceipt> {
+        let token_id = self.token_id.expect("must set a token ID to use `TokenKeyUpdateFlow`");
+
+        let info = self.query_info(client, token_id, timeout).await?;
+
+        let operator_key = client.load_operator().as_ref().map(|it| it.signer.public_key());
+
+        let missing_signatories =
+            self.missing_signatories(&self.required_keys(&info)?, operator_key);
+
+        if !missing_signatories.is_empty() {
+            return Err(Error::TokenKeyChangeUnsigned { token_id, missing_signatories });
+        }
+
+        let receipt = self
+            .make_transaction(token_id)
+            .execute_with_optional_timeout(client, timeout)
+            .await?
+            .get_receipt_query()
+            .execute_with_optional_timeout(client, timeout)
+            .await?;
+
+        let info = self.query_info(client, token_id, timeout).await?;
+
+        let key_types: Vec<_> = self
+            .changes
+            .iter()
+            .filter(|(key_type, change)| {
+                key_type.get(&info) != Self::new_key(*key_type, change.as_ref()).as_ref()
+            })
+            .map(|(key_type, _)| *key_type)
+            .collect();
+
+        if !key_types.is_empty() {
+            return Err(Error::TokenKeysNotUpdated { token_id, key_types });
+        }
+
+        Ok(receipt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::{
+        TokenKeyType,
+        TokenKeyUpdateFlow,
+    };
+    use crate::{
+        AccountId,
+        Error,
+        Key,
+        KeyList,
+        LedgerId,
+        PrivateKey,
+        TokenId,
+        TokenInfo,
+        TokenKeyValidation,
+        TokenSupplyType,
+        TokenType,
+    };
+
+    fn private_key(index: u8) -> PrivateKey {
+        PrivateKey::from_bytes_ed25519(&[index; 32]).unwrap()
+    }
+
+    fn key(index: u8) -> Key {
+        private_key(index).public_key().into()
+    }
+
+    fn make_info() -> TokenInfo {
+        TokenInfo {
+            token_id: TokenId::new(0, 0, 1000),
+            name: "test token name".to_owned(),
+            symbol: "TTN".to_owned(),
+            decimals: 3,
+            total_supply: 1000,
+            treasury_account_id: AccountId::new(0, 0, 7),
+            admin_key: Some(key(1)),
+            kyc_key: None,
+            freeze_key: None,
+            wipe_key: None,
+            supply_key: Some(key(2)),
+            fee_schedule_key: None,
+            default_freeze_status: None,
+            default_kyc_status: None,
+            is_deleted: false,
+            auto_renew_account: None,
+            auto_renew_period: None,
+            expiration_time: None,
+            token_memo: String::new(),
+            token_type: TokenType::FungibleCommon,
+            supply_type: TokenSupplyType::Infinite,
+            max_supply: 0,
+            custom_fees: Vec::new(),
+            pause_key: Some(key(3)),
+            pause_status: Some(false),
+            ledger_id: LedgerId::testnet(),
+            metadata: Vec::new(),
+            metadata_key: None,
+        }
+    }
+
+    #[test]
+    fn admin_signs_by_default() {
+        let mut flow = TokenKeyUpdateFlow::new();
+
+        flow.remove_key(TokenKeyType::Supply);
+
+        assert_eq!(flow.required_keys(&make_info()).unwrap(), [key(1)]);
+    }
+
+    #[test]
+    fn full_validation_requires_new_key() {
+        let mut flow = TokenKeyUpdateFlow::new();
+
+        flow.replace_key(TokenKeyType::Supply, key(4)).use_admin_key(false);
+
+        assert_eq!(flow.required_keys(&make_info()).unwrap(), [key(2), key(4)]);
+
+        flow.key_verification_mode(TokenKeyValidation::NoValidation);
+
+        assert_eq!(flow.required_keys(&make_info()).unwrap(), [key(2)]);
+    }
+
+    #[test]
+    fn refuses_missing_key() {
+        let mut flow = TokenKeyUpdateFlow::new();
+
+        flow.replace_key(TokenKeyType::Wipe, key(4));
+
+        assert_matches!(
+            flow.required_keys(&make_info()),
+            Err(Error::TokenKeyChangeRefused { key_type: TokenKeyType::Wipe, .. })
+        );
+    }
+
+    #[test]
+    fn refuses_immutable_unless_allowed() {
+        let mut flow = TokenKeyUpdateFlow::new();
+
+        flow.remove_key(TokenKeyType::Admin);
+
+        assert_matches!(
+            flow.required_keys(&make_info()),
+            Err(Error::TokenKeyChangeRefused { key_type: TokenKeyType::Admin, .. })
+        );
+
+        flow.allow_immutable(true);
+
+        assert_eq!(flow.required_keys(&make_info()).unwrap(), [key(1)]);
+    }
+
+    #[test]
+    fn refuses_dead_admin_key() {
+        let mut flow = TokenKeyUpdateFlow::new();
+
+        flow.replace_key(TokenKeyType::Admin, TokenKeyUpdateFlow::dead_key());
+
+        assert_matches!(
+            flow.required_keys(&make_info()),
+            Err(Error::TokenKeyChangeRefused { key_type: TokenKeyType::Admin, .. })
+        );
+    }
+
+    #[test]
+    fn refuses_removing_pause_key_while_paused() {
+        let mut info = make_info();
+        info.pause_status = Some(true);
+
+        let mut flow = TokenKeyUpdateFlow::new();
+
+        flow.remove_key(TokenKeyType::Pause);
+
+        assert_matches!(
+            flow.required_keys(&info),
+            Err(Error::TokenKeyChangeRefused { key_type: TokenKeyType::Pause, .. })
+        );
+    }
+
+    #[test]
+    fn missing_signatories() {
+        let mut flow = TokenKeyUpdateFlow::new();
+
+        flow.sign(private_key(2));
+
+        let required = [key(1), key(2), key(4)];
+
+        assert_eq!(flow.missing_signatories(&required, None), [key(1), key(4)]);
+        assert_eq!(
+            flow.missing_signatories(&required, Some(private_key(1).public_key())),
+            [key(4)]
+        );
+    }
+
+    #[test]
+    fn missing_signatories_of_threshold_key() {
+        let mut flow = TokenKeyUpdateFlow::new();
+
+        flow.sign(private_key(2));
+
+        let threshold_key =
+            Key::from(KeyList { keys: Vec::from([key(1), key(2), key(3)]), threshold: Some(2) });
+
+        assert_eq!(
+            flow.missing_signatories(&[threshold_key.clone()], None),
+            [threshold_key.clone()]
+        );
+
+        flow.sign(private_key(3));
+
+        assert!(flow.missing_signatories(&[threshold_key], None).is_empty());
+    }
+}
@@ -0,0 +1,991 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{
+    AtomicBool,
+    Ordering,
+};
+use std::time::Duration;
+
+use futures_core::future::BoxFuture;
+use futures_util::StreamExt;
+use parking_lot::Mutex;
+use time::OffsetDateTime;
+use triomphe::Arc;
+use unsize::{
+    CoerceUnsize,
+    Coercion,
+};
+
+use super::{
+    NftId,
+    TokenId,
+    TokenMintTransaction,
+};
+#[cfg(feature = "mirror-rest")]
+use crate::mirror_rest;
+use crate::signer::AnySigner;
+use crate::{
+    AccountId,
+    Client,
+    Error,
+    PrivateKey,
+    PublicKey,
+    Status,
+    TransactionId,
+    TransactionReceipt,
+    TransactionReceiptQuery,
+};
+
+/// Mint any number of NFTs, splitting the metadata into as many [`TokenMintTransaction`]s as needed.
+///
+/// The operation of this flow is as follows:
+/// 1. Split the metadata into batches of at most [`batch_size`](Self::batch_size) entries.
+/// 2. Execute a [`TokenMintTransaction`] for each batch, with at most
+///    [`max_concurrency`](Self::max_concurrency) transactions in flight at a time.
+/// 3. Map each metadata entry to its minted [`NftId`], using the serials from the batch's receipt.
+///
+/// The flow remembers which batches were minted, so if it fails part way through,
+/// calling `execute` again only mints the remaining batches.
+/// Batches whose outcome is unknown (for example because their receipt timed out) are checked first,
+/// using the mirror node (with the `mirror-rest` feature) once the network no longer has their receipt.
+pub struct TokenMintFlow {
+    node_account_ids: Option<Vec<AccountId>>,
+    token_id: Option<TokenId>,
+    metadata: Vec<Vec<u8>>,
+    batch_size: usize,
+    max_concurrency: usize,
+    signers: Vec<AnySigner>,
+    progress: Option<Arc<dyn Fn(usize, usize) + Send + Sync>>,
+    // behind a lock so batches in flight can record their transaction ID as soon as they start.
+    batches: Mutex<Vec<BatchState>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BatchState {
+    Pending,
+    // the transaction was sent, but we don't know if it reached consensus.
+    Submitted(TransactionId),
+    Minted(Vec<i64>),
+}
+
+/// How long after its valid start a transaction can still reach consensus.
+///
+/// This is the longest valid duration the network accepts, plus some leeway for clock skew.
+pub(super) const MAX_TRANSACTION_LIFETIME: Duration = Duration::from_secs(180 + 30);
+
+/// The network operations [`TokenMintFlow`] is made of.
+trait MintNetwork: Sync {
+    fn operator_account_id(&self) -> Option<AccountId>;
+
+    /// Executes `transaction` and returns its receipt.
+    fn mint(
+        &self,
+        transaction: TokenMintTransaction,
+    ) -> BoxFuture<'_, crate::Result<TransactionReceipt>>;
+
+    /// Returns the receipt of `transaction_id` without validating its status.
+    fn receipt(
+        &self,
+        transaction_id: TransactionId,
+    ) -> BoxFuture<'_, crate::Result<TransactionReceipt>>;
+
+    /// Looks `transaction_id` up on the mirror node.
+    ///
+    /// Returns `None` if the mirror node has no record of it, or the `mirror-rest` feature is disabled.
+    fn mirror_receipt(
+        &self,
+        token_id: TokenId,
+        transaction_id: TransactionId,
+    ) -> BoxFuture<'_, crate::Result<Option<BatchState>>>;
+}
+
+struct ClientMintNetwork<'a> {
+    client: &'a Client,
+    node_account_ids: Option<Vec<AccountId>>,
+    timeout: Option<Duration>,
+}
+
+#[cfg(feature = "mirror-rest")]
+#[derive(serde_derive::Deserialize)]
+struct MirrorTransaction {
+    result: String,
+    #[serde(default)]
+    nft_transfers: Vec<MirrorNftTransfer>,
+}
+
+#[cfg(feature = "mirror-rest")]
+#[derive(serde_derive::Deserialize)]
+struct MirrorNftTransfer {
+    serial_number: i64,
+    sender_account_id: Option<String>,
+    token_id: String,
+}
+
+impl MintNetwork for ClientMintNetwork<'_> {
+    fn operator_account_id(&self) -> Option<AccountId> {
+        self.client.get_operator_account_id()
+    }
+
+    fn mint(
+        &self,
+        mut transaction: TokenMintTransaction,
+    ) -> BoxFuture<'_, crate::Result<TransactionReceipt>> {
+        Box::pin(async move {
+            transaction
+                .execute_with_optional_timeout(self.client, self.timeout)
+                .await?
+                .get_receipt_query()
+                .execute_with_optional_timeout(self.client, self.timeout)
+                .await
+        })
+    }
+
+    fn receipt(
+        &self,
+        transaction_id: TransactionId,
+    ) -> BoxFuture<'_, crate::Result<TransactionReceipt>> {
+        Box::pin(async move {
+            let mut query = TransactionReceiptQuery::new();
+
+            query.transaction_id(transaction_id);
+
+            if let Some(node_account_ids) = &self.node_account_ids {
+                query.node_account_ids(node_account_ids.iter().copied());
+            }
+
+            query
+                .validate_status(false)
+                .execute_with_optional_timeout(self.client, self.timeout)
+                .await
+        })
+    }
+
+    #[cfg(not(feature = "mirror-rest"))]
+    fn mirror_receipt(
+        &self,
+        _token_id: TokenId,
+        _transaction_id: TransactionId,
+    ) -> BoxFuture<'_, crate::Result<Option<BatchState>>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    #[cfg(feature = "mirror-rest")]
+    fn mirror_receipt(
+        &self,
+        token_id: TokenId,
+        transaction_id: TransactionId,
+    ) -> BoxFuture<'_, crate::Result<Option<BatchState>>> {
+        Box::pin(async move {
+            let path = format!(
+                "/api/v1/transactions/{}",
+                mirror_rest::transaction_id_path_segment(&transaction_id)
+            );
+
+            let Some(transactions) = mirror_rest::get_all_if_found::<MirrorTransaction>(
+                self.client,
+                &path,
+                "transactions",
+            )
+            .await?
+            else {
+                return Ok(None);
+            };
+
+            // duplicates submitted to other nodes show up as well, with their own result.
+            let Some(transaction) = transactions
+                .iter()
+                .find(|it| it.result == "SUCCESS")
+                .or_else(|| transactions.first())
+            else {
+                return Ok(None);
+            };
+
+            if transaction.result != "SUCCESS" {
+                return Ok(Some(BatchState::Pending));
+            }
+
+            let token_id = token_id.to_string();
+
+            let mut serials: Vec<_> = transaction
+                .nft_transfers
+                .iter()
+                .filter(|it| it.sender_account_id.is_none() && it.token_id == token_id)
+                .map(|it| it.serial_number)
+                .collect();
+
+            // the receipt lists serials in the order the metadata was minted in, which is ascending.
+            serials.sort_unstable();
+
+            Ok(Some(BatchState::Minted(serials)))
+        })
+    }
+}
+
+impl Default for TokenMintFlow {
+    fn default() -> Self {
+        Self {
+            node_account_ids: None,
+            token_id: None,
+            metadata: Vec::new(),
+            batch_size: Self::MAX_BATCH_SIZE,
+            max_concurrency: Self::DEFAULT_MAX_CONCURRENCY,
+            signers: Vec::new(),
+            progress: None,
+            batches: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl fmt::Debug for TokenMintFlow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenMintFlow")
+            .field("node_account_ids", &self.node_account_ids)
+            .field("token_id", &self.token_id)
+            .field("metadata", &self.metadata.len())
+            .field("batch_size", &self.batch_size)
+            .field("max_concurrency", &self.max_concurrency)
+            .field("signers", &self.signers)
+            .field("batches", &*self.batches.lock())
+            .finish_non_exhaustive()
+    }
+}
+
+impl TokenMintFlow {
+    /// The most metadata entries a single [`TokenMintTransaction`] may contain.
+    pub const MAX_BATCH_SIZE: usize = 10;
+
+    const DEFAULT_MAX_CONCURRENCY: usize = 4;
+
+    /// Create a new `TokenMintFlow`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn require_not_started(&self) {
+        assert!(
+            self.batches.lock().is_empty(),
+            "`TokenMintFlow` can't be changed after it has started minting"
+        );
+    }
+
+    /// Returns the account IDs of the nodes the transactions may be submitted to.
+    #[must_use]
+    pub fn get_node_account_ids(&self) -> Option<&[AccountId]> {
+        self.node_account_ids.as_deref()
+    }
+
+    /// Sets the account IDs of the nodes the transactions may be submitted to.
+    pub fn node_account_ids(
+        &mut self,
+        node_account_ids: impl IntoIterator<Item = AccountId>,
+    ) -> &mut Self {
+        self.node_account_ids = Some(node_account_ids.into_iter().collect());
+
+        self
+    }
+
+    /// Returns the token to mint NFTs of.
+    #[must_use]
+    pub fn get_token_id(&self) -> Option<TokenId> {
+        self.token_id
+    }
+
+    /// Sets the token to mint NFTs of.
+    ///
+    /// # Panics
+    /// - If the flow has already started minting.
+    pub fn token_id(&mut self, token_id: impl Into<TokenId>) -> &mut Self {
+        self.require_not_started();
+        self.token_id = Some(token_id.into());
+
+        self
+    }
+
+    /// Returns the metadata of the NFTs to mint, one entry per NFT.
+    #[must_use]
+    pub fn get_metadata(&self) -> &[Vec<u8>] {
+        &self.metadata
+    }
+
+    /// Sets the metadata of the NFTs to mint, one entry per NFT.
+    ///
+    /// # Panics
+    /// - If the flow has already started minting.
+    pub fn metadata<Bytes>(&mut self, metadata: impl IntoIterator<Item = Bytes>) -> &mut Self
+    where
+        Bytes: AsRef<[u8]>,
+    {
+        self.require_not_started();
+        self.metadata = metadata.into_iter().map(|bytes| bytes.as_ref().to_vec()).collect();
+
+        self
+    }
+
+    /// Adds the metadata of one more NFT to mint.
+    ///
+    /// # Panics
+    /// - If the flow has already started minting.
+    pub fn add_metadata(&mut self, metadata: impl AsRef<[u8]>) -> &mut Self {
+        self.require_not_started();
+        self.metadata.push(metadata.as_ref().to_vec());
+
+        self
+    }
+
+    /// Returns the most metadata entries that are minted by a single transaction.
+    #[must_use]
+    pub fn get_batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Sets the most metadata entries that are minted by a single transaction.
+    ///
+    /// Defaults to [`MAX_BATCH_SIZE`](Self::MAX_BATCH_SIZE).
+    ///
+    /// # Panics
+    /// - If `batch_size` is zero or greater than [`MAX_BATCH_SIZE`](Self::MAX_BATCH_SIZE).
+    /// - If the flow has already started minting.
+    pub fn batch_size(&mut self, batch_size: usize) -> &mut Self {
+        assert!(
+            (1..=Self::MAX_BATCH_SIZE).contains(&batch_size),
+            "batch size must be between 1 and {}",
+            Self::MAX_BATCH_SIZE
+        );
+
+        self.require_not_started();
+        self.batch_size = batch_size;
+
+        self
+    }
+
+    /// Returns the most mint transactions that are executed at the same time.
+    #[must_use]
+    pub fn get_max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    /// Sets the most mint transactions that are executed at the same time.
+    ///
+    /// Defaults to 4.
+    ///
+    /// # Panics
+    /// - If `max_concurrency` is zero.
+    pub fn max_concurrency(&mut self, max_concurrency: usize) -> &mut Self {
+        assert!(max_concurrency > 0, "max concurrency must be greater than zero");
+
+        self.max_concurrency = max_concurrency;
+
+        self
+    }
+
+    /// Sets a callback that's called with the number of minted NFTs and the total number of NFTs
+    /// every time a batch finishes.
+    pub fn progress<F: Fn(usize, usize) + Send + Sync + 'static>(&mut self, f: F) -> &mut Self {
+        self.progress = Some(Arc::new(f).unsize(Coercion!(to dyn Fn(usize, usize) + Send + Sync)));
+
+        self
+    }
+
+    /// Adds a key to sign the mint transactions with, usually the token's supply key.
+    pub fn sign(&mut self, key: PrivateKey) -> &mut Self {
+        self.sign_signer(AnySigner::PrivateKey(key))
+    }
+
+    /// Adds a signer to sign the mint transactions with, usually for the token's supply key.
+    pub fn sign_with<F: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static>(
+        &mut self,
+        public_key: PublicKey,
+        signer: F,
+    ) -> &mut Self {
+        self.sign_signer(AnySigner::arbitrary(Box::new(public_key), signer))
+    }
+
+    fn sign_signer(&mut self, signer: AnySigner) -> &mut Self {
+        if !self.signers.iter().any(|it| it.public_key() == signer.public_key()) {
+            self.signers.push(signer);
+        }
+
+        self
+    }
+
+    /// Returns the NFTs minted so far, keyed by the index of their metadata.
+    #[must_use]
+    pub fn get_minted(&self) -> BTreeMap<usize, NftId> {
+        let Some(token_id) = self.token_id else {
+            return BTreeMap::new();
+        };
+
+        let mut minted = BTreeMap::new();
+
+        for (batch, state) in self.batches.lock().iter().enumerate() {
+            if let BatchState::Minted(serials) = state {
+                for (offset, serial) in serials.iter().enumerate() {
+                    minted.insert(batch * self.batch_size + offset, token_id.nft(*serial as u64));
+                }
+            }
+        }
+
+        minted
+    }
+
+    fn report_progress(&self) {
+        if let Some(progress) = &self.progress {
+            let minted = self
+                .batches
+                .lock()
+                .iter()
+                .map(|it| match it {
+                    BatchState::Minted(serials) => serials.len(),
+                    BatchState::Pending | BatchState::Submitted(_) => 0,
+                })
+                .sum();
+
+            progress(minted, self.metadata.len());
+        }
+    }
+
+    /// Mints every NFT that hasn't been minted yet.
+    ///
+    /// Returns the minted NFTs, keyed by the index of their metadata.
+    ///
+    /// # Errors
+    /// - [`Error::NoPayerAccountOrTransactionId`] if the client has no operator.
+    /// - [`Error::ReceiptStatus`] if a mint transaction failed.
+    /// - Any other error from executing a mint transaction or querying its receipt.
+    ///
+    /// In all of these cases the batches that were minted are kept, and calling `execute` again resumes the flow.
+    ///
+    /// # Panics
+    /// - If the token ID isn't set.
+    pub async fn execute(&mut self, client: &Client) -> crate::Result<BTreeMap<usize, NftId>> {
+        self.execute_with_optional_timeout(client, None).await
+    }
+
+    /// Mints every NFT that hasn't been minted yet.
+    ///
+    /// Returns the minted NFTs, keyed by the index of their metadata.
+    ///
+    /// # Errors
+    /// - [`Error::NoPayerAccountOrTransactionId`] if the client has no operator.
+    /// - [`Error::ReceiptStatus`] if a mint transaction failed.
+    /// - Any other error from executing a mint transaction or querying its receipt.
+    ///
+    /// In all of these cases the batches that were minted are kept, and calling `execute` again resumes the flow.
+    ///
+    /// # Panics
+    /// - If the token ID isn't set.
+    pub async fn execute_with_timeout(
+        &mut self,
+        client: &Client,
+        timeout_per_transaction: Duration,
+    ) -> crate::Result<BTreeMap<usize, NftId>> {
+        self.execute_with_optional_timeout(client, Some(timeout_per_transaction)).await
+    }
+
+    async fn execute_with_optional_timeout(
+        &mut self,
+        client: &Client,
+        timeout: Option<Duration>,
+    ) -> crate::Result<BTreeMap<usize, NftId>> {
+        let network =
+            ClientMintNetwork { client, node_account_ids: self.node_account_ids.clone(), timeout };
+
+        self.execute_on(&network).await
+    }
+
+    async fn execute_on(
+        &mut self,
+        network: &impl MintNetwork,
+    ) -> crate::Result<BTreeMap<usize, NftId>> {
+        let token_id = self.token_id.expect("must set a token ID to use `TokenMintFlow`");
+
+        let batches = self.batches.get_mut();
+
+        if batches.is_empty() {
+            *batches = vec![BatchState::Pending; self.metadata.len().div_ceil(self.batch_size)];
+        }
+
+        // find out what happened to the batches that were in flight when the flow last failed.
+        for index in 0..batches.len() {
+            if let BatchState::Submitted(transaction_id) = batches[index] {
+                batches[index] = resolve_submitted(network, token_id, transaction_id).await?;
+            }
+        }
+
+        self.report_progress();
+
+        let payer = network.operator_account_id().ok_or(Error::NoPayerAccountOrTransactionId)?;
+
+        let pending: Vec<_> = self
+            .batches
+            .get_mut()
+            .iter()
+            .enumerate()
+            .filter(|(_, state)| matches!(state, BatchState::Pending))
+            .map(|(index, _)| index)
+            .collect();
+
+        let this = &*self;
+
+        // once a batch fails, no new batches are started, but the ones in flight are allowed to finish.
+        let failed = AtomicBool::new(false);
+
+        let mut outcomes = futures_util::stream::iter(pending)
+            .map(|index| {
+                let failed = &failed;
+
+                async move {
+                    if failed.load(Ordering::Relaxed) {
+                        return (index, None);
+                    }
+
+                    // generated only now, so that a batch that waited for others doesn't start out close to expiring.
+                    let transaction_id = TransactionId::generate(payer);
+
+                    // recorded before sending, so that the batch can be resolved if we lose track of it.
+                    this.batches.lock()[index] = BatchState::Submitted(transaction_id);
+
+                    let result =
+                        network.mint(this.make_transaction(token_id, index, transaction_id)).await;
+
+                    if result.is_err() {
+                        failed.store(true, Ordering::Relaxed);
+                    }
+
+                    (index, Some(result))
+                }
+            })
+            .buffer_unordered(self.max_concurrency);
+
+        let mut first_error = None;
+
+        while let Some((index, result)) = outcomes.next().await {
+            let state = match result {
+                // skipped because another batch failed.
+                None => BatchState::Pending,
+                Some(Ok(receipt)) => BatchState::Minted(receipt.serials),
+                // the transaction was rejected, so nothing was minted.
+                Some(Err(
+                    error @ (Error::TransactionPreCheckStatus { .. } | Error::ReceiptStatus { .. }),
+                )) => {
+                    first_error.get_or_insert(error);
+                    BatchState::Pending
+                }
+                // otherwise the batch stays submitted, and is resolved when the flow resumes.
+                Some(Err(error)) => {
+                    first_error.get_or_insert(error);
+                    continue;
+                }
+            };
+
+            this.batches.lock()[index] = state;
+
+            this.report_progress();
+        }
+
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(self.get_minted()),
+        }
+    }
+
+    fn make_transaction(
+        &self,
+        token_id: TokenId,
+        index: usize,
+        transaction_id: TransactionId,
+    ) -> TokenMintTransaction {
+        let start = index * self.batch_size;
+        let end = usize::min(start + self.batch_size, self.metadata.len());
+
+        let mut transaction = TokenMintTransaction::new();
+
+        transaction
+            .token_id(token_id)
+            .metadata(&self.metadata[start..end])
+            .transaction_id(transaction_id)
+            .regenerate_transaction_id(false);
+
+        if let Some(node_account_ids) = &self.node_account_ids {
+            transaction.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        for signer in &self.signers {
+            transaction.sign_signer(signer.clone());
+        }
+
+        transaction
+    }
+}
+
+async fn resolve_submitted(
+    network: &impl MintNetwork,
+    token_id: TokenId,
+    transaction_id: TransactionId,
+) -> crate::Result<BatchState> {
+    let error = match network.receipt(transaction_id).await {
+        Ok(receipt) if receipt.status == Status::Success => {
+            return Ok(BatchState::Minted(receipt.serials))
+        }
+        Ok(_) => return Ok(BatchState::Pending),
+        Err(error) => error,
+    };
+
+    if !is_receipt_not_found(&error) {
+        return Err(error);
+    }
+
+    // the network forgets receipts after a few minutes, but the mirror node keeps the record.
+    if let Some(state) = network.mirror_receipt(token_id, transaction_id).await? {
+        return Ok(state);
+    }
+
+    // neither knows about the transaction, which can't change once it's too old to reach consensus.
+    let expired = OffsetDateTime::now_utc() - transaction_id.valid_start > MAX_TRANSACTION_LIFETIME;
+
+    if expired {
+        Ok(BatchState::Pending)
+    } else {
+        Err(error)
+    }
+}
+
+fn is_receipt_not_found(error: &Error) -> bool {
+    match error {
+        Error::TimedOut(error) => is_receipt_not_found(error),
+        Error::QueryPreCheckStatus { status, .. } => *status == Status::ReceiptNotFound,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{
+        AtomicI64,
+        AtomicUsize,
+        Ordering,
+    };
+
+    use assert_matches::assert_matches;
+    use futures_core::future::BoxFuture;
+    use parking_lot::Mutex;
+    use time::{
+        Duration,
+        OffsetDateTime,
+    };
+    use triomphe::Arc;
+
+    use super::{
+        BatchState,
+        MintNetwork,
+    };
+    use crate::{
+        AccountId,
+        Error,
+        Status,
+        TokenId,
+        TokenMintFlow,
+        TokenMintTransaction,
+        TransactionId,
+        TransactionReceipt,
+    };
+
+    const TOKEN_ID: TokenId = TokenId::new(0, 0, 1000);
+
+    const PAYER: AccountId = AccountId::new(0, 0, 5005);
+
+    fn receipt(status: Status, serials: Vec<i64>) -> TransactionReceipt {
+        TransactionReceipt { status, serials, ..crate::transaction_receipt::make_receipt() }
+    }
+
+    /// A network that mints serials in order, and fails every mint after the first `mints_before_failure`.
+    struct FakeNetwork {
+        mints_before_failure: AtomicUsize,
+        next_serial: AtomicI64,
+        minted: Mutex<Vec<(TransactionId, usize)>>,
+        receipt: Option<TransactionReceipt>,
+        mirror: Option<BatchState>,
+    }
+
+    impl FakeNetwork {
+        fn new() -> Self {
+            Self {
+                mints_before_failure: AtomicUsize::new(usize::MAX),
+                next_serial: AtomicI64::new(1),
+                minted: Mutex::new(Vec::new()),
+                receipt: None,
+                mirror: None,
+            }
+        }
+    }
+
+    impl MintNetwork for FakeNetwork {
+        fn operator_account_id(&self) -> Option<AccountId> {
+            Some(PAYER)
+        }
+
+        fn mint(
+            &self,
+            transaction: TokenMintTransaction,
+        ) -> BoxFuture<'_, crate::Result<TransactionReceipt>> {
+            Box::pin(async move {
+                let fail = self
+                    .mints_before_failure
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |it| it.checked_sub(1))
+                    .is_err();
+
+                if fail {
+                    return Err(Error::GrpcStatus(tonic::Status::unavailable("node is down")));
+                }
+
+                let count = transaction.get_metadata().len();
+                let first = self.next_serial.fetch_add(count as i64, Ordering::Relaxed);
+
+                self.minted.lock().push((transaction.get_transaction_id().unwrap(), count));
+
+                Ok(receipt(Status::Success, (first..first + count as i64).collect()))
+            })
+        }
+
+        fn receipt(
+            &self,
+            transaction_id: TransactionId,
+        ) -> BoxFuture<'_, crate::Result<TransactionReceipt>> {
+            Box::pin(async move {
+                self.receipt.clone().ok_or(Error::QueryPreCheckStatus {
+                    status: Status::ReceiptNotFound,
+                    transaction_id: Box::new(transaction_id),
+                })
+            })
+        }
+
+        fn mirror_receipt(
+            &self,
+            _token_id: TokenId,
+            _transaction_id: TransactionId,
+        ) -> BoxFuture<'_, crate::Result<Option<BatchState>>> {
+            Box::pin(async move { Ok(self.mirror.clone()) })
+        }
+    }
+
+    fn flow_of(count: u8) -> TokenMintFlow {
+        let mut flow = TokenMintFlow::new();
+
+        flow.token_id(TOKEN_ID).metadata((0..count).map(|it| [it])).batch_size(2);
+
+        flow
+    }
+
+    fn submitted_at(valid_start: OffsetDateTime) -> BatchState {
+        BatchState::Submitted(TransactionId {
+            account_id: PAYER,
+            valid_start,
+            nonce: None,
+            scheduled: false,
+        })
+    }
+
+    #[test]
+    fn get_set() {
+        let mut flow = TokenMintFlow::new();
+
+        flow.token_id(TOKEN_ID)
+            .metadata([[1], [2]])
+            .add_metadata([3])
+            .batch_size(2)
+            .max_concurrency(8);
+
+        assert_eq!(flow.get_token_id(), Some(TOKEN_ID));
+        assert_eq!(flow.get_metadata(), [[1], [2], [3]]);
+        assert_eq!(flow.get_batch_size(), 2);
+        assert_eq!(flow.get_max_concurrency(), 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "batch size must be between 1 and 10")]
+    fn batch_size_too_large() {
+        TokenMintFlow::new().batch_size(11);
+    }
+
+    #[test]
+    fn minted_maps_metadata_index_to_serial() {
+        let mut flow = TokenMintFlow::new();
+
+        flow.token_id(TOKEN_ID).metadata([[1], [2], [3], [4], [5]]).batch_size(2);
+
+        *flow.batches.get_mut() = Vec::from([
+            BatchState::Minted(Vec::from([10, 11])),
+            BatchState::Pending,
+            BatchState::Minted(Vec::from([12])),
+        ]);
+
+        let minted = flow.get_minted();
+
+        assert_eq!(minted.len(), 3);
+        assert_eq!(minted[&0], TOKEN_ID.nft(10));
+        assert_eq!(minted[&1], TOKEN_ID.nft(11));
+        assert_eq!(minted[&4], TOKEN_ID.nft(12));
+    }
+
+    #[test]
+    fn progress_counts_minted() {
+        let reported = Arc::new(AtomicUsize::new(0));
+
+        let mut flow = TokenMintFlow::new();
+
+        flow.token_id(TOKEN_ID).metadata([[1], [2], [3]]).progress({
+            let reported = Arc::clone(&reported);
+            move |minted, total| {
+                assert_eq!(total, 3);
+                reported.store(minted, Ordering::Relaxed);
+            }
+        });
+
+        *flow.batches.get_mut() = Vec::from([BatchState::Minted(Vec::from([1, 2, 3]))]);
+        flow.report_progress();
+
+        assert_eq!(reported.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be changed after it has started minting")]
+    fn change_after_start_panics() {
+        let mut flow = TokenMintFlow::new();
+
+        flow.token_id(TOKEN_ID).metadata([[1]]);
+        *flow.batches.get_mut() = Vec::from([BatchState::Pending]);
+
+        flow.add_metadata([2]);
+    }
+
+    #[tokio::test]
+    async fn execute_mints_every_batch() {
+        let network = FakeNetwork::new();
+        let mut flow = flow_of(5);
+
+        let minted = flow.execute_on(&network).await.unwrap();
+
+        assert_eq!(minted.len(), 5);
+        assert_eq!(network.minted.lock().len(), 3);
+
+        let mut serials: Vec<_> = minted.values().map(|it| it.serial).collect();
+        serials.sort_unstable();
+
+        assert_eq!(serials, [1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn execute_gives_each_batch_its_own_transaction_id() {
+        let network = FakeNetwork::new();
+        let mut flow = flow_of(6);
+
+        flow.max_concurrency(1).execute_on(&network).await.unwrap();
+
+        let minted = network.minted.lock();
+
+        assert_eq!(minted.len(), 3);
+
+        // generated as each batch starts, rather than all at once before the first batch is sent.
+        for pair in minted.windows(2) {
+            assert_ne!(pair[0].0, pair[1].0);
+        }
+    }
+
+    #[tokio::test]
+    async fn resume_remints_only_the_failed_batches() {
+        let network = FakeNetwork::new();
+        let mut flow = flow_of(6);
+
+        network.mints_before_failure.store(1, Ordering::Relaxed);
+
+        let error = flow.max_concurrency(1).execute_on(&network).await.unwrap_err();
+
+        assert_matches!(error, Error::GrpcStatus(_));
+        assert_eq!(flow.get_minted().len(), 2);
+        assert_matches!(flow.batches.get_mut()[1], BatchState::Submitted(_));
+        assert_matches!(flow.batches.get_mut()[2], BatchState::Pending);
+
+        // the second batch was never minted, its receipt just expired and the mirror node never saw it.
+        flow.batches.get_mut()[1] = submitted_at(OffsetDateTime::now_utc() - Duration::minutes(10));
+
+        network.mints_before_failure.store(usize::MAX, Ordering::Relaxed);
+
+        let minted = flow.execute_on(&network).await.unwrap();
+
+        assert_eq!(minted.len(), 6);
+        assert_eq!(network.minted.lock().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn resume_uses_receipt_of_submitted_batch() {
+        let network = FakeNetwork {
+            receipt: Some(receipt(Status::Success, Vec::from([7, 8]))),
+            ..FakeNetwork::new()
+        };
+
+        let mut flow = flow_of(2);
+
+        *flow.batches.get_mut() = Vec::from([submitted_at(OffsetDateTime::now_utc())]);
+
+        let minted = flow.execute_on(&network).await.unwrap();
+
+        assert_eq!(minted[&0], TOKEN_ID.nft(7));
+        assert_eq!(minted[&1], TOKEN_ID.nft(8));
+        assert!(network.minted.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn resume_falls_back_to_mirror_node_after_receipt_expires() {
+        let network = FakeNetwork {
+            mirror: Some(BatchState::Minted(Vec::from([3, 4]))),
+            ..FakeNetwork::new()
+        };
+
+        let mut flow = flow_of(2);
+
+        *flow.batches.get_mut() =
+            Vec::from([submitted_at(OffsetDateTime::now_utc() - Duration::minutes(10))]);
+
+        let minted = flow.execute_on(&network).await.unwrap();
+
+        assert_eq!(minted[&0], TOKEN_ID.nft(3));
+        assert_eq!(minted[&1], TOKEN_ID.nft(4));
+        assert!(network.minted.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn resume_of_recent_unknown_batch_fails() {
+        let network = FakeNetwork::new();
+        let mut flow = flow_of(2);
+
+        // the transaction could still reach consensus, so minting it again might mint twice.
+        *flow.batches.get_mut() = Vec::from([submitted_at(OffsetDateTime::now_utc())]);
+
+        let error = flow.execute_on(&network).await.unwrap_err();
+
+        assert_matches!(error, Error::QueryPreCheckStatus { status: Status::ReceiptNotFound, .. });
+        assert_matches!(flow.batches.get_mut()[0], BatchState::Submitted(_));
+        assert!(network.minted.lock().is_empty());
+    }
+}
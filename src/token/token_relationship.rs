@@ -0,0 +1,142 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use hedera_proto::services::{
+    self,
+    TokenFreezeStatus,
+    TokenKycStatus,
+};
+
+use crate::protobuf::ToProtobuf;
+use crate::{
+    FromProtobuf,
+    TokenId,
+};
+
+/// An account's relationship with a token it's associated with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenRelationship {
+    /// The token the account is associated with.
+    pub token_id: TokenId,
+
+    /// The symbol of the token.
+    pub symbol: String,
+
+    /// The account's balance of the token, in its smallest denomination.
+    pub balance: u64,
+
+    /// Whether the account has been granted KYC for the token, `None` if the token has no KYC key.
+    pub kyc_status: Option<bool>,
+
+    /// Whether the account is frozen for the token, `None` if the token has no freeze key.
+    pub freeze_status: Option<bool>,
+
+    /// The number of decimal places of the token.
+    pub decimals: u32,
+
+    /// Whether the association was created automatically, using one of the account's automatic association slots.
+    pub automatic_association: bool,
+}
+
+impl FromProtobuf<services::TokenRelationship> for TokenRelationship {
+    fn from_protobuf(pb: services::TokenRelationship) -> crate::Result<Self>
+    where
+        Self: Sized,
+    {
+        let kyc_status = match pb.kyc_status() {
+            TokenKycStatus::KycNotApplicable => None,
+            TokenKycStatus::Granted => Some(true),
+            TokenKycStatus::Revoked => Some(false),
+        };
+
+        let freeze_status = match pb.freeze_status() {
+            TokenFreezeStatus::FreezeNotApplicable => None,
+            TokenFreezeStatus::Frozen => Some(true),
+            TokenFreezeStatus::Unfrozen => Some(false),
+        };
+
+        let token_id = pb_getf!(pb, token_id)?;
+
+        Ok(Self {
+            token_id: TokenId::from_protobuf(token_id)?,
+            symbol: pb.symbol,
+            balance: pb.balance,
+            kyc_status,
+            freeze_status,
+            decimals: pb.decimals,
+            automatic_association: pb.automatic_association,
+        })
+    }
+}
+
+impl ToProtobuf for TokenRelationship {
+    type Protobuf = services::TokenRelationship;
+
+    fn to_protobuf(&self) -> Self::Protobuf {
+        services::TokenRelationship {
+            token_id: Some(self.token_id.to_protobuf()),
+            symbol: self.symbol.clone(),
+            balance: self.balance,
+            kyc_status: match self.kyc_status {
+                Some(true) => TokenKycStatus::Granted as i32,
+                Some(false) => TokenKycStatus::Revoked as i32,
+                None => TokenKycStatus::KycNotApplicable as i32,
+            },
+            freeze_status: match self.freeze_status {
+                Some(true) => TokenFreezeStatus::Frozen as i32,
+                Some(false) => TokenFreezeStatus::Unfrozen as i32,
+                None => TokenFreezeStatus::FreezeNotApplicable as i32,
+            },
+            decimals: self.decimals,
+            automatic_association: self.automatic_association,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::protobuf::{
+        FromProtobuf,
+        ToProtobuf,
+    };
+    use crate::transaction::test_helpers::TEST_TOKEN_ID;
+    use crate::TokenRelationship;
+
+    fn make_relationship() -> TokenRelationship {
+        TokenRelationship {
+            token_id: TEST_TOKEN_ID,
+            symbol: "F".to_owned(),
+            balance: 15,
+            kyc_status: Some(true),
+            freeze_status: None,
+            decimals: 2,
+            automatic_association: true,
+        }
+    }
+
+    #[test]
+    fn protobuf_round_trip() {
+        let relationship = make_relationship();
+
+        let round_tripped = TokenRelationship::from_protobuf(relationship.to_protobuf()).unwrap();
+
+        assert_eq!(round_tripped, relationship);
+    }
+}
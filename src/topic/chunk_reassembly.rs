@@ -0,0 +1,584 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::collections::{
+    HashMap,
+    VecDeque,
+};
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::task::{
+    self,
+    Poll,
+};
+
+use hedera_proto::mirror;
+use time::OffsetDateTime;
+use tokio::time::Sleep;
+
+use super::topic_message::{
+    PbTopicMessageChunk,
+    PbTopicMessageHeader,
+};
+use crate::protobuf::FromProtobuf;
+use crate::{
+    Error,
+    IncompleteTopicMessage,
+    IncompleteTopicMessageReason,
+    TopicMessage,
+    TopicMessageItem,
+    TransactionId,
+};
+
+/// What to do with the chunks of a chunked topic message that can't be reassembled.
+///
+/// See [`TopicMessageQuery::incomplete_message_policy`](crate::TopicMessageQuery::incomplete_message_policy)
+/// and [`TopicMessageQuery::duplicate_chunk_policy`](crate::TopicMessageQuery::duplicate_chunk_policy).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ChunkReassemblyPolicy {
+    /// Silently drop the chunks.
+    #[default]
+    Drop,
+
+    /// Yield a [`TopicMessageItem::Incomplete`] with the chunks, and continue the subscription.
+    ///
+    /// Streams of [`TopicMessage`]s can't hold incomplete messages, so they yield an
+    /// [`Error::IncompleteTopicMessage`] instead, see
+    /// [`TopicMessageQuery::subscribe_items`](crate::TopicMessageQuery::subscribe_items).
+    YieldPartial,
+
+    /// Yield an [`Error::IncompleteTopicMessage`] with the chunks, and end the subscription.
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ChunkReassemblyOptions {
+    /// How long to wait for the rest of a chunked message after receiving its first chunk.
+    pub(crate) timeout: std::time::Duration,
+
+    /// The maximum number of partially received chunked messages to hold on to.
+    pub(crate) max_incomplete_messages: Option<usize>,
+
+    /// What to do with chunked messages that expired or were evicted.
+    pub(crate) on_incomplete: ChunkReassemblyPolicy,
+
+    /// What to do with repeated chunks.
+    pub(crate) on_duplicate: ChunkReassemblyPolicy,
+}
+
+impl Default for ChunkReassemblyOptions {
+    fn default() -> Self {
+        Self {
+            timeout: std::time::Duration::from_secs(15 * 60),
+            max_incomplete_messages: None,
+            on_incomplete: ChunkReassemblyPolicy::Drop,
+            on_duplicate: ChunkReassemblyPolicy::Drop,
+        }
+    }
+}
+
+enum IncompleteMessage {
+    Partial(OffsetDateTime, Vec<PbTopicMessageChunk>),
+    Expired,
+    Complete,
+}
+
+/// Reassembles the chunks of chunked topic messages received from the mirror node.
+pub(crate) struct ChunkReassembler {
+    options: ChunkReassemblyOptions,
+    messages: HashMap<TransactionId, IncompleteMessage>,
+
+    /// The deadline of each partial message, in the order they started, which is also the order they expire in.
+    ///
+    /// Entries of messages that stopped being partial are skipped when they reach the front.
+    partials: VecDeque<(OffsetDateTime, TransactionId)>,
+    partial_count: usize,
+
+    /// When each message that was completed or given up on is forgotten, in that order.
+    ///
+    /// Until then its late chunks are recognized, instead of starting another message.
+    finished: VecDeque<(OffsetDateTime, TransactionId)>,
+
+    output: VecDeque<crate::Result<TopicMessageItem>>,
+
+    /// Fires when the partial message at the front of `partials` expires.
+    timer: Option<(OffsetDateTime, Pin<Box<Sleep>>)>,
+
+    failed: bool,
+}
+
+impl ChunkReassembler {
+    pub(crate) fn new(options: ChunkReassemblyOptions) -> Self {
+        Self {
+            options,
+            messages: HashMap::new(),
+            partials: VecDeque::new(),
+            partial_count: 0,
+            finished: VecDeque::new(),
+            output: VecDeque::new(),
+            timer: None,
+            failed: false,
+        }
+    }
+
+    /// Returns the next message (or error) that's ready to be yielded.
+    pub(crate) fn pop(&mut self) -> Option<crate::Result<TopicMessageItem>> {
+        self.output.pop_front()
+    }
+
+    /// Returns `true` once a policy of [`ChunkReassemblyPolicy::Error`] was applied,
+    /// after which nothing more is reassembled.
+    pub(crate) fn is_failed(&self) -> bool {
+        self.failed
+    }
+
+    /// Returns `true` if the message started by `initial_transaction_id` is still being reassembled.
+    pub(crate) fn is_in_flight(&self, initial_transaction_id: &TransactionId) -> bool {
+        matches!(self.messages.get(initial_transaction_id), Some(IncompleteMessage::Partial(..)))
+    }
+
+    pub(crate) fn push(&mut self, item: mirror::ConsensusTopicResponse) {
+        if self.failed {
+            return;
+        }
+
+        if let Err(e) = self.try_push(item, OffsetDateTime::now_utc()) {
+            self.output.push_back(Err(e));
+        }
+    }
+
+    /// Gives up on the partial messages that timed out, once the earliest of them does.
+    ///
+    /// Until then this returns [`Poll::Pending`], and `cx` is woken when it's time.
+    pub(crate) fn poll_expire(&mut self, cx: &mut task::Context<'_>) -> Poll<()> {
+        if self.failed {
+            return Poll::Pending;
+        }
+
+        let Some(deadline) = self.next_deadline() else {
+            self.timer = None;
+            return Poll::Pending;
+        };
+
+        if self.timer.as_ref().is_some_and(|(at, _)| *at != deadline) {
+            self.timer = None;
+        }
+
+        let (_, timer) = self.timer.get_or_insert_with(|| {
+            let wait = std::time::Duration::try_from(deadline - OffsetDateTime::now_utc())
+                .unwrap_or_default();
+
+            (deadline, Box::pin(tokio::time::sleep(wait)))
+        });
+
+        task::ready!(timer.as_mut().poll(cx));
+
+        self.timer = None;
+        self.expire(OffsetDateTime::now_utc().max(deadline));
+
+        Poll::Ready(())
+    }
+
+    fn is_partial_until(&self, tx_id: &TransactionId, deadline: OffsetDateTime) -> bool {
+        matches!(self.messages.get(tx_id), Some(IncompleteMessage::Partial(it, _)) if *it == deadline)
+    }
+
+    /// Returns the deadline of the partial message that expires first.
+    fn next_deadline(&mut self) -> Option<OffsetDateTime> {
+        while let Some(&(deadline, tx_id)) = self.partials.front() {
+            if self.is_partial_until(&tx_id, deadline) {
+                return Some(deadline);
+            }
+
+            self.partials.pop_front();
+        }
+
+        None
+    }
+
+    fn try_push(
+        &mut self,
+        mut item: mirror::ConsensusTopicResponse,
+        now: OffsetDateTime,
+    ) -> crate::Result<()> {
+        self.expire(now);
+
+        let header = PbTopicMessageHeader {
+            consensus_timestamp: pb_getf!(item, consensus_timestamp)?.into(),
+            sequence_number: item.sequence_number,
+            running_hash: item.running_hash,
+            running_hash_version: item.running_hash_version,
+            message: item.message,
+        };
+
+        let chunk = match item.chunk_info.take() {
+            Some(chunk_info) if chunk_info.total > 1 => PbTopicMessageChunk {
+                header,
+                initial_transaction_id: TransactionId::from_protobuf(pb_getf!(
+                    chunk_info,
+                    initial_transaction_id
+                )?)?,
+                number: chunk_info.number,
+                total: chunk_info.total,
+            },
+            _ => {
+                self.output
+                    .push_back(Ok(TopicMessageItem::Message(TopicMessage::from_single(header))));
+                return Ok(());
+            }
+        };
+
+        let tx_id = chunk.initial_transaction_id;
+
+        if !self.messages.contains_key(&tx_id) {
+            self.evict_for_new_message(now);
+
+            let deadline = now + self.options.timeout;
+
+            self.messages.insert(tx_id, IncompleteMessage::Partial(deadline, Vec::new()));
+            self.partials.push_back((deadline, tx_id));
+            self.partial_count += 1;
+        }
+
+        let Some(entry) = self.messages.get_mut(&tx_id) else {
+            return Ok(());
+        };
+
+        let chunks = match entry {
+            IncompleteMessage::Partial(_, chunks) => chunks,
+            // a chunk of a message that we already gave up on, which was already handled.
+            IncompleteMessage::Expired => return Ok(()),
+            IncompleteMessage::Complete => {
+                let reason = IncompleteTopicMessageReason::DuplicateChunk;
+                self.apply(self.options.on_duplicate, vec![chunk], reason);
+                return Ok(());
+            }
+        };
+
+        let index = match chunks.binary_search_by_key(&chunk.number, |it| it.number) {
+            Ok(_) => {
+                let reason = IncompleteTopicMessageReason::DuplicateChunk;
+                self.apply(self.options.on_duplicate, vec![chunk], reason);
+                return Ok(());
+            }
+            Err(index) => index,
+        };
+
+        chunks.insert(index, chunk);
+
+        // find the smallest `total` so that we aren't susceptable to stuff like total changing (and getting bigger)
+        // later on there's a check that ensures that they all have the same total.
+        let total = chunks.iter().map(|it| it.total).min().unwrap();
+
+        // note: because of the way we handle `total`, `total` can get *smaller*.
+        if chunks.len() >= total as usize {
+            let chunks = mem::take(chunks);
+            self.finish(tx_id, IncompleteMessage::Complete, now);
+            self.output.push_back(Ok(TopicMessageItem::Message(TopicMessage::from_chunks(chunks))));
+        }
+
+        Ok(())
+    }
+
+    /// Gives up on every message that wasn't reassembled before its timeout,
+    /// and forgets the messages that were finished long enough ago.
+    fn expire(&mut self, now: OffsetDateTime) {
+        while let Some(deadline) = self.next_deadline() {
+            if deadline > now {
+                break;
+            }
+
+            let (_, tx_id) = self.partials.pop_front().unwrap();
+
+            self.give_up(tx_id, IncompleteTopicMessageReason::Expired, now);
+        }
+
+        while let Some(&(forget_at, tx_id)) = self.finished.front() {
+            if forget_at > now {
+                break;
+            }
+
+            self.finished.pop_front();
+            self.messages.remove(&tx_id);
+        }
+    }
+
+    /// Makes room for a new message if there are already as many partial messages as allowed,
+    /// by giving up on the ones that started first.
+    fn evict_for_new_message(&mut self, now: OffsetDateTime) {
+        let Some(max) = self.options.max_incomplete_messages else { return };
+
+        while self.partial_count >= max {
+            if self.next_deadline().is_none() {
+                return;
+            }
+
+            let (_, tx_id) = self.partials.pop_front().unwrap();
+
+            self.give_up(tx_id, IncompleteTopicMessageReason::Evicted, now);
+        }
+    }
+
+    /// Marks the partial message `tx_id` as `state`, remembering it for another timeout to recognize its late chunks.
+    ///
+    /// Returns the chunks received so far.
+    fn finish(
+        &mut self,
+        tx_id: TransactionId,
+        state: IncompleteMessage,
+        now: OffsetDateTime,
+    ) -> Vec<PbTopicMessageChunk> {
+        let Some(message) = self.messages.get_mut(&tx_id) else { return Vec::new() };
+
+        let IncompleteMessage::Partial(_, chunks) = mem::replace(message, state) else {
+            return Vec::new();
+        };
+
+        self.partial_count -= 1;
+        self.finished.push_back((now + self.options.timeout, tx_id));
+
+        chunks
+    }
+
+    fn give_up(
+        &mut self,
+        tx_id: TransactionId,
+        reason: IncompleteTopicMessageReason,
+        now: OffsetDateTime,
+    ) {
+        let chunks = self.finish(tx_id, IncompleteMessage::Expired, now);
+
+        if !chunks.is_empty() {
+            self.apply(self.options.on_incomplete, chunks, reason);
+        }
+    }
+
+    fn apply(
+        &mut self,
+        policy: ChunkReassemblyPolicy,
+        chunks: Vec<PbTopicMessageChunk>,
+        reason: IncompleteTopicMessageReason,
+    ) {
+        let message = || IncompleteTopicMessage::from_chunks(chunks, reason);
+
+        match policy {
+            ChunkReassemblyPolicy::Drop => {}
+            ChunkReassemblyPolicy::YieldPartial => {
+                self.output.push_back(Ok(TopicMessageItem::Incomplete(message())));
+            }
+            ChunkReassemblyPolicy::Error => {
+                self.output.push_back(Err(Error::IncompleteTopicMessage(Box::new(message()))));
+                self.failed = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use futures_util::future::poll_fn;
+    use hedera_proto::{
+        mirror,
+        services,
+    };
+    use time::{
+        Duration,
+        OffsetDateTime,
+    };
+
+    use super::{
+        ChunkReassembler,
+        ChunkReassemblyOptions,
+        ChunkReassemblyPolicy,
+    };
+    use crate::{
+        AccountId,
+        Error,
+        IncompleteTopicMessageReason,
+        ToProtobuf,
+        TopicMessageItem,
+        TransactionId,
+    };
+
+    fn tx_id(n: u64) -> TransactionId {
+        TransactionId::generate(AccountId::new(0, 0, n))
+    }
+
+    fn make_chunk(
+        tx_id: TransactionId,
+        sequence_number: u64,
+        number: i32,
+        total: i32,
+    ) -> mirror::ConsensusTopicResponse {
+        mirror::ConsensusTopicResponse {
+            consensus_timestamp: Some(services::Timestamp {
+                seconds: 1_700_000_000,
+                nanos: sequence_number as i32,
+            }),
+            message: vec![number as u8; 4],
+            running_hash: vec![0; 48],
+            sequence_number,
+            running_hash_version: 3,
+            chunk_info: Some(services::ConsensusMessageChunkInfo {
+                initial_transaction_id: Some(tx_id.to_protobuf()),
+                total,
+                number,
+            }),
+        }
+    }
+
+    #[test]
+    fn reassembles() {
+        let tx_id = tx_id(1001);
+        let mut reassembler = ChunkReassembler::new(ChunkReassemblyOptions::default());
+
+        reassembler.push(make_chunk(tx_id, 2, 2, 2));
+        assert!(reassembler.pop().is_none());
+        assert!(reassembler.is_in_flight(&tx_id));
+
+        reassembler.push(make_chunk(tx_id, 1, 1, 2));
+
+        let message = assert_matches!(
+            reassembler.pop(),
+            Some(Ok(TopicMessageItem::Message(message))) => message
+        );
+        assert_eq!(message.contents, [1, 1, 1, 1, 2, 2, 2, 2]);
+        assert!(!reassembler.is_in_flight(&tx_id));
+    }
+
+    #[test]
+    fn duplicate_yield_partial() {
+        let tx_id = tx_id(1001);
+        let mut reassembler = ChunkReassembler::new(ChunkReassemblyOptions {
+            on_duplicate: ChunkReassemblyPolicy::YieldPartial,
+            ..Default::default()
+        });
+
+        reassembler.push(make_chunk(tx_id, 1, 1, 2));
+        reassembler.push(make_chunk(tx_id, 2, 1, 2));
+
+        assert_matches!(
+            reassembler.pop(),
+            Some(Ok(TopicMessageItem::Incomplete(message)))
+                if message.reason == IncompleteTopicMessageReason::DuplicateChunk
+        );
+
+        reassembler.push(make_chunk(tx_id, 3, 2, 2));
+        assert_matches!(reassembler.pop(), Some(Ok(TopicMessageItem::Message(_))));
+    }
+
+    #[test]
+    fn expired_error() {
+        let tx_id = tx_id(1001);
+        let mut reassembler = ChunkReassembler::new(ChunkReassemblyOptions {
+            on_incomplete: ChunkReassemblyPolicy::Error,
+            ..Default::default()
+        });
+
+        let now = OffsetDateTime::now_utc();
+
+        reassembler.try_push(make_chunk(tx_id, 1, 1, 2), now).unwrap();
+        reassembler
+            .try_push(make_chunk(self::tx_id(1002), 2, 1, 2), now + Duration::minutes(16))
+            .unwrap();
+
+        assert_matches!(
+            reassembler.pop(),
+            Some(Err(Error::IncompleteTopicMessage(message)))
+                if message.reason == IncompleteTopicMessageReason::Expired
+                    && message.initial_transaction_id == tx_id
+        );
+
+        assert!(reassembler.is_failed());
+    }
+
+    #[test]
+    fn evicts_oldest() {
+        let first = tx_id(1001);
+        let second = tx_id(1002);
+        let mut reassembler = ChunkReassembler::new(ChunkReassemblyOptions {
+            max_incomplete_messages: Some(1),
+            on_incomplete: ChunkReassemblyPolicy::YieldPartial,
+            ..Default::default()
+        });
+
+        reassembler.push(make_chunk(first, 1, 1, 2));
+        reassembler.push(make_chunk(second, 2, 1, 2));
+
+        assert_matches!(
+            reassembler.pop(),
+            Some(Ok(TopicMessageItem::Incomplete(message)))
+                if message.reason == IncompleteTopicMessageReason::Evicted
+                    && message.initial_transaction_id == first
+        );
+
+        assert!(!reassembler.is_in_flight(&first));
+        assert!(reassembler.is_in_flight(&second));
+    }
+
+    #[test]
+    fn forgets_finished_messages() {
+        let tx_id = tx_id(1001);
+        let mut reassembler = ChunkReassembler::new(ChunkReassemblyOptions::default());
+
+        let now = OffsetDateTime::now_utc();
+
+        reassembler.try_push(make_chunk(tx_id, 1, 1, 2), now).unwrap();
+        reassembler.try_push(make_chunk(tx_id, 2, 2, 2), now).unwrap();
+        assert_matches!(reassembler.pop(), Some(Ok(TopicMessageItem::Message(_))));
+
+        // a late chunk is still recognized as a duplicate.
+        reassembler.try_push(make_chunk(tx_id, 3, 2, 2), now + Duration::minutes(14)).unwrap();
+        assert!(reassembler.messages.contains_key(&tx_id));
+        assert!(!reassembler.is_in_flight(&tx_id));
+
+        reassembler
+            .try_push(make_chunk(self::tx_id(1002), 4, 1, 1), now + Duration::minutes(16))
+            .unwrap();
+
+        assert!(!reassembler.messages.contains_key(&tx_id));
+        assert!(reassembler.finished.is_empty());
+    }
+
+    #[tokio::test]
+    async fn expires_without_new_chunks() {
+        let tx_id = tx_id(1001);
+        let mut reassembler = ChunkReassembler::new(ChunkReassemblyOptions {
+            timeout: std::time::Duration::from_millis(10),
+            on_incomplete: ChunkReassemblyPolicy::YieldPartial,
+            ..Default::default()
+        });
+
+        reassembler.push(make_chunk(tx_id, 1, 1, 2));
+        assert!(reassembler.pop().is_none());
+
+        poll_fn(|cx| reassembler.poll_expire(cx)).await;
+
+        assert_matches!(
+            reassembler.pop(),
+            Some(Ok(TopicMessageItem::Incomplete(message)))
+                if message.reason == IncompleteTopicMessageReason::Expired
+                    && message.initial_transaction_id == tx_id
+        );
+        assert!(!reassembler.is_in_flight(&tx_id));
+    }
+}
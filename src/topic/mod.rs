@@ -18,6 +18,8 @@
  * ‍
  */
 
+mod chunk_reassembly;
+mod topic_checkpoint;
 mod topic_create_transaction;
 mod topic_delete_transaction;
 mod topic_id;
@@ -26,8 +28,17 @@ mod topic_info_query;
 mod topic_message;
 mod topic_message_query;
 mod topic_message_submit_transaction;
+mod topic_message_subscription;
+mod topic_multiplexer;
+mod topic_running_hash;
 mod topic_update_transaction;
 
+pub use chunk_reassembly::ChunkReassemblyPolicy;
+pub use topic_checkpoint::{
+    FileTopicCheckpointStore,
+    TopicCheckpoint,
+    TopicCheckpointStore,
+};
 pub use topic_create_transaction::TopicCreateTransaction;
 pub(crate) use topic_create_transaction::TopicCreateTransactionData;
 pub use topic_delete_transaction::TopicDeleteTransaction;
@@ -36,10 +47,22 @@ pub use topic_id::TopicId;
 pub use topic_info::TopicInfo;
 pub use topic_info_query::TopicInfoQuery;
 pub(crate) use topic_info_query::TopicInfoQueryData;
-pub use topic_message::TopicMessage;
+pub use topic_message::{
+    IncompleteTopicMessage,
+    IncompleteTopicMessageReason,
+    TopicMessage,
+    TopicMessageChunk,
+    TopicMessageItem,
+};
 pub use topic_message_query::TopicMessageQuery;
 pub(crate) use topic_message_query::TopicMessageQueryData;
 pub use topic_message_submit_transaction::TopicMessageSubmitTransaction;
 pub(crate) use topic_message_submit_transaction::TopicMessageSubmitTransactionData;
+pub use topic_message_subscription::TopicMessageSubscription;
+pub use topic_multiplexer::{
+    TopicMultiplexer,
+    TopicMultiplexerHandle,
+};
+pub use topic_running_hash::TopicRunningHash;
 pub use topic_update_transaction::TopicUpdateTransaction;
 pub(crate) use topic_update_transaction::TopicUpdateTransactionData;
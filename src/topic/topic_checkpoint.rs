@@ -0,0 +1,416 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::{
+    fs,
+    io,
+};
+
+use time::{
+    Duration,
+    OffsetDateTime,
+};
+
+use crate::{
+    atomic_file,
+    Error,
+    TopicId,
+    TopicRunningHash,
+    TransactionId,
+};
+
+const HEADER: &str = "hedera-topic-checkpoint 1";
+
+/// The position of a durable topic subscription, after the last message it acknowledged.
+///
+/// See [`TopicMessageQuery::subscribe_durable`](crate::TopicMessageQuery::subscribe_durable).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicCheckpoint {
+    sequence_number: u64,
+    consensus_timestamp: OffsetDateTime,
+    running_hash: Vec<u8>,
+    pending: Vec<PendingChunkedMessage>,
+}
+
+/// A chunked message that was partially received when a checkpoint was taken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PendingChunkedMessage {
+    pub(crate) initial_transaction_id: TransactionId,
+
+    /// The consensus timestamp of the first chunk received.
+    pub(crate) consensus_timestamp: OffsetDateTime,
+
+    /// The running hash of the topic before the first chunk received, if it's known.
+    pub(crate) previous: Option<TopicRunningHash>,
+}
+
+impl TopicCheckpoint {
+    pub(crate) fn new(
+        sequence_number: u64,
+        consensus_timestamp: OffsetDateTime,
+        running_hash: Vec<u8>,
+        pending: Vec<PendingChunkedMessage>,
+    ) -> Self {
+        Self { sequence_number, consensus_timestamp, running_hash, pending }
+    }
+
+    /// Returns the sequence number of the last acknowledged message.
+    ///
+    /// For chunked messages, this is the sequence number of the *last* chunk.
+    #[must_use]
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    /// Returns the consensus timestamp of the last acknowledged message.
+    #[must_use]
+    pub fn consensus_timestamp(&self) -> OffsetDateTime {
+        self.consensus_timestamp
+    }
+
+    /// Returns the running hash of the topic after the last acknowledged message.
+    #[must_use]
+    pub fn running_hash(&self) -> &[u8] {
+        &self.running_hash
+    }
+
+    /// Returns `true` if chunked messages were partially received when this checkpoint was taken.
+    ///
+    /// Resuming from such a checkpoint receives their chunks again.
+    #[must_use]
+    pub fn has_pending_chunks(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// The earliest chunk of the messages that were partially received, if there were any.
+    fn earliest_pending(&self) -> Option<&PendingChunkedMessage> {
+        self.pending.iter().min_by_key(|it| it.consensus_timestamp)
+    }
+
+    /// Returns the time a subscription resuming from this checkpoint starts at.
+    pub(crate) fn resume_start_time(&self) -> OffsetDateTime {
+        match self.earliest_pending() {
+            Some(pending) => pending.consensus_timestamp,
+            None => self.consensus_timestamp + Duration::nanoseconds(1),
+        }
+    }
+
+    /// Returns the running hash of the topic before the first message a resuming subscription receives, if it's known.
+    pub(crate) fn resume_running_hash(&self) -> Option<TopicRunningHash> {
+        match self.earliest_pending() {
+            Some(pending) => pending.previous.clone(),
+            None => Some(self.to_running_hash()),
+        }
+    }
+
+    /// Returns the running hash of the topic after the last acknowledged message.
+    pub(crate) fn to_running_hash(&self) -> TopicRunningHash {
+        TopicRunningHash::new(self.sequence_number, self.running_hash.clone())
+    }
+
+    /// Returns `true` if `initial_transaction_id` identifies a message that was partially received.
+    pub(crate) fn is_pending(&self, initial_transaction_id: &TransactionId) -> bool {
+        self.pending.iter().any(|it| &it.initial_transaction_id == initial_transaction_id)
+    }
+
+    /// Create a new `TopicCheckpoint` from bytes produced by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Errors
+    /// - [`Error::BasicParse`] if `bytes` isn't a valid checkpoint.
+    pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
+        let text = std::str::from_utf8(bytes).map_err(Error::basic_parse)?;
+        let mut lines = text.lines();
+
+        if lines.next() != Some(HEADER) {
+            return Err(Error::basic_parse("expected a topic checkpoint header"));
+        }
+
+        let mut sequence_number = None;
+        let mut consensus_timestamp = None;
+        let mut running_hash = None;
+        let mut pending = Vec::new();
+
+        for line in lines {
+            let mut fields = line.split(' ');
+
+            match fields.next() {
+                Some("sequence_number") => {
+                    sequence_number =
+                        Some(next_field(&mut fields)?.parse().map_err(Error::basic_parse)?);
+                }
+                Some("consensus_timestamp") => {
+                    consensus_timestamp = Some(parse_timestamp(next_field(&mut fields)?)?);
+                }
+                Some("running_hash") => {
+                    running_hash =
+                        Some(hex::decode(next_field(&mut fields)?).map_err(Error::basic_parse)?);
+                }
+                Some("pending") => {
+                    let initial_transaction_id = next_field(&mut fields)?.parse()?;
+                    let consensus_timestamp = parse_timestamp(next_field(&mut fields)?)?;
+
+                    let previous = match (fields.next(), fields.next()) {
+                        (Some(sequence_number), Some(running_hash)) => Some(TopicRunningHash::new(
+                            sequence_number.parse().map_err(Error::basic_parse)?,
+                            hex::decode(running_hash).map_err(Error::basic_parse)?,
+                        )),
+                        (None, None) => None,
+                        _ => return Err(Error::basic_parse("malformed pending chunked message")),
+                    };
+
+                    pending.push(PendingChunkedMessage {
+                        initial_transaction_id,
+                        consensus_timestamp,
+                        previous,
+                    });
+                }
+                _ => return Err(Error::basic_parse(format!("unexpected line `{line}`"))),
+            }
+        }
+
+        let missing = |name: &str| Error::basic_parse(format!("missing `{name}`"));
+
+        Ok(Self {
+            sequence_number: sequence_number.ok_or_else(|| missing("sequence_number"))?,
+            consensus_timestamp: consensus_timestamp
+                .ok_or_else(|| missing("consensus_timestamp"))?,
+            running_hash: running_hash.ok_or_else(|| missing("running_hash"))?,
+            pending,
+        })
+    }
+
+    /// Convert `self` to bytes that can be persisted and read back with [`from_bytes`](Self::from_bytes).
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut text = String::new();
+
+        // writing to a `String` can't fail.
+        let _ = writeln!(text, "{HEADER}");
+        let _ = writeln!(text, "sequence_number {}", self.sequence_number);
+        let _ = writeln!(
+            text,
+            "consensus_timestamp {}",
+            self.consensus_timestamp.unix_timestamp_nanos()
+        );
+        let _ = writeln!(text, "running_hash {}", hex::encode(&self.running_hash));
+
+        for pending in &self.pending {
+            let _ = write!(
+                text,
+                "pending {} {}",
+                pending.initial_transaction_id,
+                pending.consensus_timestamp.unix_timestamp_nanos()
+            );
+
+            if let Some(previous) = &pending.previous {
+                let _ = write!(
+                    text,
+                    " {} {}",
+                    previous.sequence_number,
+                    hex::encode(&previous.running_hash)
+                );
+            }
+
+            text.push('\n');
+        }
+
+        text.into_bytes()
+    }
+}
+
+fn next_field<'a>(fields: &mut impl Iterator<Item = &'a str>) -> crate::Result<&'a str> {
+    fields.next().ok_or_else(|| Error::basic_parse("unexpected end of line"))
+}
+
+fn parse_timestamp(s: &str) -> crate::Result<OffsetDateTime> {
+    let nanos = s.parse().map_err(Error::basic_parse)?;
+
+    OffsetDateTime::from_unix_timestamp_nanos(nanos).map_err(Error::basic_parse)
+}
+
+/// Persists the checkpoints of durable topic subscriptions.
+///
+/// See [`TopicMessageQuery::subscribe_durable`](crate::TopicMessageQuery::subscribe_durable).
+pub trait TopicCheckpointStore: Send + Sync {
+    /// Returns the stored checkpoint of `topic_id`, if there is one.
+    ///
+    /// # Errors
+    /// - [`Error::TopicCheckpointStore`] if the checkpoint can't be read.
+    fn load(&self, topic_id: TopicId) -> crate::Result<Option<TopicCheckpoint>>;
+
+    /// Stores `checkpoint` as the checkpoint of `topic_id`, replacing any previous one.
+    ///
+    /// This is called whenever a message is acknowledged, and must not return
+    /// until the checkpoint would be returned by `load` after a restart.
+    ///
+    /// # Errors
+    /// - [`Error::TopicCheckpointStore`] if the checkpoint can't be written.
+    fn save(&self, topic_id: TopicId, checkpoint: &TopicCheckpoint) -> crate::Result<()>;
+}
+
+impl<T: TopicCheckpointStore + ?Sized> TopicCheckpointStore for &T {
+    fn load(&self, topic_id: TopicId) -> crate::Result<Option<TopicCheckpoint>> {
+        T::load(self, topic_id)
+    }
+
+    fn save(&self, topic_id: TopicId, checkpoint: &TopicCheckpoint) -> crate::Result<()> {
+        T::save(self, topic_id, checkpoint)
+    }
+}
+
+/// A [`TopicCheckpointStore`] that keeps a file per topic in a directory.
+#[derive(Debug, Clone)]
+pub struct FileTopicCheckpointStore {
+    directory: PathBuf,
+}
+
+impl FileTopicCheckpointStore {
+    /// Create a new `FileTopicCheckpointStore` keeping its files in `directory`.
+    ///
+    /// The directory is created when the first checkpoint is saved.
+    #[must_use]
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self { directory: directory.into() }
+    }
+
+    /// Returns the directory checkpoints are kept in.
+    #[must_use]
+    pub fn directory(&self) -> &std::path::Path {
+        &self.directory
+    }
+
+    fn path(&self, topic_id: TopicId) -> PathBuf {
+        self.directory.join(format!("{topic_id}.checkpoint"))
+    }
+}
+
+impl TopicCheckpointStore for FileTopicCheckpointStore {
+    fn load(&self, topic_id: TopicId) -> crate::Result<Option<TopicCheckpoint>> {
+        let bytes = match fs::read(self.path(topic_id)) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(Error::TopicCheckpointStore(e.into())),
+        };
+
+        TopicCheckpoint::from_bytes(&bytes).map(Some)
+    }
+
+    fn save(&self, topic_id: TopicId, checkpoint: &TopicCheckpoint) -> crate::Result<()> {
+        atomic_file::write(&self.path(topic_id), &checkpoint.to_bytes())
+            .map_err(|e| Error::TopicCheckpointStore(e.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::OffsetDateTime;
+
+    use super::{
+        FileTopicCheckpointStore,
+        PendingChunkedMessage,
+        TopicCheckpoint,
+        TopicCheckpointStore,
+    };
+    use crate::{
+        TopicId,
+        TopicRunningHash,
+    };
+
+    fn timestamp(nanos: i128) -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp_nanos(1_700_000_000_000_000_000 + nanos).unwrap()
+    }
+
+    fn make_checkpoint() -> TopicCheckpoint {
+        TopicCheckpoint::new(
+            42,
+            timestamp(42),
+            vec![0xab; 48],
+            vec![
+                PendingChunkedMessage {
+                    initial_transaction_id: "0.0.1001@1700000000.000000001".parse().unwrap(),
+                    consensus_timestamp: timestamp(40),
+                    previous: Some(TopicRunningHash::new(39, vec![0xcd; 48])),
+                },
+                PendingChunkedMessage {
+                    initial_transaction_id: "0.0.1002@1700000000.000000002".parse().unwrap(),
+                    consensus_timestamp: timestamp(41),
+                    previous: None,
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn to_from_bytes() {
+        let checkpoint = make_checkpoint();
+
+        assert_eq!(TopicCheckpoint::from_bytes(&checkpoint.to_bytes()).unwrap(), checkpoint);
+    }
+
+    #[test]
+    fn resume_without_pending() {
+        let checkpoint = TopicCheckpoint::new(42, timestamp(42), vec![0xab; 48], Vec::new());
+
+        assert_eq!(checkpoint.resume_start_time(), timestamp(43));
+        assert_eq!(
+            checkpoint.resume_running_hash(),
+            Some(TopicRunningHash::new(42, vec![0xab; 48]))
+        );
+    }
+
+    #[test]
+    fn resume_with_pending() {
+        let checkpoint = make_checkpoint();
+
+        assert_eq!(checkpoint.resume_start_time(), timestamp(40));
+        assert_eq!(
+            checkpoint.resume_running_hash(),
+            Some(TopicRunningHash::new(39, vec![0xcd; 48]))
+        );
+        assert!(checkpoint.is_pending(&"0.0.1002@1700000000.000000002".parse().unwrap()));
+        assert!(!checkpoint.is_pending(&"0.0.1003@1700000000.000000003".parse().unwrap()));
+    }
+
+    #[test]
+    fn from_bytes_missing_field() {
+        assert!(
+            TopicCheckpoint::from_bytes(b"hedera-topic-checkpoint 1\nsequence_number 1\n").is_err()
+        );
+    }
+
+    #[test]
+    fn file_store() {
+        let directory = std::env::temp_dir()
+            .join(format!("hedera-topic-checkpoint-test-{}", std::process::id()));
+
+        let store = FileTopicCheckpointStore::new(&directory);
+        let topic_id = TopicId::new(0, 0, 5005);
+
+        assert_eq!(store.load(topic_id).unwrap(), None);
+
+        store.save(topic_id, &make_checkpoint()).unwrap();
+
+        assert_eq!(store.load(topic_id).unwrap(), Some(make_checkpoint()));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -40,6 +40,7 @@ use crate::{
     AccountId,
     BoxGrpcFuture,
     Error,
+    FixedFee,
     Key,
     Transaction,
     ValidateChecksums,
@@ -73,6 +74,15 @@ pub struct TopicCreateTransactionData {
 
     /// Account to be used at the topic's expiration time to extend the life of the topic.
     auto_renew_account_id: Option<AccountId>,
+
+    /// Access control for updating the topic's custom fees.
+    fee_schedule_key: Option<Key>,
+
+    /// Keys that are exempt from paying the topic's custom fees when they sign a message submission.
+    fee_exempt_keys: Vec<Key>,
+
+    /// Fixed fees charged for each message submitted to the topic.
+    custom_fees: Vec<FixedFee>,
 }
 
 impl Default for TopicCreateTransactionData {
@@ -83,6 +93,9 @@ impl Default for TopicCreateTransactionData {
             submit_key: None,
             auto_renew_period: Some(Duration::days(90)),
             auto_renew_account_id: None,
+            fee_schedule_key: None,
+            fee_exempt_keys: Vec::new(),
+            custom_fees: Vec::new(),
         }
     }
 }
@@ -153,6 +166,58 @@ impl TopicCreateTransaction {
         self.data_mut().auto_renew_account_id = Some(id);
         self
     }
+
+    /// Returns the access control for updating the topic's custom fees.
+    #[must_use]
+    pub fn get_fee_schedule_key(&self) -> Option<&Key> {
+        self.data().fee_schedule_key.as_ref()
+    }
+
+    /// Sets the access control for updating the topic's custom fees.
+    ///
+    /// Without a fee schedule key, the topic's custom fees can't be changed after creation.
+    pub fn fee_schedule_key(&mut self, key: impl Into<Key>) -> &mut Self {
+        self.data_mut().fee_schedule_key = Some(key.into());
+        self
+    }
+
+    /// Returns the keys that are exempt from paying the topic's custom fees.
+    #[must_use]
+    pub fn get_fee_exempt_keys(&self) -> &[Key] {
+        &self.data().fee_exempt_keys
+    }
+
+    /// Sets the keys that are exempt from paying the topic's custom fees.
+    ///
+    /// A message submission signed by any of these keys isn't charged the custom fees.
+    pub fn fee_exempt_keys(&mut self, keys: impl IntoIterator<Item = Key>) -> &mut Self {
+        self.data_mut().fee_exempt_keys = keys.into_iter().collect();
+        self
+    }
+
+    /// Adds a key that is exempt from paying the topic's custom fees.
+    pub fn add_fee_exempt_key(&mut self, key: impl Into<Key>) -> &mut Self {
+        self.data_mut().fee_exempt_keys.push(key.into());
+        self
+    }
+
+    /// Returns the fixed fees charged for each message submitted to the topic.
+    #[must_use]
+    pub fn get_custom_fees(&self) -> &[FixedFee] {
+        &self.data().custom_fees
+    }
+
+    /// Sets the fixed fees charged for each message submitted to the topic.
+    pub fn custom_fees(&mut self, fees: impl IntoIterator<Item = FixedFee>) -> &mut Self {
+        self.data_mut().custom_fees = fees.into_iter().collect();
+        self
+    }
+
+    /// Adds a fixed fee charged for each message submitted to the topic.
+    pub fn add_custom_fee(&mut self, fee: FixedFee) -> &mut Self {
+        self.data_mut().custom_fees.push(fee);
+        self
+    }
 }
 
 impl TransactionData for TopicCreateTransactionData {}
@@ -169,6 +234,11 @@ impl TransactionExecute for TopicCreateTransactionData {
 
 impl ValidateChecksums for TopicCreateTransactionData {
     fn validate_checksums(&self, ledger_id: &RefLedgerId) -> Result<(), Error> {
+        for fee in &self.custom_fees {
+            fee.fee_collector_account_id.validate_checksums(ledger_id)?;
+            fee.fee.denominating_token_id.validate_checksums(ledger_id)?;
+        }
+
         self.auto_renew_account_id.validate_checksums(ledger_id)
     }
 }
@@ -206,6 +276,9 @@ impl FromProtobuf<services::ConsensusCreateTopicTransactionBody> for TopicCreate
             submit_key: Option::from_protobuf(pb.submit_key)?,
             auto_renew_period: pb.auto_renew_period.map(Into::into),
             auto_renew_account_id: Option::from_protobuf(pb.auto_renew_account)?,
+            fee_schedule_key: Option::from_protobuf(pb.fee_schedule_key)?,
+            fee_exempt_keys: Vec::from_protobuf(pb.fee_exempt_key_list)?,
+            custom_fees: Vec::from_protobuf(pb.custom_fees)?,
         })
     }
 }
@@ -220,6 +293,9 @@ impl ToProtobuf for TopicCreateTransactionData {
             admin_key: self.admin_key.to_protobuf(),
             submit_key: self.submit_key.to_protobuf(),
             auto_renew_period: self.auto_renew_period.to_protobuf(),
+            fee_schedule_key: self.fee_schedule_key.to_protobuf(),
+            fee_exempt_key_list: self.fee_exempt_keys.to_protobuf(),
+            custom_fees: self.custom_fees.to_protobuf(),
         }
     }
 }
@@ -243,6 +319,10 @@ mod tests {
     use crate::{
         AccountId,
         AnyTransaction,
+        FixedFee,
+        FixedFeeData,
+        Hbar,
+        Key,
         PublicKey,
         TopicCreateTransaction,
     };
@@ -251,6 +331,14 @@ mod tests {
         unused_private_key().public_key()
     }
 
+    fn custom_fee() -> FixedFee {
+        FixedFee {
+            fee: FixedFeeData::from_hbar(Hbar::new(1)),
+            fee_collector_account_id: Some(AccountId::new(0, 0, 5008)),
+            all_collectors_are_exempt: false,
+        }
+    }
+
     const AUTO_RENEW_ACCOUNT_ID: AccountId = AccountId::new(0, 0, 5007);
     const AUTO_RENEW_PERIOD: Duration = Duration::days(1);
 
@@ -379,6 +467,9 @@ mod tests {
                             ),
                         },
                     ),
+                    fee_schedule_key: None,
+                    fee_exempt_key_list: [],
+                    custom_fees: [],
                 },
             )
         "#]]
@@ -406,6 +497,9 @@ mod tests {
             submit_key: Some(key().to_protobuf()),
             auto_renew_period: Some(AUTO_RENEW_PERIOD.to_protobuf()),
             auto_renew_account: Some(AUTO_RENEW_ACCOUNT_ID.to_protobuf()),
+            fee_schedule_key: Some(key().to_protobuf()),
+            fee_exempt_key_list: Vec::from([key().to_protobuf()]),
+            custom_fees: Vec::from([custom_fee().to_protobuf()]),
         };
 
         let tx = TopicCreateTransactionData::from_protobuf(tx).unwrap();
@@ -414,6 +508,9 @@ mod tests {
         assert_eq!(tx.submit_key, Some(key().into()));
         assert_eq!(tx.auto_renew_period, Some(AUTO_RENEW_PERIOD));
         assert_eq!(tx.auto_renew_account_id, Some(AUTO_RENEW_ACCOUNT_ID));
+        assert_eq!(tx.fee_schedule_key, Some(key().into()));
+        assert_eq!(tx.fee_exempt_keys, [Key::from(key())]);
+        assert_eq!(tx.custom_fees, [custom_fee()]);
     }
 
     #[test]
@@ -471,4 +568,46 @@ mod tests {
     fn get_set_auto_renew_account_id_frozen_panics() {
         make_transaction().auto_renew_account_id(AUTO_RENEW_ACCOUNT_ID);
     }
+
+    #[test]
+    fn get_set_fee_schedule_key() {
+        let mut tx = TopicCreateTransaction::new();
+        tx.fee_schedule_key(key());
+
+        assert_eq!(tx.get_fee_schedule_key(), Some(&key().into()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_set_fee_schedule_key_frozen_panics() {
+        make_transaction().fee_schedule_key(key());
+    }
+
+    #[test]
+    fn get_set_fee_exempt_keys() {
+        let mut tx = TopicCreateTransaction::new();
+        tx.fee_exempt_keys([key().into()]).add_fee_exempt_key(key());
+
+        assert_eq!(tx.get_fee_exempt_keys(), [Key::from(key()), Key::from(key())]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_set_fee_exempt_keys_frozen_panics() {
+        make_transaction().add_fee_exempt_key(key());
+    }
+
+    #[test]
+    fn get_set_custom_fees() {
+        let mut tx = TopicCreateTransaction::new();
+        tx.custom_fees([custom_fee()]).add_custom_fee(custom_fee());
+
+        assert_eq!(tx.get_custom_fees(), [custom_fee(), custom_fee()]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_set_custom_fees_frozen_panics() {
+        make_transaction().add_custom_fee(custom_fee());
+    }
 }
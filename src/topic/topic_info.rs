@@ -27,6 +27,7 @@ use time::{
 use crate::protobuf::ToProtobuf;
 use crate::{
     AccountId,
+    FixedFee,
     FromProtobuf,
     Key,
     LedgerId,
@@ -65,6 +66,15 @@ pub struct TopicInfo {
     /// The interval at which the auto-renew account will be charged to extend the topic's expiry.
     pub auto_renew_period: Option<Duration>,
 
+    /// Access control for updating the topic's custom fees.
+    pub fee_schedule_key: Option<Key>,
+
+    /// Keys that are exempt from paying the topic's custom fees.
+    pub fee_exempt_keys: Vec<Key>,
+
+    /// Fixed fees charged for each message submitted to the topic.
+    pub custom_fees: Vec<FixedFee>,
+
     /// The ledger ID the response was returned from
     pub ledger_id: LedgerId,
 }
@@ -108,6 +118,9 @@ impl FromProtobuf<services::ConsensusGetTopicInfoResponse> for TopicInfo {
         let expiration_time = info.expiration_time.map(Into::into);
         let auto_renew_period = info.auto_renew_period.map(Into::into);
         let auto_renew_account_id = Option::from_protobuf(info.auto_renew_account)?;
+        let fee_schedule_key = Option::from_protobuf(info.fee_schedule_key)?;
+        let fee_exempt_keys = Vec::from_protobuf(info.fee_exempt_key_list)?;
+        let custom_fees = Vec::from_protobuf(info.custom_fees)?;
         let ledger_id = LedgerId::from_bytes(info.ledger_id);
 
         Ok(Self {
@@ -120,6 +133,9 @@ impl FromProtobuf<services::ConsensusGetTopicInfoResponse> for TopicInfo {
             sequence_number: info.sequence_number,
             expiration_time,
             topic_memo: info.memo,
+            fee_schedule_key,
+            fee_exempt_keys,
+            custom_fees,
             ledger_id,
         })
     }
@@ -141,6 +157,9 @@ impl ToProtobuf for TopicInfo {
                 auto_renew_period: self.auto_renew_period.to_protobuf(),
                 auto_renew_account: self.auto_renew_account_id.to_protobuf(),
                 ledger_id: self.ledger_id.to_bytes(),
+                fee_schedule_key: self.fee_schedule_key.to_protobuf(),
+                fee_exempt_key_list: self.fee_exempt_keys.to_protobuf(),
+                custom_fees: self.custom_fees.to_protobuf(),
             }),
             header: None,
         }
@@ -181,6 +200,9 @@ mod tests {
                     account: Some(services::account_id::Account::AccountNum(4)),
                 }),
                 ledger_id: LedgerId::testnet().to_bytes(),
+                fee_schedule_key: None,
+                fee_exempt_key_list: Vec::new(),
+                custom_fees: Vec::new(),
             }),
         }
     }
@@ -217,6 +239,9 @@ mod tests {
                         nanoseconds: 0,
                     },
                 ),
+                fee_schedule_key: None,
+                fee_exempt_keys: [],
+                custom_fees: [],
                 ledger_id: "testnet",
             }
         "#]]
@@ -351,6 +376,9 @@ mod tests {
                         ledger_id: [
                             1,
                         ],
+                        fee_schedule_key: None,
+                        fee_exempt_key_list: [],
+                        custom_fees: [],
                     },
                 ),
             }
@@ -390,6 +418,9 @@ mod tests {
                         nanoseconds: 0,
                     },
                 ),
+                fee_schedule_key: None,
+                fee_exempt_keys: [],
+                custom_fees: [],
                 ledger_id: "testnet",
             }
         "#]]
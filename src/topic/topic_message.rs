@@ -18,8 +18,6 @@
  * ‍
  */
 
-use std::iter;
-
 use time::OffsetDateTime;
 
 use crate::TransactionId;
@@ -39,6 +37,21 @@ pub struct TopicMessageChunk {
 
     /// Sequence number for this chunk.
     pub sequence_number: u64,
+
+    /// The position of this chunk in the message, starting at `1`.
+    pub number: u32,
+}
+
+impl TopicMessageChunk {
+    fn from_pb(pb: &PbTopicMessageChunk) -> Self {
+        Self {
+            consensus_timestamp: pb.header.consensus_timestamp,
+            content_size: pb.header.message.len(),
+            running_hash: pb.header.running_hash.clone(),
+            sequence_number: pb.header.sequence_number,
+            number: u32::try_from(pb.number).unwrap_or_default(),
+        }
+    }
 }
 
 /// Topic message records.
@@ -120,26 +133,12 @@ impl TopicMessage {
             acc
         });
 
+        let chunks = pb.iter().map(TopicMessageChunk::from_pb).collect();
+
         let mut pb = pb;
 
         let last = pb.pop().unwrap();
 
-        let chunks = pb
-            .into_iter()
-            .map(|it| TopicMessageChunk {
-                consensus_timestamp: it.header.consensus_timestamp,
-                content_size: it.header.message.len(),
-                running_hash: it.header.running_hash,
-                sequence_number: it.header.sequence_number,
-            })
-            .chain(iter::once(TopicMessageChunk {
-                consensus_timestamp: last.header.consensus_timestamp,
-                content_size: last.header.message.len(),
-                running_hash: last.header.running_hash.clone(),
-                sequence_number: last.header.sequence_number,
-            }))
-            .collect();
-
         Self {
             consensus_timestamp: last.header.consensus_timestamp,
             contents,
@@ -152,6 +151,89 @@ impl TopicMessage {
     }
 }
 
+/// Why a chunked topic message couldn't be reassembled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum IncompleteTopicMessageReason {
+    /// The rest of the message wasn't received before the reassembly timeout.
+    Expired,
+
+    /// The message was given up on to make room for newer messages,
+    /// because too many messages were being reassembled.
+    Evicted,
+
+    /// A chunk was received more than once.
+    DuplicateChunk,
+}
+
+/// The chunks of a chunked topic message that couldn't be reassembled.
+///
+/// See [`ChunkReassemblyPolicy`](crate::ChunkReassemblyPolicy).
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct IncompleteTopicMessage {
+    /// The [`TransactionId`] of the first chunk of the message.
+    pub initial_transaction_id: TransactionId,
+
+    /// The number of chunks the message was supposed to have.
+    pub total: u32,
+
+    /// The contents of the received chunks, concatenated in order.
+    ///
+    /// Use the `number` and `content_size` of each chunk to find its part of the contents.
+    pub contents: Vec<u8>,
+
+    /// The received chunks, in order.
+    pub chunks: Vec<TopicMessageChunk>,
+
+    /// Why the message couldn't be reassembled.
+    pub reason: IncompleteTopicMessageReason,
+}
+
+impl IncompleteTopicMessage {
+    pub(crate) fn from_chunks(
+        pb: Vec<PbTopicMessageChunk>,
+        reason: IncompleteTopicMessageReason,
+    ) -> Self {
+        assert!(!pb.is_empty(), "no chunks provided to `IncompleteTopicMessage::from_chunks`");
+
+        let total = pb.iter().map(|it| it.total).min().unwrap();
+
+        Self {
+            initial_transaction_id: pb[0].initial_transaction_id,
+            total: u32::try_from(total).unwrap_or_default(),
+            contents: pb.iter().flat_map(|it| it.header.message.iter().copied()).collect(),
+            chunks: pb.iter().map(TopicMessageChunk::from_pb).collect(),
+            reason,
+        }
+    }
+}
+
+/// A message received by [`TopicMessageQuery::subscribe_items`](crate::TopicMessageQuery::subscribe_items).
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub enum TopicMessageItem {
+    /// A message, reassembled from all of its chunks if it was chunked.
+    Message(TopicMessage),
+
+    /// The chunks of a chunked message that couldn't be reassembled.
+    ///
+    /// Only yielded with [`ChunkReassemblyPolicy::YieldPartial`](crate::ChunkReassemblyPolicy::YieldPartial).
+    Incomplete(IncompleteTopicMessage),
+}
+
+impl TopicMessageItem {
+    /// Converts `self` for streams of [`TopicMessage`]s, which yield incomplete messages as errors.
+    pub(crate) fn into_message(self) -> crate::Result<TopicMessage> {
+        match self {
+            Self::Message(message) => Ok(message),
+            Self::Incomplete(message) => {
+                Err(crate::Error::IncompleteTopicMessage(Box::new(message)))
+            }
+        }
+    }
+}
+
 pub(crate) struct PbTopicMessageHeader {
     pub(crate) consensus_timestamp: OffsetDateTime,
     pub(crate) sequence_number: u64,
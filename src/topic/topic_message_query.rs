@@ -18,16 +18,15 @@
  * ‍
  */
 
-use std::collections::HashMap;
-use std::{
-    mem,
-    task,
-};
+use std::task;
 
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
-use futures_util::TryStreamExt;
+use futures_util::{
+    StreamExt,
+    TryStreamExt,
+};
 use hedera_proto::mirror;
 use hedera_proto::mirror::consensus_service_client::ConsensusServiceClient;
 use hedera_proto::mirror::ConsensusTopicQuery;
@@ -38,23 +37,28 @@ use time::{
 use tonic::transport::Channel;
 use tonic::Response;
 
-use super::topic_message::{
-    PbTopicMessageChunk,
-    PbTopicMessageHeader,
+use super::chunk_reassembly::{
+    ChunkReassembler,
+    ChunkReassemblyOptions,
 };
+use super::topic_running_hash::RunningHashVerifier;
 use crate::mirror_query::{
     AnyMirrorQueryData,
     AnyMirrorQueryMessage,
     MirrorRequest,
 };
-use crate::protobuf::FromProtobuf;
 use crate::{
     AnyMirrorQueryResponse,
+    ChunkReassemblyPolicy,
+    Client,
     MirrorQuery,
     ToProtobuf,
+    TopicCheckpointStore,
     TopicId,
     TopicMessage,
-    TransactionId,
+    TopicMessageItem,
+    TopicMessageSubscription,
+    TopicRunningHash,
 };
 
 // TODO: test, test, and test
@@ -85,14 +89,37 @@ pub struct TopicMessageQueryData {
 
     /// The maximum number of messages to receive before stopping.
     limit: u64,
+
+    /// The trusted running hash to verify messages from, if they're verified.
+    running_hash_checkpoint: Option<TopicRunningHash>,
+
+    /// How chunked messages are reassembled.
+    chunk_reassembly: ChunkReassemblyOptions,
 }
 
 impl TopicMessageQueryData {
-    fn map_stream<'a, S>(stream: S) -> impl Stream<Item = crate::Result<TopicMessage>>
+    fn map_stream<'a, S>(&self, stream: S) -> impl Stream<Item = crate::Result<TopicMessage>>
     where
         S: Stream<Item = crate::Result<mirror::ConsensusTopicResponse>> + Send + 'a,
     {
-        MessagesMapStream { inner: stream, incomplete_messages: HashMap::new() }
+        self.map_items(stream).map(|item| item.and_then(TopicMessageItem::into_message))
+    }
+
+    fn map_items<'a, S>(&self, stream: S) -> impl Stream<Item = crate::Result<TopicMessageItem>>
+    where
+        S: Stream<Item = crate::Result<mirror::ConsensusTopicResponse>> + Send + 'a,
+    {
+        // without a topic ID the mirror node fails the request before sending any messages to verify.
+        let verifier = self.running_hash_checkpoint.clone().map(|checkpoint| {
+            RunningHashVerifier::new(self.topic_id.unwrap_or(TopicId::new(0, 0, 0)), checkpoint)
+        });
+
+        MessagesMapStream {
+            inner: stream,
+            reassembler: ChunkReassembler::new(self.chunk_reassembly.clone()),
+            verifier,
+            failed: false,
+        }
     }
 }
 
@@ -146,6 +173,181 @@ impl TopicMessageQuery {
         self.data.limit = limit;
         self
     }
+
+    /// Returns the trusted running hash messages are verified from, if they're verified.
+    #[must_use]
+    pub fn get_running_hash_checkpoint(&self) -> Option<&TopicRunningHash> {
+        self.data.running_hash_checkpoint.as_ref()
+    }
+
+    /// Verify the running hash of every message, starting from the trusted running hash `checkpoint`.
+    ///
+    /// Each message must be the one after the previous (the first must be the one after `checkpoint`),
+    /// and its running hash must be the version 3 running hash of the previous running hash, the topic ID,
+    /// its sequence number, consensus timestamp, payer and contents.
+    /// Otherwise the stream fails with [`Error::TopicRunningHash`](crate::Error::TopicRunningHash) and ends.
+    ///
+    /// The [start time](Self::start_time) must be after the message of `checkpoint`, and no later than the message after it.
+    ///
+    /// Only messages submitted with chunk info can be verified, since the mirror node only reports their payer.
+    /// The payer of chunked messages is taken from their initial transaction ID.
+    pub fn verify_running_hashes(&mut self, checkpoint: TopicRunningHash) -> &mut Self {
+        self.data.running_hash_checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// Returns how long to wait for the rest of a chunked message after receiving its first chunk.
+    #[must_use]
+    pub fn get_chunk_reassembly_timeout(&self) -> std::time::Duration {
+        self.data.chunk_reassembly.timeout
+    }
+
+    /// Sets how long to wait for the rest of a chunked message after receiving its first chunk.
+    /// Defaults to 15 minutes.
+    ///
+    /// Messages that time out are handled according to the [incomplete message policy](Self::incomplete_message_policy).
+    /// Timeouts are handled as they happen, even while no messages are received.
+    pub fn chunk_reassembly_timeout(&mut self, timeout: std::time::Duration) -> &mut Self {
+        self.data.chunk_reassembly.timeout = timeout;
+        self
+    }
+
+    /// Returns the maximum number of partially received chunked messages to hold on to.
+    #[must_use]
+    pub fn get_max_incomplete_messages(&self) -> Option<usize> {
+        self.data.chunk_reassembly.max_incomplete_messages
+    }
+
+    /// Sets the maximum number of partially received chunked messages to hold on to.
+    /// Defaults to _unlimited_.
+    ///
+    /// When the first chunk of another message is received, the message that started first is given up on,
+    /// and handled according to the [incomplete message policy](Self::incomplete_message_policy).
+    pub fn max_incomplete_messages(&mut self, max: usize) -> &mut Self {
+        self.data.chunk_reassembly.max_incomplete_messages = Some(max);
+        self
+    }
+
+    /// Returns what to do with chunked messages that time out or are evicted.
+    #[must_use]
+    pub fn get_incomplete_message_policy(&self) -> ChunkReassemblyPolicy {
+        self.data.chunk_reassembly.on_incomplete
+    }
+
+    /// Sets what to do with chunked messages that time out or are evicted.
+    /// Defaults to [`ChunkReassemblyPolicy::Drop`].
+    pub fn incomplete_message_policy(&mut self, policy: ChunkReassemblyPolicy) -> &mut Self {
+        self.data.chunk_reassembly.on_incomplete = policy;
+        self
+    }
+
+    /// Returns what to do with chunks that are received more than once.
+    #[must_use]
+    pub fn get_duplicate_chunk_policy(&self) -> ChunkReassemblyPolicy {
+        self.data.chunk_reassembly.on_duplicate
+    }
+
+    /// Sets what to do with chunks that are received more than once.
+    /// Defaults to [`ChunkReassemblyPolicy::Drop`].
+    ///
+    /// Only the repeated chunk is handled according to `policy`, the message is still reassembled.
+    pub fn duplicate_chunk_policy(&mut self, policy: ChunkReassemblyPolicy) -> &mut Self {
+        self.data.chunk_reassembly.on_duplicate = policy;
+        self
+    }
+
+    /// Subscribe to this query, receiving chunked messages that couldn't be reassembled as well.
+    ///
+    /// With [`ChunkReassemblyPolicy::YieldPartial`], they're yielded as [`TopicMessageItem::Incomplete`],
+    /// instead of as the [`Error::IncompleteTopicMessage`](crate::Error::IncompleteTopicMessage)
+    /// that [`subscribe`](Self::subscribe) yields.
+    ///
+    /// # Panics
+    /// - If the topic ID isn't set.
+    pub fn subscribe_items<'a>(
+        &self,
+        client: &'a Client,
+    ) -> BoxStream<'a, crate::Result<TopicMessageItem>> {
+        assert!(self.data.topic_id.is_some(), "must set a topic ID to use `subscribe_items`");
+
+        let timeout = client.request_timeout().unwrap_or_else(|| {
+            std::time::Duration::from_millis(backoff::default::MAX_ELAPSED_TIME_MILLIS)
+        });
+
+        let channel = client.mirrornet().load().channel();
+
+        Box::pin(self.data.map_items(crate::mirror_query::subscribe(
+            channel,
+            timeout,
+            self.data.clone(),
+        )))
+    }
+
+    /// Subscribe to this query, resuming from the checkpoint of the topic in `store`.
+    ///
+    /// Without a stored checkpoint, the subscription starts at the [start time](Self::start_time).
+    /// Otherwise it starts after the last acknowledged message, receiving the chunks of chunked messages
+    /// that were only partially received again, and the start time is ignored.
+    ///
+    /// Each [acknowledged](TopicMessageSubscription::ack) message saves a checkpoint to `store`,
+    /// so messages are delivered at least once across restarts.
+    ///
+    /// When [verifying running hashes](Self::verify_running_hashes), verification continues from the stored checkpoint.
+    /// If the running hash before the chunks that are received again wasn't recorded, the first of them can't be verified,
+    /// but the ones after it must lead up to the stored checkpoint.
+    ///
+    /// Note that the [limit](Self::limit) applies to each subscription, counting chunks that are received again.
+    ///
+    /// # Errors
+    /// - Any error returned by the store's [`load`](TopicCheckpointStore::load).
+    ///
+    /// # Panics
+    /// - If the topic ID isn't set.
+    pub fn subscribe_durable<'a>(
+        &self,
+        client: &'a Client,
+        store: impl TopicCheckpointStore + 'a,
+    ) -> crate::Result<TopicMessageSubscription<'a>> {
+        let topic_id = self.data.topic_id.expect("must set a topic ID to use `subscribe_durable`");
+
+        let checkpoint = store.load(topic_id)?;
+
+        let mut data = self.data.clone();
+
+        if let Some(checkpoint) = &checkpoint {
+            data.start_time = Some(checkpoint.resume_start_time());
+        }
+
+        let verifier = data.running_hash_checkpoint.clone().map(|trusted| {
+            let Some(checkpoint) = &checkpoint else {
+                return RunningHashVerifier::new(topic_id, trusted);
+            };
+
+            match checkpoint.resume_running_hash() {
+                Some(previous) => RunningHashVerifier::new(topic_id, previous),
+                // nobody saw the running hash before the pending chunks, but they must lead up to the checkpoint.
+                None => RunningHashVerifier::until(topic_id, checkpoint.to_running_hash()),
+            }
+        });
+
+        let timeout = client.request_timeout().unwrap_or_else(|| {
+            std::time::Duration::from_millis(backoff::default::MAX_ELAPSED_TIME_MILLIS)
+        });
+
+        // note: we don't care about keeping the mirrornet around, so, we just take the channel (which is arc-like)
+        let channel = client.mirrornet().load().channel();
+
+        let chunk_reassembly = data.chunk_reassembly.clone();
+
+        Ok(TopicMessageSubscription::new(
+            topic_id,
+            Box::pin(crate::mirror_query::subscribe(channel, timeout, data)),
+            Box::new(store),
+            checkpoint,
+            verifier,
+            chunk_reassembly,
+        ))
+    }
 }
 
 impl From<TopicMessageQueryData> for AnyMirrorQueryData {
@@ -199,19 +401,19 @@ impl MirrorRequest for TopicMessageQueryData {
         })
     }
 
-    fn make_item_stream<'a, S>(stream: S) -> Self::ItemStream<'a>
+    fn make_item_stream<'a, S>(&self, stream: S) -> Self::ItemStream<'a>
     where
         S: Stream<Item = crate::Result<Self::GrpcItem>> + Send + 'a,
     {
-        Box::pin(Self::map_stream(stream))
+        Box::pin(self.map_stream(stream))
     }
 
-    fn try_collect<'a, S>(stream: S) -> BoxFuture<'a, crate::Result<Self::Response>>
+    fn try_collect<'a, S>(&self, stream: S) -> BoxFuture<'a, crate::Result<Self::Response>>
     where
         S: Stream<Item = crate::Result<Self::GrpcItem>> + Send + 'a,
     {
         // this doesn't reuse the work in `make_item_stream`
-        Box::pin(Self::map_stream(stream).try_collect())
+        Box::pin(self.map_stream(stream).try_collect())
     }
 
     fn update_context(context: &mut Self::Context, item: &Self::GrpcItem) {
@@ -232,30 +434,14 @@ impl From<Vec<TopicMessage>> for AnyMirrorQueryResponse {
     }
 }
 
-enum IncompleteMessage {
-    Partial(OffsetDateTime, Vec<PbTopicMessageChunk>),
-    Expired,
-    Complete,
-}
-
-impl IncompleteMessage {
-    fn handle_expiry(&mut self) -> &mut Self {
-        match self {
-            IncompleteMessage::Partial(expiry, _) if *expiry < OffsetDateTime::now_utc() => {
-                *self = Self::Expired;
-            }
-            _ => {}
-        }
-
-        self
-    }
-}
-
 pin_project_lite::pin_project! {
     struct MessagesMapStream<S> {
         #[pin]
         inner: S,
-        incomplete_messages: HashMap<TransactionId, IncompleteMessage>,
+        reassembler: ChunkReassembler,
+        verifier: Option<RunningHashVerifier>,
+        // set once verification fails, the stream can't be trusted after that.
+        failed: bool,
     }
 }
 
@@ -263,7 +449,7 @@ impl<S> Stream for MessagesMapStream<S>
 where
     S: Stream<Item = crate::Result<mirror::ConsensusTopicResponse>> + Send,
 {
-    type Item = crate::Result<TopicMessage>;
+    type Item = crate::Result<TopicMessageItem>;
 
     fn poll_next(
         self: std::pin::Pin<&mut Self>,
@@ -274,78 +460,34 @@ where
         let mut this = self.project();
 
         loop {
-            let item = match task::ready!(this.inner.as_mut().poll_next(cx)) {
-                Some(Ok(item)) => item,
-                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
-                None => return Poll::Ready(None),
-            };
-
-            match filter_map(item, this.incomplete_messages) {
-                Ok(Some(item)) => return Poll::Ready(Some(Ok(item))),
-                Ok(None) => {}
-                Err(e) => return Poll::Ready(Some(Err(e))),
+            if let Some(item) = this.reassembler.pop() {
+                return Poll::Ready(Some(item));
             }
-        }
-    }
-}
-
-fn filter_map(
-    mut item: mirror::ConsensusTopicResponse,
-    incomplete_messages: &mut HashMap<TransactionId, IncompleteMessage>,
-) -> crate::Result<Option<TopicMessage>> {
-    let header = PbTopicMessageHeader {
-        consensus_timestamp: pb_getf!(item, consensus_timestamp)?.into(),
-        sequence_number: item.sequence_number,
-        running_hash: item.running_hash,
-        running_hash_version: item.running_hash_version,
-        message: item.message,
-    };
-
-    let item = match item.chunk_info.take() {
-        Some(chunk_info) if chunk_info.total > 1 => PbTopicMessageChunk {
-            header,
-            initial_transaction_id: TransactionId::from_protobuf(pb_getf!(
-                chunk_info,
-                initial_transaction_id
-            )?)?,
-            number: chunk_info.number,
-            total: chunk_info.total,
-        },
-        _ => return Ok(Some(TopicMessage::from_single(header))),
-    };
-
-    let tx_id = item.initial_transaction_id;
-
-    let entry = incomplete_messages.entry(tx_id).or_insert_with(|| {
-        IncompleteMessage::Partial(
-            // todo: configurable?
-            OffsetDateTime::now_utc() + time::Duration::minutes(15),
-            Vec::new(),
-        )
-    });
 
-    let IncompleteMessage::Partial(_, messages) = entry.handle_expiry() else { return Ok(None) };
-
-    match messages.binary_search_by_key(&item.number, |it| it.number) {
-        // We have a duplicate `number`, so, we'll just ignore it (this is unspecified behavior)
-        Ok(_) => {}
-        Err(index) => messages.insert(index, item),
-    };
+            if *this.failed || this.reassembler.is_failed() {
+                return Poll::Ready(None);
+            }
 
-    // find the smallest `total` so that we aren't susceptable to stuff like total changing (and getting bigger)
-    // later on there's a check that ensures that they all have the same total.
-    let total = messages.iter().map(|it| it.total).min().unwrap();
+            let item = match this.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(item))) => item,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                // messages can time out while we wait for the next chunk.
+                Poll::Pending => {
+                    task::ready!(this.reassembler.poll_expire(cx));
+                    continue;
+                }
+            };
 
-    // note: because of the way we handle `total`, `total` can get *smaller*.
+            if let Some(verifier) = this.verifier.as_mut() {
+                if let Err(e) = verifier.verify(&item) {
+                    *this.failed = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
 
-    match messages.len() >= total as usize {
-        true => {
-            let messages = mem::take(messages);
-            *entry = IncompleteMessage::Complete;
-            Ok(Some(TopicMessage::from_chunks(messages)))
+            this.reassembler.push(item);
         }
-
-        false => Ok(None),
     }
 }
 
@@ -354,8 +496,10 @@ mod tests {
     use time::OffsetDateTime;
 
     use crate::{
+        ChunkReassemblyPolicy,
         TopicId,
         TopicMessageQuery,
+        TopicRunningHash,
     };
 
     #[test]
@@ -390,4 +534,27 @@ mod tests {
 
         assert_eq!(query.get_limit(), 1415);
     }
+
+    #[test]
+    fn get_set_running_hash_checkpoint() {
+        let mut query = TopicMessageQuery::new();
+        query.verify_running_hashes(TopicRunningHash::topic_creation());
+
+        assert_eq!(query.get_running_hash_checkpoint(), Some(&TopicRunningHash::topic_creation()));
+    }
+
+    #[test]
+    fn get_set_chunk_reassembly() {
+        let mut query = TopicMessageQuery::new();
+        query
+            .chunk_reassembly_timeout(std::time::Duration::from_secs(60))
+            .max_incomplete_messages(16)
+            .incomplete_message_policy(ChunkReassemblyPolicy::YieldPartial)
+            .duplicate_chunk_policy(ChunkReassemblyPolicy::Error);
+
+        assert_eq!(query.get_chunk_reassembly_timeout(), std::time::Duration::from_secs(60));
+        assert_eq!(query.get_max_incomplete_messages(), Some(16));
+        assert_eq!(query.get_incomplete_message_policy(), ChunkReassemblyPolicy::YieldPartial);
+        assert_eq!(query.get_duplicate_chunk_policy(), ChunkReassemblyPolicy::Error);
+    }
 }
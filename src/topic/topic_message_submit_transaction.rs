@@ -43,6 +43,7 @@ use crate::transaction::{
 };
 use crate::{
     BoxGrpcFuture,
+    CustomFeeLimit,
     Error,
     TopicId,
     Transaction,
@@ -92,6 +93,30 @@ impl TopicMessageSubmitTransaction {
         self.data_mut().chunk_data_mut().data = bytes.into();
         self
     }
+
+    /// Returns the maximum custom fees the payers are willing to pay for submitting this message.
+    #[must_use]
+    pub fn get_custom_fee_limits(&self) -> &[CustomFeeLimit] {
+        &self.body().custom_fee_limits
+    }
+
+    /// Sets the maximum custom fees the payers are willing to pay for submitting this message.
+    ///
+    /// If the topic charges more than this (or charges in a denomination without a limit), the transaction fails.
+    /// Without any limits, all of the topic's custom fees are accepted.
+    pub fn custom_fee_limits(
+        &mut self,
+        limits: impl IntoIterator<Item = CustomFeeLimit>,
+    ) -> &mut Self {
+        self.body_mut().custom_fee_limits = limits.into_iter().collect();
+        self
+    }
+
+    /// Adds a limit for the custom fees a payer is willing to pay for submitting this message.
+    pub fn add_custom_fee_limit(&mut self, limit: CustomFeeLimit) -> &mut Self {
+        self.body_mut().custom_fee_limits.push(limit);
+        self
+    }
 }
 
 impl TransactionData for TopicMessageSubmitTransactionData {
@@ -228,7 +253,11 @@ mod tests {
         transaction_bodies,
     };
     use crate::{
+        AccountId,
         AnyTransaction,
+        CustomFeeLimit,
+        FixedFeeData,
+        Hbar,
         TopicId,
         TopicMessageSubmitTransaction,
     };
@@ -355,4 +384,41 @@ mod tests {
         let mut tx = make_transaction();
         tx.message(MESSAGE);
     }
+
+    fn custom_fee_limit() -> CustomFeeLimit {
+        CustomFeeLimit {
+            account_id: Some(AccountId::new(0, 0, 5006)),
+            fees: Vec::from([FixedFeeData::from_hbar(Hbar::new(1))]),
+        }
+    }
+
+    #[test]
+    fn to_from_bytes_custom_fee_limits() {
+        let mut tx = TopicMessageSubmitTransaction::new_for_tests();
+        tx.topic_id(TOPIC_ID).message(MESSAGE).add_custom_fee_limit(custom_fee_limit());
+        tx.freeze().unwrap();
+
+        let tx2 = AnyTransaction::from_bytes(&tx.to_bytes().unwrap()).unwrap();
+
+        let tx = transaction_bodies(tx);
+        let tx2 = transaction_bodies(tx2);
+
+        assert_eq!(tx[0].max_custom_fees.len(), 1);
+        assert_eq!(tx, tx2);
+    }
+
+    #[test]
+    fn get_set_custom_fee_limits() {
+        let mut tx = TopicMessageSubmitTransaction::new();
+        tx.custom_fee_limits([custom_fee_limit()]);
+
+        assert_eq!(tx.get_custom_fee_limits(), [custom_fee_limit()]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_set_custom_fee_limits_frozen_panics() {
+        let mut tx = make_transaction();
+        tx.add_custom_fee_limit(custom_fee_limit());
+    }
 }
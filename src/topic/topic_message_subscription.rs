@@ -0,0 +1,261 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::collections::{
+    HashMap,
+    VecDeque,
+};
+use std::pin::Pin;
+use std::task;
+
+use futures_core::stream::BoxStream;
+use futures_core::Stream;
+use futures_util::StreamExt;
+use hedera_proto::mirror;
+
+use super::chunk_reassembly::{
+    ChunkReassembler,
+    ChunkReassemblyOptions,
+};
+use super::topic_checkpoint::PendingChunkedMessage;
+use super::topic_running_hash::RunningHashVerifier;
+use crate::protobuf::FromProtobuf;
+use crate::{
+    Error,
+    TopicCheckpoint,
+    TopicCheckpointStore,
+    TopicId,
+    TopicMessage,
+    TopicMessageItem,
+    TopicRunningHash,
+    TransactionId,
+};
+
+/// A subscription to a topic that resumes from its last acknowledged message.
+///
+/// Messages are delivered at least once: a message that isn't [acknowledged](Self::ack)
+/// before the process stops is delivered again by the next subscription using the same store.
+///
+/// The subscription keeps a checkpoint for every delivered message until it, or a later message, is acknowledged.
+/// Acknowledge messages regularly (acknowledging only the latest of a batch is enough),
+/// a subscription whose messages are never acknowledged keeps growing.
+///
+/// See [`TopicMessageQuery::subscribe_durable`](crate::TopicMessageQuery::subscribe_durable).
+pub struct TopicMessageSubscription<'a> {
+    topic_id: TopicId,
+    inner: BoxStream<'a, crate::Result<mirror::ConsensusTopicResponse>>,
+    store: Box<dyn TopicCheckpointStore + 'a>,
+
+    /// The checkpoint this subscription resumed from, used to skip messages that were already acknowledged.
+    resumed_from: Option<TopicCheckpoint>,
+
+    /// The sequence number of the last acknowledged message.
+    acknowledged: Option<u64>,
+
+    verifier: Option<RunningHashVerifier>,
+
+    /// The running hash of the topic after the last chunk received, if it's known.
+    last_received: Option<TopicRunningHash>,
+
+    reassembler: ChunkReassembler,
+
+    /// Where each partially received chunked message started.
+    pending: HashMap<TransactionId, PendingChunkedMessage>,
+
+    /// The checkpoint after each delivered message that hasn't been acknowledged yet, in delivery order.
+    unacknowledged: VecDeque<TopicCheckpoint>,
+
+    // set once running hash verification fails, the stream can't be trusted after that.
+    failed: bool,
+}
+
+impl<'a> TopicMessageSubscription<'a> {
+    pub(crate) fn new(
+        topic_id: TopicId,
+        inner: BoxStream<'a, crate::Result<mirror::ConsensusTopicResponse>>,
+        store: Box<dyn TopicCheckpointStore + 'a>,
+        resumed_from: Option<TopicCheckpoint>,
+        verifier: Option<RunningHashVerifier>,
+        chunk_reassembly: ChunkReassemblyOptions,
+    ) -> Self {
+        Self {
+            topic_id,
+            inner,
+            store,
+            acknowledged: resumed_from.as_ref().map(TopicCheckpoint::sequence_number),
+            last_received: verifier.as_ref().and_then(RunningHashVerifier::last).cloned(),
+            verifier,
+            resumed_from,
+            reassembler: ChunkReassembler::new(chunk_reassembly),
+            pending: HashMap::new(),
+            unacknowledged: VecDeque::new(),
+            failed: false,
+        }
+    }
+
+    /// Returns the ID of the topic this subscription receives messages from.
+    #[must_use]
+    pub fn topic_id(&self) -> TopicId {
+        self.topic_id
+    }
+
+    /// Acknowledge that `message`, and every message delivered before it, has been fully processed.
+    ///
+    /// This saves a checkpoint to the store, so that a later subscription resumes after `message`.
+    /// Acknowledging a message that was already acknowledged does nothing.
+    ///
+    /// # Errors
+    /// - [`Error::TopicMessageNotDelivered`] if `message` wasn't delivered by this subscription.
+    /// - Any error returned by the store's [`save`](TopicCheckpointStore::save).
+    pub fn ack(&mut self, message: &TopicMessage) -> crate::Result<()> {
+        let sequence_number = message.sequence_number;
+
+        if self.acknowledged.is_some_and(|it| sequence_number <= it) {
+            return Ok(());
+        }
+
+        let index = self
+            .unacknowledged
+            .iter()
+            .position(|it| it.sequence_number() == sequence_number)
+            .ok_or(Error::TopicMessageNotDelivered { topic_id: self.topic_id, sequence_number })?;
+
+        // only forget the checkpoints once the store has the new one, so that a failed save can be retried.
+        self.store.save(self.topic_id, &self.unacknowledged[index])?;
+
+        self.unacknowledged.drain(..=index);
+        self.acknowledged = Some(sequence_number);
+
+        Ok(())
+    }
+
+    /// Returns the checkpoint after `message`, with the chunked messages that are still being received.
+    fn checkpoint_after(&mut self, message: &TopicMessage) -> TopicCheckpoint {
+        let reassembler = &self.reassembler;
+
+        // messages that were given up on will never be delivered, so they mustn't hold back where we resume.
+        self.pending.retain(|transaction_id, _| reassembler.is_in_flight(transaction_id));
+
+        TopicCheckpoint::new(
+            message.sequence_number,
+            message.consensus_timestamp,
+            message.running_hash.clone(),
+            self.pending.values().cloned().collect(),
+        )
+    }
+
+    fn handle_item(&mut self, item: mirror::ConsensusTopicResponse) -> crate::Result<()> {
+        if let Some(verifier) = &mut self.verifier {
+            if let Err(e) = verifier.verify(&item) {
+                self.failed = true;
+                return Err(e);
+            }
+        }
+
+        let previous = self
+            .last_received
+            .replace(TopicRunningHash::new(item.sequence_number, item.running_hash.clone()));
+
+        let chunked_message_id = match &item.chunk_info {
+            Some(chunk_info) if chunk_info.total > 1 => Some(TransactionId::from_protobuf(
+                pb_getf!(chunk_info.clone(), initial_transaction_id)?,
+            )?),
+            _ => None,
+        };
+
+        if let Some(resumed_from) = &self.resumed_from {
+            // everything up to the checkpoint was acknowledged, except the chunks of messages that were pending.
+            let pending = chunked_message_id.as_ref().is_some_and(|it| resumed_from.is_pending(it));
+
+            if item.sequence_number <= resumed_from.sequence_number() && !pending {
+                return Ok(());
+            }
+        }
+
+        if let Some(initial_transaction_id) = chunked_message_id {
+            let consensus_timestamp = pb_getf!(item, consensus_timestamp)?.into();
+
+            self.pending.entry(initial_transaction_id).or_insert_with(|| PendingChunkedMessage {
+                initial_transaction_id,
+                consensus_timestamp,
+                previous,
+            });
+        }
+
+        self.reassembler.push(item);
+
+        Ok(())
+    }
+
+    /// Returns the next message (or error) that's ready to be yielded.
+    fn pop_ready(&mut self) -> Option<crate::Result<TopicMessage>> {
+        let message = match self.reassembler.pop()?.and_then(TopicMessageItem::into_message) {
+            Ok(message) => message,
+            Err(e) => return Some(Err(e)),
+        };
+
+        if let Some(initial_transaction_id) = &message.transaction {
+            self.pending.remove(initial_transaction_id);
+        }
+
+        let checkpoint = self.checkpoint_after(&message);
+        self.unacknowledged.push_back(checkpoint);
+
+        Some(Ok(message))
+    }
+}
+
+impl Stream for TopicMessageSubscription<'_> {
+    type Item = crate::Result<TopicMessage>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Option<Self::Item>> {
+        use task::Poll;
+
+        let this = self.get_mut();
+
+        loop {
+            if let Some(item) = this.pop_ready() {
+                return Poll::Ready(Some(item));
+            }
+
+            if this.failed || this.reassembler.is_failed() {
+                return Poll::Ready(None);
+            }
+
+            let item = match this.inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(item))) => item,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                // messages can time out while we wait for the next chunk.
+                Poll::Pending => {
+                    task::ready!(this.reassembler.poll_expire(cx));
+                    continue;
+                }
+            };
+
+            if let Err(e) = this.handle_item(item) {
+                return Poll::Ready(Some(Err(e)));
+            }
+        }
+    }
+}
@@ -0,0 +1,426 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::collections::VecDeque;
+use std::mem;
+use std::pin::Pin;
+use std::task::{
+    self,
+    Waker,
+};
+
+use futures_core::stream::BoxStream;
+use futures_core::Stream;
+use futures_util::StreamExt;
+use parking_lot::Mutex;
+use time::OffsetDateTime;
+use triomphe::Arc;
+
+use crate::{
+    Client,
+    TopicId,
+    TopicMessage,
+    TopicMessageQuery,
+};
+
+enum Command {
+    Add(TopicMessageQuery),
+    Remove(TopicId),
+}
+
+type Subscribe<'a> =
+    Box<dyn Fn(&TopicMessageQuery) -> BoxStream<'a, crate::Result<TopicMessage>> + Send + 'a>;
+
+#[derive(Default)]
+struct Commands {
+    pending: Vec<Command>,
+    waker: Option<Waker>,
+}
+
+/// Subscribes to many topics at once, merging their messages into a single stream.
+///
+/// Each topic has its own mirror node stream, which reconnects independently,
+/// and each item of the merged stream is tagged with the topic it came from.
+/// Streams are only read from when the merged stream is polled, so a slow consumer applies
+/// backpressure to every topic instead of messages being buffered without limit.
+/// Each poll starts with the stream after the one that yielded last, so a busy topic can't starve the others.
+///
+/// Topics can be added and removed at any time, from any task, with a [`TopicMultiplexerHandle`].
+/// A topic is removed once its stream ends, either because of its end time or limit,
+/// or after yielding an error it can't recover from.
+///
+/// The merged stream ends once there are no topics left and no handles to add more.
+pub struct TopicMultiplexer<'a> {
+    subscribe: Subscribe<'a>,
+    commands: Arc<Mutex<Commands>>,
+    open: Vec<(TopicId, BoxStream<'a, crate::Result<TopicMessage>>)>,
+    queued: VecDeque<TopicMessageQuery>,
+    max_open_streams: Option<usize>,
+
+    /// The index of the stream to poll first, so that busy topics can't starve the others.
+    next: usize,
+}
+
+impl<'a> TopicMultiplexer<'a> {
+    /// Create a new `TopicMultiplexer` without any topics, which subscribes with `client`.
+    #[must_use]
+    pub fn new(client: &'a Client) -> Self {
+        Self::with_subscribe(Box::new(move |query| query.subscribe(client)))
+    }
+
+    fn with_subscribe(subscribe: Subscribe<'a>) -> Self {
+        Self {
+            subscribe,
+            commands: Arc::default(),
+            open: Vec::new(),
+            queued: VecDeque::new(),
+            max_open_streams: None,
+            next: 0,
+        }
+    }
+
+    /// Returns the maximum number of mirror node streams to have open at once.
+    #[must_use]
+    pub fn get_max_open_streams(&self) -> Option<usize> {
+        self.max_open_streams
+    }
+
+    /// Sets the maximum number of mirror node streams to have open at once.
+    /// Defaults to _unlimited_.
+    ///
+    /// Topics past the maximum wait, in the order they were added, until another topic's stream ends or it's removed.
+    /// Topics without an end time or limit never end on their own,
+    /// so the topics queued behind them wait until one of them is removed.
+    pub fn max_open_streams(&mut self, max: usize) -> &mut Self {
+        self.max_open_streams = Some(max);
+        self
+    }
+
+    /// Returns a handle that adds and removes topics of this multiplexer.
+    #[must_use]
+    pub fn handle(&self) -> TopicMultiplexerHandle {
+        TopicMultiplexerHandle { commands: Arc::clone(&self.commands) }
+    }
+
+    /// Subscribe to `topic_id`, starting at `start_time`.
+    ///
+    /// See [`TopicMultiplexerHandle::add_topic`].
+    pub fn add_topic(
+        &mut self,
+        topic_id: impl Into<TopicId>,
+        start_time: Option<OffsetDateTime>,
+    ) -> &mut Self {
+        self.handle().add_topic(topic_id, start_time);
+        self
+    }
+
+    /// Subscribe to the topic of `query`.
+    ///
+    /// See [`TopicMultiplexerHandle::add_query`].
+    ///
+    /// # Panics
+    /// - If the topic ID of `query` isn't set.
+    pub fn add_query(&mut self, query: TopicMessageQuery) -> &mut Self {
+        self.handle().add_query(query);
+        self
+    }
+
+    /// Unsubscribe from `topic_id`.
+    ///
+    /// See [`TopicMultiplexerHandle::remove_topic`].
+    pub fn remove_topic(&mut self, topic_id: impl Into<TopicId>) -> &mut Self {
+        self.handle().remove_topic(topic_id);
+        self
+    }
+
+    fn apply_commands(&mut self, waker: &Waker) {
+        let pending = {
+            let mut commands = self.commands.lock();
+            commands.waker = Some(waker.clone());
+            mem::take(&mut commands.pending)
+        };
+
+        for command in pending {
+            match command {
+                Command::Add(query) => {
+                    // `add_query` ensures that the topic ID is set.
+                    let topic_id = query.get_topic_id().unwrap();
+
+                    self.remove(topic_id);
+                    self.queued.push_back(query);
+                }
+
+                Command::Remove(topic_id) => self.remove(topic_id),
+            }
+        }
+
+        while self.max_open_streams.map_or(true, |max| self.open.len() < max) {
+            let Some(query) = self.queued.pop_front() else { break };
+
+            // `add_query` ensures that the topic ID is set.
+            let topic_id = query.get_topic_id().unwrap();
+
+            self.open.push((topic_id, (self.subscribe)(&query)));
+        }
+    }
+
+    fn remove(&mut self, topic_id: TopicId) {
+        self.open.retain(|(it, _)| *it != topic_id);
+        self.queued.retain(|it| it.get_topic_id() != Some(topic_id));
+    }
+}
+
+impl Stream for TopicMultiplexer<'_> {
+    type Item = (TopicId, crate::Result<TopicMessage>);
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Option<Self::Item>> {
+        use task::Poll;
+
+        let this = self.get_mut();
+
+        'streams: loop {
+            this.apply_commands(cx.waker());
+
+            if this.open.is_empty() {
+                // nobody else can add a topic, so there will never be another message.
+                if this.queued.is_empty() && Arc::count(&this.commands) == 1 {
+                    return Poll::Ready(None);
+                }
+
+                return Poll::Pending;
+            }
+
+            let len = this.open.len();
+
+            for offset in 0..len {
+                let index = (this.next + offset) % len;
+                let (topic_id, stream) = &mut this.open[index];
+
+                match stream.poll_next_unpin(cx) {
+                    Poll::Ready(Some(item)) => {
+                        let topic_id = *topic_id;
+                        this.next = index + 1;
+                        return Poll::Ready(Some((topic_id, item)));
+                    }
+
+                    Poll::Ready(None) => {
+                        // the stream ended, so make room for a queued topic and start over.
+                        this.open.remove(index);
+                        this.next = index;
+                        continue 'streams;
+                    }
+
+                    Poll::Pending => {}
+                }
+            }
+
+            return Poll::Pending;
+        }
+    }
+}
+
+/// Adds and removes the topics of a [`TopicMultiplexer`], from any task.
+///
+/// Changes are applied the next time the multiplexer is polled.
+#[derive(Clone)]
+pub struct TopicMultiplexerHandle {
+    commands: Arc<Mutex<Commands>>,
+}
+
+impl TopicMultiplexerHandle {
+    /// Subscribe to `topic_id`, starting at `start_time`.
+    ///
+    /// Without a start time, the topic starts at the time it's added, even if it has to wait for a stream.
+    /// Adding a topic that's already subscribed to replaces its subscription.
+    pub fn add_topic(&self, topic_id: impl Into<TopicId>, start_time: Option<OffsetDateTime>) {
+        let mut query = TopicMessageQuery::new();
+
+        query.topic_id(topic_id).start_time(start_time.unwrap_or_else(OffsetDateTime::now_utc));
+
+        self.send(Command::Add(query));
+    }
+
+    /// Subscribe to the topic of `query`, with all of its options.
+    ///
+    /// Without a start time, the topic starts at the time it's added, even if it has to wait for a stream.
+    /// Adding a topic that's already subscribed to replaces its subscription.
+    ///
+    /// # Panics
+    /// - If the topic ID of `query` isn't set.
+    pub fn add_query(&self, mut query: TopicMessageQuery) {
+        assert!(
+            query.get_topic_id().is_some(),
+            "must set a topic ID to add a `TopicMessageQuery` to a `TopicMultiplexer`"
+        );
+
+        if query.get_start_time().is_none() {
+            query.start_time(OffsetDateTime::now_utc());
+        }
+
+        self.send(Command::Add(query));
+    }
+
+    /// Unsubscribe from `topic_id`, closing its stream.
+    ///
+    /// Does nothing if the topic isn't subscribed to.
+    pub fn remove_topic(&self, topic_id: impl Into<TopicId>) {
+        self.send(Command::Remove(topic_id.into()));
+    }
+
+    fn send(&self, command: Command) {
+        let waker = {
+            let mut commands = self.commands.lock();
+            commands.pending.push(command);
+            commands.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl Drop for TopicMultiplexerHandle {
+    fn drop(&mut self) {
+        // the multiplexer might be waiting for this handle to add a topic.
+        let waker = self.commands.lock().waker.take();
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_core::stream::BoxStream;
+    use futures_util::{
+        stream,
+        FutureExt,
+        StreamExt,
+    };
+    use time::OffsetDateTime;
+
+    use super::TopicMultiplexer;
+    use crate::topic::topic_message::PbTopicMessageHeader;
+    use crate::{
+        TopicId,
+        TopicMessage,
+        TopicMessageQuery,
+    };
+
+    /// Topic `0.0.1` yields two messages and ends, every other topic yields messages forever.
+    fn subscribe(query: &TopicMessageQuery) -> BoxStream<'static, crate::Result<TopicMessage>> {
+        let topic_id = query.get_topic_id().unwrap();
+
+        let message = move |sequence_number| {
+            Ok(TopicMessage::from_single(PbTopicMessageHeader {
+                consensus_timestamp: OffsetDateTime::UNIX_EPOCH,
+                sequence_number,
+                running_hash: Vec::new(),
+                running_hash_version: 3,
+                message: topic_id.num.to_be_bytes().to_vec(),
+            }))
+        };
+
+        if topic_id.num == 1 {
+            stream::iter([message(1), message(2)]).boxed()
+        } else {
+            stream::iter(1..).map(message).boxed()
+        }
+    }
+
+    fn next_topic(multiplexer: &mut TopicMultiplexer<'_>) -> Option<Option<TopicId>> {
+        multiplexer.next().now_or_never().map(|it| it.map(|(topic_id, _)| topic_id))
+    }
+
+    #[test]
+    fn add_remove() {
+        let mut multiplexer = TopicMultiplexer::with_subscribe(Box::new(subscribe));
+        let handle = multiplexer.handle();
+
+        assert_eq!(next_topic(&mut multiplexer), None);
+
+        handle.add_topic(TopicId::new(0, 0, 2), None);
+        handle.add_topic(TopicId::new(0, 0, 3), None);
+
+        // streams take turns.
+        assert_eq!(next_topic(&mut multiplexer), Some(Some(TopicId::new(0, 0, 2))));
+        assert_eq!(next_topic(&mut multiplexer), Some(Some(TopicId::new(0, 0, 3))));
+        assert_eq!(next_topic(&mut multiplexer), Some(Some(TopicId::new(0, 0, 2))));
+
+        handle.remove_topic(TopicId::new(0, 0, 2));
+
+        assert_eq!(next_topic(&mut multiplexer), Some(Some(TopicId::new(0, 0, 3))));
+        assert_eq!(next_topic(&mut multiplexer), Some(Some(TopicId::new(0, 0, 3))));
+
+        handle.remove_topic(TopicId::new(0, 0, 3));
+
+        assert_eq!(next_topic(&mut multiplexer), None);
+
+        drop(handle);
+
+        assert_eq!(next_topic(&mut multiplexer), Some(None));
+    }
+
+    #[test]
+    fn queues_past_max_open_streams() {
+        let mut multiplexer = TopicMultiplexer::with_subscribe(Box::new(subscribe));
+
+        multiplexer
+            .max_open_streams(1)
+            .add_topic(TopicId::new(0, 0, 2), None)
+            .add_topic(TopicId::new(0, 0, 3), None);
+
+        for _ in 0..3 {
+            assert_eq!(next_topic(&mut multiplexer), Some(Some(TopicId::new(0, 0, 2))));
+        }
+
+        assert_eq!(multiplexer.open.len(), 1);
+        assert_eq!(multiplexer.queued.len(), 1);
+
+        multiplexer.remove_topic(TopicId::new(0, 0, 2));
+
+        assert_eq!(next_topic(&mut multiplexer), Some(Some(TopicId::new(0, 0, 3))));
+        assert!(multiplexer.queued.is_empty());
+    }
+
+    #[test]
+    fn stream_end_opens_queued_topic() {
+        let mut multiplexer = TopicMultiplexer::with_subscribe(Box::new(subscribe));
+
+        multiplexer
+            .max_open_streams(1)
+            .add_topic(TopicId::new(0, 0, 1), None)
+            .add_topic(TopicId::new(0, 0, 2), None);
+
+        assert_eq!(next_topic(&mut multiplexer), Some(Some(TopicId::new(0, 0, 1))));
+        assert_eq!(next_topic(&mut multiplexer), Some(Some(TopicId::new(0, 0, 1))));
+        assert_eq!(next_topic(&mut multiplexer), Some(Some(TopicId::new(0, 0, 2))));
+
+        multiplexer.remove_topic(TopicId::new(0, 0, 2));
+
+        // no topics are left, and there are no handles to add more.
+        assert_eq!(next_topic(&mut multiplexer), Some(None));
+    }
+}
@@ -0,0 +1,443 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use hedera_proto::{
+    mirror,
+    services,
+};
+use sha2::{
+    Digest,
+    Sha384,
+};
+
+use crate::{
+    Error,
+    TopicId,
+    TopicMessage,
+    TopicRunningHashError,
+};
+
+/// The only running hash version that can be verified.
+const RUNNING_HASH_VERSION: u64 = 3;
+
+/// The running hash of a topic after a given message, used as a trusted starting point to verify later messages.
+///
+/// See [`TopicMessageQuery::verify_running_hashes`](crate::TopicMessageQuery::verify_running_hashes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicRunningHash {
+    /// The sequence number of the message, `0` before the topic's first message.
+    pub sequence_number: u64,
+
+    /// The running hash of the topic after the message.
+    pub running_hash: Vec<u8>,
+}
+
+impl TopicRunningHash {
+    /// Create a `TopicRunningHash` for the topic's state after message `sequence_number`.
+    #[must_use]
+    pub fn new(sequence_number: u64, running_hash: Vec<u8>) -> Self {
+        Self { sequence_number, running_hash }
+    }
+
+    /// Returns the running hash of a topic without any messages, to verify a topic from its first message.
+    #[must_use]
+    pub fn topic_creation() -> Self {
+        Self { sequence_number: 0, running_hash: vec![0; 48] }
+    }
+}
+
+impl From<&TopicMessage> for TopicRunningHash {
+    fn from(message: &TopicMessage) -> Self {
+        Self::new(message.sequence_number, message.running_hash.clone())
+    }
+}
+
+/// The fields a version 3 running hash covers, besides the previous running hash.
+struct RunningHashInput<'a> {
+    payer: [i64; 3],
+    topic: [i64; 3],
+    consensus_seconds: i64,
+    consensus_nanos: i32,
+    sequence_number: u64,
+    message: &'a [u8],
+}
+
+impl RunningHashInput<'_> {
+    /// `TC_ARRAY`, then the `TC_CLASSDESC` of `byte[]`, the way a stream writes its first `byte[]`.
+    const FIRST_BYTE_ARRAY: [u8; 19] = [
+        0x75, 0x72, 0x00, 0x02, b'[', b'B', 0xac, 0xf3, 0x17, 0xf8, 0x06, 0x08, 0x54, 0xe0, 0x02,
+        0x00, 0x00, 0x78, 0x70,
+    ];
+
+    /// `TC_ARRAY`, then a `TC_REFERENCE` to the class descriptor written with the first `byte[]`.
+    const NEXT_BYTE_ARRAY: [u8; 6] = [0x75, 0x71, 0x00, 0x7e, 0x00, 0x00];
+
+    // The network hashes the output of a Java `ObjectOutputStream`, which writes the previous running hash
+    // and the message hash as `byte[]` objects, and the fields in between as a single block of data.
+    fn next_running_hash(&self, previous: &[u8]) -> Vec<u8> {
+        let mut data = Vec::with_capacity(76);
+
+        data.extend_from_slice(&RUNNING_HASH_VERSION.to_be_bytes());
+
+        for it in self.payer.iter().chain(&self.topic) {
+            data.extend_from_slice(&it.to_be_bytes());
+        }
+
+        data.extend_from_slice(&self.consensus_seconds.to_be_bytes());
+        data.extend_from_slice(&self.consensus_nanos.to_be_bytes());
+        data.extend_from_slice(&self.sequence_number.to_be_bytes());
+
+        let message_hash = Sha384::digest(self.message);
+
+        let mut hasher = Sha384::new();
+
+        // STREAM_MAGIC, STREAM_VERSION
+        hasher.update([0xac, 0xed, 0x00, 0x05]);
+
+        hasher.update(Self::FIRST_BYTE_ARRAY);
+        hasher.update((previous.len() as u32).to_be_bytes());
+        hasher.update(previous);
+
+        // TC_BLOCKDATA
+        hasher.update([0x77, data.len() as u8]);
+        hasher.update(&data);
+
+        hasher.update(Self::NEXT_BYTE_ARRAY);
+        hasher.update((message_hash.len() as u32).to_be_bytes());
+        hasher.update(message_hash);
+
+        hasher.finalize().to_vec()
+    }
+}
+
+/// Verifies the running hash of each message of a topic's stream, starting from a trusted running hash.
+pub(crate) struct RunningHashVerifier {
+    topic_id: TopicId,
+    // `None` until the first message, if the running hash before it isn't known.
+    last: Option<TopicRunningHash>,
+    // a trusted running hash the stream must pass through.
+    until: Option<TopicRunningHash>,
+}
+
+impl RunningHashVerifier {
+    pub(crate) fn new(topic_id: TopicId, checkpoint: TopicRunningHash) -> Self {
+        Self { topic_id, last: Some(checkpoint), until: None }
+    }
+
+    /// Creates a verifier for a stream that starts at an unknown running hash, before the trusted `checkpoint`.
+    ///
+    /// The first message can't be verified, but every message after it is chained to it,
+    /// and the stream must reach `checkpoint` exactly.
+    pub(crate) fn until(topic_id: TopicId, checkpoint: TopicRunningHash) -> Self {
+        Self { topic_id, last: None, until: Some(checkpoint) }
+    }
+
+    /// Returns the running hash of the last verified message.
+    pub(crate) fn last(&self) -> Option<&TopicRunningHash> {
+        self.last.as_ref()
+    }
+
+    fn error(&self, reason: TopicRunningHashError) -> Error {
+        Error::TopicRunningHash { topic_id: self.topic_id, reason }
+    }
+
+    /// Verifies `item` (a single chunk, for chunked messages) follows the last verified message.
+    pub(crate) fn verify(&mut self, item: &mirror::ConsensusTopicResponse) -> crate::Result<()> {
+        let sequence_number = item.sequence_number;
+
+        if let Some(until) = &self.until {
+            if sequence_number > until.sequence_number {
+                return Err(self.error(TopicRunningHashError::SequenceGap {
+                    expected: self
+                        .last
+                        .as_ref()
+                        .map_or(until.sequence_number, |it| it.sequence_number + 1),
+                    received: sequence_number,
+                }));
+            }
+        }
+
+        let Some(last) = &self.last else {
+            return self.accept(item);
+        };
+
+        let expected = last.sequence_number + 1;
+
+        if sequence_number != expected {
+            return Err(self.error(TopicRunningHashError::SequenceGap {
+                expected,
+                received: sequence_number,
+            }));
+        }
+
+        if item.running_hash_version != RUNNING_HASH_VERSION {
+            return Err(self.error(TopicRunningHashError::UnsupportedVersion {
+                sequence_number,
+                version: item.running_hash_version,
+            }));
+        }
+
+        let payer = item
+            .chunk_info
+            .as_ref()
+            .and_then(|it| it.initial_transaction_id.as_ref())
+            .and_then(|it| it.account_id.as_ref())
+            .and_then(|it| match it.account {
+                Some(services::account_id::Account::AccountNum(num)) => {
+                    Some([it.shard_num, it.realm_num, num])
+                }
+                _ => None,
+            })
+            .ok_or_else(|| self.error(TopicRunningHashError::MissingPayer { sequence_number }))?;
+
+        let consensus_timestamp = item
+            .consensus_timestamp
+            .as_ref()
+            .ok_or_else(|| Error::from_protobuf("unexpected missing `consensus_timestamp`"))?;
+
+        let input = RunningHashInput {
+            payer,
+            topic: [
+                self.topic_id.shard as i64,
+                self.topic_id.realm as i64,
+                self.topic_id.num as i64,
+            ],
+            consensus_seconds: consensus_timestamp.seconds,
+            consensus_nanos: consensus_timestamp.nanos,
+            sequence_number,
+            message: &item.message,
+        };
+
+        if input.next_running_hash(&last.running_hash) != item.running_hash {
+            return Err(self.error(TopicRunningHashError::Mismatch { sequence_number }));
+        }
+
+        self.accept(item)
+    }
+
+    fn accept(&mut self, item: &mirror::ConsensusTopicResponse) -> crate::Result<()> {
+        let sequence_number = item.sequence_number;
+
+        if let Some(until) = &self.until {
+            if sequence_number == until.sequence_number {
+                if item.running_hash != until.running_hash {
+                    return Err(self.error(TopicRunningHashError::Mismatch { sequence_number }));
+                }
+
+                self.until = None;
+            }
+        }
+
+        self.last = Some(TopicRunningHash::new(sequence_number, item.running_hash.clone()));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use hedera_proto::{
+        mirror,
+        services,
+    };
+    use hex_literal::hex;
+
+    use super::{
+        RunningHashInput,
+        RunningHashVerifier,
+    };
+    use crate::{
+        Error,
+        TopicId,
+        TopicRunningHash,
+        TopicRunningHashError,
+    };
+
+    const TOPIC_ID: TopicId = TopicId::new(0, 0, 5005);
+
+    // the running hashes of "hello", "world" and "!" sent to 0.0.5005 by 0.0.1001 at 1_700_000_000 seconds
+    // and 1, 2 and 3 nanos, computed with Java's `ObjectOutputStream` the way consensus nodes do.
+    const HELLO: [u8; 48] = hex!(
+        "f94b4ac1a53ef266926c3dbc4d8f2f72522dfaca507f42594f17254aac4d46c1cee1f2d96170cab0b2f677027d793856"
+    );
+    const WORLD: [u8; 48] = hex!(
+        "b847fe164d94457455bc5449cacdbe52fa295e1d6b04991c5570241082691c29bf1bc23c7f3ed71a91364e76916e3068"
+    );
+    const BANG: [u8; 48] = hex!(
+        "5255373d0a59020d76126a8f7eb03517887bdc5a7c66ee633aa585b70e70fe85eab5a6b87c6f2adaf4473999c6aa9d5f"
+    );
+
+    fn make_response(
+        sequence_number: u64,
+        message: &[u8],
+        running_hash: &[u8],
+    ) -> mirror::ConsensusTopicResponse {
+        mirror::ConsensusTopicResponse {
+            consensus_timestamp: Some(services::Timestamp {
+                seconds: 1_700_000_000,
+                nanos: sequence_number as i32,
+            }),
+            message: message.to_vec(),
+            running_hash: running_hash.to_vec(),
+            sequence_number,
+            running_hash_version: 3,
+            chunk_info: Some(services::ConsensusMessageChunkInfo {
+                initial_transaction_id: Some(services::TransactionId {
+                    account_id: Some(services::AccountId {
+                        shard_num: 0,
+                        realm_num: 0,
+                        account: Some(services::account_id::Account::AccountNum(1001)),
+                    }),
+                    ..Default::default()
+                }),
+                total: 1,
+                number: 1,
+            }),
+        }
+    }
+
+    #[test]
+    fn next_running_hash() {
+        let input = RunningHashInput {
+            payer: [0, 0, 1001],
+            topic: [0, 0, 5005],
+            consensus_seconds: 1_700_000_000,
+            consensus_nanos: 2,
+            sequence_number: 2,
+            message: b"world",
+        };
+
+        assert_eq!(input.next_running_hash(&HELLO), WORLD);
+    }
+
+    #[test]
+    fn verifies_chain() {
+        let first = make_response(1, b"hello", &HELLO);
+        let second = make_response(2, b"world", &WORLD);
+
+        let mut verifier = RunningHashVerifier::new(TOPIC_ID, TopicRunningHash::topic_creation());
+
+        verifier.verify(&first).unwrap();
+        verifier.verify(&second).unwrap();
+
+        assert_eq!(verifier.last, Some(TopicRunningHash::new(2, WORLD.to_vec())));
+    }
+
+    #[test]
+    fn tampered_message() {
+        let first = make_response(1, b"jello", &HELLO);
+
+        let mut verifier = RunningHashVerifier::new(TOPIC_ID, TopicRunningHash::topic_creation());
+
+        assert_matches!(
+            verifier.verify(&first),
+            Err(Error::TopicRunningHash {
+                reason: TopicRunningHashError::Mismatch { sequence_number: 1 },
+                ..
+            })
+        );
+    }
+
+    #[test]
+    fn sequence_gap() {
+        let first = make_response(1, b"hello", &HELLO);
+        let third = make_response(3, b"!", &BANG);
+
+        let mut verifier = RunningHashVerifier::new(TOPIC_ID, TopicRunningHash::topic_creation());
+
+        verifier.verify(&first).unwrap();
+
+        assert_matches!(
+            verifier.verify(&third),
+            Err(Error::TopicRunningHash {
+                reason: TopicRunningHashError::SequenceGap { expected: 2, received: 3 },
+                ..
+            })
+        );
+    }
+
+    #[test]
+    fn missing_payer() {
+        let mut first = make_response(1, b"hello", &HELLO);
+        first.chunk_info = None;
+
+        let mut verifier = RunningHashVerifier::new(TOPIC_ID, TopicRunningHash::topic_creation());
+
+        assert_matches!(
+            verifier.verify(&first),
+            Err(Error::TopicRunningHash {
+                reason: TopicRunningHashError::MissingPayer { sequence_number: 1 },
+                ..
+            })
+        );
+    }
+
+    #[test]
+    fn verifies_until_checkpoint() {
+        let first = make_response(1, b"hello", &HELLO);
+        let second = make_response(2, b"world", &WORLD);
+        let third = make_response(3, b"!", &BANG);
+
+        let mut verifier =
+            RunningHashVerifier::until(TOPIC_ID, TopicRunningHash::new(2, WORLD.to_vec()));
+
+        verifier.verify(&first).unwrap();
+        verifier.verify(&second).unwrap();
+        verifier.verify(&third).unwrap();
+
+        assert_eq!(verifier.last, Some(TopicRunningHash::new(3, BANG.to_vec())));
+    }
+
+    #[test]
+    fn until_checkpoint_mismatch() {
+        let first = make_response(1, b"hello", &HELLO);
+        let second = make_response(2, b"world", &WORLD);
+
+        let mut verifier =
+            RunningHashVerifier::until(TOPIC_ID, TopicRunningHash::new(2, vec![0xcd; 48]));
+
+        verifier.verify(&first).unwrap();
+
+        assert_matches!(
+            verifier.verify(&second),
+            Err(Error::TopicRunningHash {
+                reason: TopicRunningHashError::Mismatch { sequence_number: 2 },
+                ..
+            })
+        );
+    }
+
+    #[test]
+    fn until_checkpoint_skipped() {
+        let third = make_response(3, b"!", &BANG);
+
+        let mut verifier =
+            RunningHashVerifier::until(TOPIC_ID, TopicRunningHash::new(2, vec![0xcd; 48]));
+
+        assert_matches!(
+            verifier.verify(&third),
+            Err(Error::TopicRunningHash {
+                reason: TopicRunningHashError::SequenceGap { expected: 2, received: 3 },
+                ..
+            })
+        );
+    }
+}
@@ -43,6 +43,7 @@ use crate::{
     AccountId,
     BoxGrpcFuture,
     Error,
+    FixedFee,
     Key,
     TopicId,
     Transaction,
@@ -79,6 +80,15 @@ pub struct TopicUpdateTransactionData {
 
     /// Optional account to be used at the topic's expiration time to extend the life of the topic.
     auto_renew_account_id: Option<AccountId>,
+
+    /// Access control for updating the topic's custom fees.
+    fee_schedule_key: Option<Key>,
+
+    /// Keys that are exempt from paying the topic's custom fees.
+    fee_exempt_keys: Option<Vec<Key>>,
+
+    /// Fixed fees charged for each message submitted to the topic.
+    custom_fees: Option<Vec<FixedFee>>,
 }
 
 impl TopicUpdateTransaction {
@@ -192,6 +202,78 @@ impl TopicUpdateTransaction {
             checksum: None,
         })
     }
+
+    /// Returns the access control for updating the topic's custom fees.
+    #[must_use]
+    pub fn get_fee_schedule_key(&self) -> Option<&Key> {
+        self.data().fee_schedule_key.as_ref()
+    }
+
+    /// Sets the access control for updating the topic's custom fees.
+    pub fn fee_schedule_key(&mut self, key: impl Into<Key>) -> &mut Self {
+        self.data_mut().fee_schedule_key = Some(key.into());
+        self
+    }
+
+    /// Clears the access control for updating the topic's custom fees.
+    pub fn clear_fee_schedule_key(&mut self) -> &mut Self {
+        self.data_mut().fee_schedule_key = Some(Key::KeyList(crate::KeyList::new()));
+        self
+    }
+
+    /// Returns the keys that are exempt from paying the topic's custom fees.
+    #[must_use]
+    pub fn get_fee_exempt_keys(&self) -> Option<&[Key]> {
+        self.data().fee_exempt_keys.as_deref()
+    }
+
+    /// Sets the keys that are exempt from paying the topic's custom fees, replacing the existing ones.
+    pub fn fee_exempt_keys(&mut self, keys: impl IntoIterator<Item = Key>) -> &mut Self {
+        self.data_mut().fee_exempt_keys = Some(keys.into_iter().collect());
+        self
+    }
+
+    /// Adds a key that is exempt from paying the topic's custom fees.
+    ///
+    /// Note that the update replaces the topic's fee exempt keys, so every key that should stay exempt must be included.
+    pub fn add_fee_exempt_key(&mut self, key: impl Into<Key>) -> &mut Self {
+        self.data_mut().fee_exempt_keys.get_or_insert_with(Vec::new).push(key.into());
+        self
+    }
+
+    /// Removes all of the topic's fee exempt keys.
+    pub fn clear_fee_exempt_keys(&mut self) -> &mut Self {
+        self.data_mut().fee_exempt_keys = Some(Vec::new());
+        self
+    }
+
+    /// Returns the fixed fees charged for each message submitted to the topic.
+    #[must_use]
+    pub fn get_custom_fees(&self) -> Option<&[FixedFee]> {
+        self.data().custom_fees.as_deref()
+    }
+
+    /// Sets the fixed fees charged for each message submitted to the topic, replacing the existing ones.
+    ///
+    /// Changing the custom fees requires a signature from the topic's fee schedule key.
+    pub fn custom_fees(&mut self, fees: impl IntoIterator<Item = FixedFee>) -> &mut Self {
+        self.data_mut().custom_fees = Some(fees.into_iter().collect());
+        self
+    }
+
+    /// Adds a fixed fee charged for each message submitted to the topic.
+    ///
+    /// Note that the update replaces the topic's custom fees, so every fee that should be kept must be included.
+    pub fn add_custom_fee(&mut self, fee: FixedFee) -> &mut Self {
+        self.data_mut().custom_fees.get_or_insert_with(Vec::new).push(fee);
+        self
+    }
+
+    /// Removes all of the topic's custom fees.
+    pub fn clear_custom_fees(&mut self) -> &mut Self {
+        self.data_mut().custom_fees = Some(Vec::new());
+        self
+    }
 }
 
 impl TransactionData for TopicUpdateTransactionData {}
@@ -209,6 +291,12 @@ impl TransactionExecute for TopicUpdateTransactionData {
 impl ValidateChecksums for TopicUpdateTransactionData {
     fn validate_checksums(&self, ledger_id: &RefLedgerId) -> Result<(), Error> {
         self.topic_id.validate_checksums(ledger_id)?;
+
+        for fee in self.custom_fees.iter().flatten() {
+            fee.fee_collector_account_id.validate_checksums(ledger_id)?;
+            fee.fee.denominating_token_id.validate_checksums(ledger_id)?;
+        }
+
         self.auto_renew_account_id.validate_checksums(ledger_id)
     }
 }
@@ -248,6 +336,12 @@ impl FromProtobuf<services::ConsensusUpdateTopicTransactionBody> for TopicUpdate
             submit_key: Option::from_protobuf(pb.submit_key)?,
             auto_renew_period: pb.auto_renew_period.map(Into::into),
             auto_renew_account_id: Option::from_protobuf(pb.auto_renew_account)?,
+            fee_schedule_key: Option::from_protobuf(pb.fee_schedule_key)?,
+            fee_exempt_keys: pb
+                .fee_exempt_key_list
+                .map(|it| Vec::from_protobuf(it.keys))
+                .transpose()?,
+            custom_fees: pb.custom_fees.map(|it| Vec::from_protobuf(it.fees)).transpose()?,
         })
     }
 }
@@ -262,6 +356,15 @@ impl ToProtobuf for TopicUpdateTransactionData {
         let submit_key = self.submit_key.to_protobuf();
         let auto_renew_period = self.auto_renew_period.map(Into::into);
         let auto_renew_account_id = self.auto_renew_account_id.to_protobuf();
+        let fee_schedule_key = self.fee_schedule_key.to_protobuf();
+        let fee_exempt_key_list = self
+            .fee_exempt_keys
+            .as_ref()
+            .map(|keys| services::FeeExemptKeyList { keys: keys.to_protobuf() });
+        let custom_fees = self
+            .custom_fees
+            .as_ref()
+            .map(|fees| services::FixedCustomFeeList { fees: fees.to_protobuf() });
 
         services::ConsensusUpdateTopicTransactionBody {
             auto_renew_account: auto_renew_account_id,
@@ -271,6 +374,9 @@ impl ToProtobuf for TopicUpdateTransactionData {
             admin_key,
             submit_key,
             auto_renew_period,
+            fee_schedule_key,
+            fee_exempt_key_list,
+            custom_fees,
         }
     }
 }
@@ -278,6 +384,7 @@ impl ToProtobuf for TopicUpdateTransactionData {
 #[cfg(test)]
 mod tests {
     use expect_test::expect;
+    use hedera_proto::services;
     use time::Duration;
 
     use crate::transaction::test_helpers::{
@@ -287,11 +394,24 @@ mod tests {
         VALID_START,
     };
     use crate::{
+        AccountId,
         AnyTransaction,
+        FixedFee,
+        FixedFeeData,
+        Hbar,
+        Key,
         TopicId,
         TopicUpdateTransaction,
     };
 
+    fn custom_fee() -> FixedFee {
+        FixedFee {
+            fee: FixedFeeData::from_hbar(Hbar::new(1)),
+            fee_collector_account_id: Some(AccountId::new(0, 0, 5008)),
+            all_collectors_are_exempt: false,
+        }
+    }
+
     fn make_transaction() -> TopicUpdateTransaction {
         let mut tx = TopicUpdateTransaction::new_for_tests();
 
@@ -362,6 +482,9 @@ mod tests {
                             ),
                         },
                     ),
+                    fee_schedule_key: None,
+                    fee_exempt_key_list: None,
+                    custom_fees: None,
                 },
             )
         "#]]
@@ -524,6 +647,9 @@ mod tests {
                             ),
                         },
                     ),
+                    fee_schedule_key: None,
+                    fee_exempt_key_list: None,
+                    custom_fees: None,
                 },
             )
         "#]]
@@ -542,4 +668,79 @@ mod tests {
 
         assert_eq!(tx, tx2);
     }
+
+    #[test]
+    fn serialize_clear_custom_fees() {
+        let mut tx = TopicUpdateTransaction::new_for_tests();
+
+        tx.topic_id("0.0.5007".parse::<TopicId>().unwrap())
+            .clear_fee_exempt_keys()
+            .clear_custom_fees()
+            .freeze()
+            .unwrap();
+
+        let services::transaction_body::Data::ConsensusUpdateTopic(body) =
+            check_body(transaction_body(tx))
+        else {
+            panic!("expected a `ConsensusUpdateTopic` body");
+        };
+
+        // an empty list removes every key or fee, as opposed to a missing list, which leaves them unchanged.
+        assert_eq!(body.fee_exempt_key_list, Some(services::FeeExemptKeyList { keys: Vec::new() }));
+        assert_eq!(body.custom_fees, Some(services::FixedCustomFeeList { fees: Vec::new() }));
+    }
+
+    #[test]
+    fn get_set_fee_schedule_key() {
+        let mut tx = TopicUpdateTransaction::new();
+        tx.fee_schedule_key(unused_private_key().public_key());
+
+        assert_eq!(tx.get_fee_schedule_key(), Some(&unused_private_key().public_key().into()));
+    }
+
+    #[test]
+    fn clear_fee_schedule_key() {
+        let mut tx = TopicUpdateTransaction::new();
+        tx.clear_fee_schedule_key();
+
+        assert_eq!(tx.get_fee_schedule_key(), Some(&Key::KeyList(crate::KeyList::new())));
+    }
+
+    #[test]
+    fn get_set_fee_exempt_keys() {
+        let key: Key = unused_private_key().public_key().into();
+
+        let mut tx = TopicUpdateTransaction::new();
+        assert_eq!(tx.get_fee_exempt_keys(), None);
+
+        tx.fee_exempt_keys([key.clone()]).add_fee_exempt_key(key.clone());
+        assert_eq!(tx.get_fee_exempt_keys(), Some(&[key.clone(), key][..]));
+
+        tx.clear_fee_exempt_keys();
+        assert_eq!(tx.get_fee_exempt_keys(), Some(&[][..]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_set_fee_exempt_keys_frozen_panics() {
+        make_transaction().add_fee_exempt_key(unused_private_key().public_key());
+    }
+
+    #[test]
+    fn get_set_custom_fees() {
+        let mut tx = TopicUpdateTransaction::new();
+        assert_eq!(tx.get_custom_fees(), None);
+
+        tx.custom_fees([custom_fee()]).add_custom_fee(custom_fee());
+        assert_eq!(tx.get_custom_fees(), Some(&[custom_fee(), custom_fee()][..]));
+
+        tx.clear_custom_fees();
+        assert_eq!(tx.get_custom_fees(), Some(&[][..]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_set_custom_fees_frozen_panics() {
+        make_transaction().add_custom_fee(custom_fee());
+    }
 }
@@ -56,6 +56,7 @@ mod data {
         NodeDeleteTransactionData as NodeDelete,
         NodeUpdateTransactionData as NodeUpdate,
     };
+    pub(super) use crate::batch_transaction::BatchTransactionData as Batch;
     pub(super) use crate::contract::{
         ContractCreateTransactionData as ContractCreate,
         ContractDeleteTransactionData as ContractDelete,
@@ -166,6 +167,7 @@ pub enum AnyTransactionData {
     TokenAirdrop(data::TokenAirdrop),
     TokenClaimAirdrop(data::TokenClaimAirdrop),
     TokenCancelAirdrop(data::TokenCancelAirdrop),
+    Batch(data::Batch),
 }
 
 impl ToTransactionDataProtobuf for AnyTransactionData {
@@ -316,11 +318,20 @@ impl ToTransactionDataProtobuf for AnyTransactionData {
             Self::TokenCancelAirdrop(transaction) => {
                 transaction.to_transaction_data_protobuf(chunk_info)
             }
+            Self::Batch(transaction) => transaction.to_transaction_data_protobuf(chunk_info),
         }
     }
 }
 
 impl TransactionData for AnyTransactionData {
+    fn validate_encoding(&self) -> crate::Result<()> {
+        match self {
+            Self::NodeCreate(transaction) => transaction.validate_encoding(),
+            Self::NodeUpdate(transaction) => transaction.validate_encoding(),
+            _ => Ok(()),
+        }
+    }
+
     fn default_max_transaction_fee(&self) -> Hbar {
         match self {
             Self::Transfer(transaction) => transaction.default_max_transaction_fee(),
@@ -372,6 +383,7 @@ impl TransactionData for AnyTransactionData {
             Self::TokenAirdrop(transaction) => transaction.default_max_transaction_fee(),
             Self::TokenClaimAirdrop(transaction) => transaction.default_max_transaction_fee(),
             Self::TokenCancelAirdrop(transaction) => transaction.default_max_transaction_fee(),
+            Self::Batch(transaction) => transaction.default_max_transaction_fee(),
         }
     }
 
@@ -426,6 +438,7 @@ impl TransactionData for AnyTransactionData {
             Self::TokenAirdrop(it) => it.maybe_chunk_data(),
             Self::TokenClaimAirdrop(it) => it.maybe_chunk_data(),
             Self::TokenCancelAirdrop(it) => it.maybe_chunk_data(),
+            Self::Batch(it) => it.maybe_chunk_data(),
         }
     }
 
@@ -480,6 +493,62 @@ impl TransactionData for AnyTransactionData {
             Self::TokenAirdrop(it) => it.wait_for_receipt(),
             Self::TokenClaimAirdrop(it) => it.wait_for_receipt(),
             Self::TokenCancelAirdrop(it) => it.wait_for_receipt(),
+            Self::Batch(it) => it.wait_for_receipt(),
+        }
+    }
+
+    fn inner_transaction_ids(&self) -> Vec<TransactionId> {
+        match self {
+            Self::AccountCreate(it) => it.inner_transaction_ids(),
+            Self::AccountUpdate(it) => it.inner_transaction_ids(),
+            Self::AccountDelete(it) => it.inner_transaction_ids(),
+            Self::AccountAllowanceApprove(it) => it.inner_transaction_ids(),
+            Self::AccountAllowanceDelete(it) => it.inner_transaction_ids(),
+            Self::ContractCreate(it) => it.inner_transaction_ids(),
+            Self::ContractUpdate(it) => it.inner_transaction_ids(),
+            Self::ContractDelete(it) => it.inner_transaction_ids(),
+            Self::ContractExecute(it) => it.inner_transaction_ids(),
+            Self::Transfer(it) => it.inner_transaction_ids(),
+            Self::TopicCreate(it) => it.inner_transaction_ids(),
+            Self::TopicUpdate(it) => it.inner_transaction_ids(),
+            Self::TopicDelete(it) => it.inner_transaction_ids(),
+            Self::TopicMessageSubmit(it) => it.inner_transaction_ids(),
+            Self::FileAppend(it) => it.inner_transaction_ids(),
+            Self::FileCreate(it) => it.inner_transaction_ids(),
+            Self::FileUpdate(it) => it.inner_transaction_ids(),
+            Self::FileDelete(it) => it.inner_transaction_ids(),
+            Self::Prng(it) => it.inner_transaction_ids(),
+            Self::TokenAssociate(it) => it.inner_transaction_ids(),
+            Self::TokenBurn(it) => it.inner_transaction_ids(),
+            Self::TokenCreate(it) => it.inner_transaction_ids(),
+            Self::TokenDelete(it) => it.inner_transaction_ids(),
+            Self::TokenDissociate(it) => it.inner_transaction_ids(),
+            Self::TokenFeeScheduleUpdate(it) => it.inner_transaction_ids(),
+            Self::TokenFreeze(it) => it.inner_transaction_ids(),
+            Self::TokenGrantKyc(it) => it.inner_transaction_ids(),
+            Self::TokenMint(it) => it.inner_transaction_ids(),
+            Self::TokenPause(it) => it.inner_transaction_ids(),
+            Self::TokenRevokeKyc(it) => it.inner_transaction_ids(),
+            Self::TokenUnfreeze(it) => it.inner_transaction_ids(),
+            Self::TokenUnpause(it) => it.inner_transaction_ids(),
+            Self::TokenUpdate(it) => it.inner_transaction_ids(),
+            Self::TokenWipe(it) => it.inner_transaction_ids(),
+            Self::SystemDelete(it) => it.inner_transaction_ids(),
+            Self::SystemUndelete(it) => it.inner_transaction_ids(),
+            Self::Freeze(it) => it.inner_transaction_ids(),
+            Self::ScheduleCreate(it) => it.inner_transaction_ids(),
+            Self::ScheduleSign(it) => it.inner_transaction_ids(),
+            Self::ScheduleDelete(it) => it.inner_transaction_ids(),
+            Self::Ethereum(it) => it.inner_transaction_ids(),
+            Self::TokenUpdateNfts(it) => it.inner_transaction_ids(),
+            Self::NodeCreate(it) => it.inner_transaction_ids(),
+            Self::NodeUpdate(it) => it.inner_transaction_ids(),
+            Self::NodeDelete(it) => it.inner_transaction_ids(),
+            Self::TokenReject(it) => it.inner_transaction_ids(),
+            Self::TokenAirdrop(it) => it.inner_transaction_ids(),
+            Self::TokenClaimAirdrop(it) => it.inner_transaction_ids(),
+            Self::TokenCancelAirdrop(it) => it.inner_transaction_ids(),
+            Self::Batch(it) => it.inner_transaction_ids(),
         }
     }
 }
@@ -540,6 +609,7 @@ impl TransactionExecute for AnyTransactionData {
             Self::TokenAirdrop(transaction) => transaction.execute(channel, request),
             Self::TokenClaimAirdrop(transaction) => transaction.execute(channel, request),
             Self::TokenCancelAirdrop(transaction) => transaction.execute(channel, request),
+            Self::Batch(transaction) => transaction.execute(channel, request),
         }
     }
 }
@@ -598,6 +668,7 @@ impl ValidateChecksums for AnyTransactionData {
             Self::TokenAirdrop(transaction) => transaction.validate_checksums(ledger_id),
             Self::TokenClaimAirdrop(transaction) => transaction.validate_checksums(ledger_id),
             Self::TokenCancelAirdrop(transaction) => transaction.validate_checksums(ledger_id),
+            Self::Batch(transaction) => transaction.validate_checksums(ledger_id),
         }
     }
 }
@@ -664,6 +735,7 @@ impl FromProtobuf<services::transaction_body::Data> for AnyTransactionData {
             Data::TokenAirdrop(pb) => data::TokenAirdrop::from_protobuf(pb)?.into(),
             Data::TokenClaimAirdrop(pb) => data::TokenClaimAirdrop::from_protobuf(pb)?.into(),
             Data::TokenCancelAirdrop(pb) => data::TokenCancelAirdrop::from_protobuf(pb)?.into(),
+            Data::AtomicBatch(pb) => data::Batch::from_protobuf(pb)?.into(),
             Data::CryptoAddLiveHash(_) => {
                 return Err(Error::from_protobuf(
                     "unsupported transaction `AddLiveHashTransaction`",
@@ -849,6 +921,9 @@ impl AnyTransactionData {
             ServicesTransactionDataList::TokenCancelAirdrop(v) => {
                 data::TokenCancelAirdrop::from_protobuf(try_into_only_element(v)?)?.into()
             }
+            ServicesTransactionDataList::AtomicBatch(v) => {
+                data::Batch::from_protobuf(try_into_only_element(v)?)?.into()
+            }
         };
 
         Ok(data)
@@ -868,6 +943,7 @@ impl AnyTransaction {
                 node_account_ids: None,
                 transaction_valid_duration: first_body.transaction_valid_duration.map(Into::into),
                 max_transaction_fee: Some(Hbar::from_tinybars(first_body.transaction_fee as i64)),
+                max_transaction_fee_usd_cents: None,
                 transaction_memo: first_body.memo,
                 transaction_id: Some(TransactionId::from_protobuf(pb_getf!(
                     first_body,
@@ -876,6 +952,8 @@ impl AnyTransaction {
                 operator: None,
                 is_frozen: true,
                 regenerate_transaction_id: Some(false),
+                batch_key: Option::from_protobuf(first_body.batch_key)?,
+                custom_fee_limits: Vec::from_protobuf(first_body.max_custom_fees)?,
             },
             signers: Vec::new(),
             sources: None,
@@ -935,6 +1013,7 @@ enum ServicesTransactionDataList {
     TokenAirdrop(Vec<services::TokenAirdropTransactionBody>),
     TokenClaimAirdrop(Vec<services::TokenClaimAirdropTransactionBody>),
     TokenCancelAirdrop(Vec<services::TokenCancelAirdropTransactionBody>),
+    AtomicBatch(Vec<services::AtomicBatchTransactionBody>),
 }
 
 impl FromProtobuf<Vec<services::transaction_body::Data>> for ServicesTransactionDataList {
@@ -1006,6 +1085,7 @@ impl FromProtobuf<Vec<services::transaction_body::Data>> for ServicesTransaction
             Data::TokenAirdrop(it) => Self::TokenAirdrop(make_vec(it, len)),
             Data::TokenClaimAirdrop(it) => Self::TokenClaimAirdrop(make_vec(it, len)),
             Data::TokenCancelAirdrop(it) => Self::TokenCancelAirdrop(make_vec(it, len)),
+            Data::AtomicBatch(it) => Self::AtomicBatch(make_vec(it, len)),
             Data::CryptoAddLiveHash(_) => {
                 return Err(Error::from_protobuf(
                     "unsupported transaction `AddLiveHashTransaction`",
@@ -1083,6 +1163,7 @@ impl FromProtobuf<Vec<services::transaction_body::Data>> for ServicesTransaction
                 (Self::TokenAirdrop(v), Data::TokenAirdrop(element)) => v.push(element),
                 (Self::TokenClaimAirdrop(v), Data::TokenClaimAirdrop(element)) => v.push(element),
                 (Self::TokenCancelAirdrop(v), Data::TokenCancelAirdrop(element)) => v.push(element),
+                (Self::AtomicBatch(v), Data::AtomicBatch(element)) => v.push(element),
 
                 _ => return Err(Error::from_protobuf("mismatched transaction types")),
             }
@@ -1130,11 +1211,16 @@ macro_rules! impl_cast_any {
                             node_account_ids: transaction.body.node_account_ids,
                             transaction_valid_duration: transaction.body.transaction_valid_duration,
                             max_transaction_fee: transaction.body.max_transaction_fee,
+                            max_transaction_fee_usd_cents: transaction
+                                .body
+                                .max_transaction_fee_usd_cents,
                             transaction_memo: transaction.body.transaction_memo,
                             transaction_id: transaction.body.transaction_id,
                             operator: transaction.body.operator,
                             is_frozen: transaction.body.is_frozen,
                             regenerate_transaction_id: transaction.body.regenerate_transaction_id,
+                            batch_key: transaction.body.batch_key,
+                            custom_fee_limits: transaction.body.custom_fee_limits,
                         },
                         signers: transaction.signers,
                         sources: transaction.sources,
@@ -1206,5 +1292,6 @@ impl_cast_any! {
     TokenReject,
     TokenAirdrop,
     TokenClaimAirdrop,
-    TokenCancelAirdrop
+    TokenCancelAirdrop,
+    Batch
 }
@@ -193,6 +193,7 @@ where
             transaction_id: *transaction_id.unwrap(),
             transaction_hash: context,
             validate_status: true,
+            inner_transaction_ids: Vec::new(),
         })
     }
 
@@ -297,6 +298,7 @@ where
             transaction_id: *transaction_id.unwrap(),
             transaction_hash: context,
             validate_status: true,
+            inner_transaction_ids: Vec::new(),
         })
     }
 
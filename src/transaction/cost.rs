@@ -32,11 +32,14 @@ impl<D: Clone> CostTransaction<D> {
                 node_account_ids: transaction.body.node_account_ids,
                 transaction_valid_duration: transaction.body.transaction_valid_duration,
                 max_transaction_fee: transaction.body.max_transaction_fee,
+                max_transaction_fee_usd_cents: transaction.body.max_transaction_fee_usd_cents,
                 transaction_memo: transaction.body.transaction_memo,
                 transaction_id: transaction.body.transaction_id,
                 operator: transaction.body.operator,
                 is_frozen: transaction.body.is_frozen,
                 regenerate_transaction_id: transaction.body.regenerate_transaction_id,
+                batch_key: transaction.body.batch_key,
+                custom_fee_limits: transaction.body.custom_fee_limits,
             },
             // cost transactions have no signers
             signers: Vec::new(),
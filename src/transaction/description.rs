@@ -0,0 +1,842 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::fmt::{
+    self,
+    Display,
+    Formatter,
+};
+
+use hedera_proto::services;
+use prost::Message;
+use time::{
+    Duration,
+    OffsetDateTime,
+};
+
+use super::TransactionExecute;
+use crate::protobuf::FromProtobuf;
+use crate::transfer_transaction::TokenTransfer;
+use crate::{
+    AccountId,
+    Error,
+    Hbar,
+    Key,
+    NftId,
+    PublicKey,
+    TokenId,
+    Transaction,
+    TransactionId,
+};
+
+/// A human readable summary of a transaction.
+///
+/// This is intended for reviewing a transaction before signing it ("what am I signing?"),
+/// and is built from the exact bytes that would be submitted to the network.
+///
+/// The [`Display`] implementation renders the summary as indented plain text.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct TransactionDescription {
+    /// The kind of transaction, IE, `TransferTransaction`.
+    pub kind: &'static str,
+
+    /// The transaction ID, `None` for scheduled transactions.
+    pub transaction_id: Option<TransactionId>,
+
+    /// The account paying for the transaction, if known.
+    pub payer_account_id: Option<AccountId>,
+
+    /// The maximum transaction fee the payer is willing to pay.
+    pub max_transaction_fee: Option<Hbar>,
+
+    /// The memo that will be recorded in the transaction record.
+    pub transaction_memo: String,
+
+    /// The start of the window in which the transaction is valid.
+    pub valid_start: Option<OffsetDateTime>,
+
+    /// The length of the window in which the transaction is valid.
+    pub transaction_valid_duration: Option<Duration>,
+
+    /// The account IDs of the nodes that the transaction may be submitted to.
+    pub node_account_ids: Vec<AccountId>,
+
+    /// Every transfer in the transaction, grouped by account.
+    pub transfers: Vec<AccountTransfers>,
+
+    /// Keys being set or changed by the transaction.
+    pub keys: Vec<KeyDescription>,
+
+    /// The signatures already present on the transaction.
+    pub signatures: Vec<SignatureDescription>,
+
+    /// The transaction that will be executed once the schedule has collected enough signatures.
+    ///
+    /// Only present for `ScheduleCreateTransaction`.
+    pub scheduled_transaction: Option<Box<TransactionDescription>>,
+}
+
+/// All transfers to and from a single account in a [`TransactionDescription`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AccountTransfers {
+    /// The account being debited or credited.
+    pub account_id: AccountId,
+
+    /// The net hbar change for the account.
+    pub hbar: Hbar,
+
+    /// The net change for the account for each fungible token, in the smallest denomination.
+    pub tokens: Vec<(TokenId, i64)>,
+
+    /// NFTs leaving the account.
+    pub nfts_sent: Vec<NftId>,
+
+    /// NFTs entering the account.
+    pub nfts_received: Vec<NftId>,
+
+    /// Whether any of the transfers for this account spend an allowance.
+    pub is_approval: bool,
+}
+
+/// A key being set or changed by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct KeyDescription {
+    /// What the key is used for, IE, `admin_key` or `supply_key`.
+    pub role: &'static str,
+
+    /// The new key.
+    pub key: Key,
+}
+
+/// A signature that's already present on a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SignatureDescription {
+    /// The public key prefix the signature was recorded with.
+    pub public_key_prefix: Vec<u8>,
+
+    /// The public key that made the signature, if the prefix is a complete key.
+    pub public_key: Option<PublicKey>,
+}
+
+impl<D: TransactionExecute> Transaction<D> {
+    /// Returns a human readable summary of this transaction.
+    ///
+    /// For chunked transactions only the first chunk is described.
+    ///
+    /// # Errors
+    /// - [`Error::NoPayerAccountOrTransactionId`] if `freeze_with` wasn't called with an operator and no transaction ID was set.
+    /// - [`Error::FromProtobuf`] if the transaction bytes can't be decoded.
+    ///
+    /// # Panics
+    /// - If `!self.is_frozen()`.
+    pub fn describe(&self) -> crate::Result<TransactionDescription> {
+        assert!(self.is_frozen(), "Transaction must be frozen to call `describe`");
+
+        let sources = self.make_sources()?;
+
+        // `TransactionSources` is never empty.
+        let signed_transaction = &sources.signed_transactions()[0];
+
+        let body = services::TransactionBody::decode(&*signed_transaction.body_bytes)
+            .map_err(Error::from_protobuf)?;
+
+        let mut description = TransactionDescription::from_body(body)?;
+
+        description.node_account_ids = sources.node_ids().to_vec();
+        description.signatures = signed_transaction
+            .sig_map
+            .iter()
+            .flat_map(|it| &it.sig_pair)
+            .map(SignatureDescription::from_protobuf_ref)
+            .collect();
+
+        Ok(description)
+    }
+}
+
+impl TransactionDescription {
+    fn from_body(body: services::TransactionBody) -> crate::Result<Self> {
+        let transaction_id = Option::<TransactionId>::from_protobuf(body.transaction_id)?;
+
+        let mut description = Self::from_data(pb_getf!(body, data)?)?;
+
+        description.transaction_id = transaction_id;
+        description.payer_account_id = transaction_id.map(|it| it.account_id);
+        description.valid_start = transaction_id.map(|it| it.valid_start);
+        description.transaction_valid_duration = body.transaction_valid_duration.map(Into::into);
+        description.max_transaction_fee = Some(Hbar::from_tinybars(body.transaction_fee as i64));
+        description.transaction_memo = body.memo;
+        description.push_key("batch_key", body.batch_key)?;
+
+        Ok(description)
+    }
+
+    fn from_scheduled_body(
+        body: services::SchedulableTransactionBody,
+        payer_account_id: Option<AccountId>,
+    ) -> crate::Result<Self> {
+        let data = schedulable_data_to_transaction_data(pb_getf!(body, data)?);
+
+        let mut description = Self::from_data(data)?;
+
+        description.payer_account_id = payer_account_id;
+        description.max_transaction_fee = Some(Hbar::from_tinybars(body.transaction_fee as i64));
+        description.transaction_memo = body.memo;
+
+        Ok(description)
+    }
+
+    fn new(kind: &'static str) -> Self {
+        Self {
+            kind,
+            transaction_id: None,
+            payer_account_id: None,
+            max_transaction_fee: None,
+            transaction_memo: String::new(),
+            valid_start: None,
+            transaction_valid_duration: None,
+            node_account_ids: Vec::new(),
+            transfers: Vec::new(),
+            keys: Vec::new(),
+            signatures: Vec::new(),
+            scheduled_transaction: None,
+        }
+    }
+
+    fn push_key(&mut self, role: &'static str, key: Option<services::Key>) -> crate::Result<()> {
+        if let Some(key) = Option::<Key>::from_protobuf(key)? {
+            self.keys.push(KeyDescription { role, key });
+        }
+
+        Ok(())
+    }
+
+    fn account_mut(&mut self, account_id: AccountId) -> &mut AccountTransfers {
+        // accounts stay in the order they first appear in the transaction.
+        let index = match self.transfers.iter().position(|it| it.account_id == account_id) {
+            Some(index) => index,
+            None => {
+                self.transfers.push(AccountTransfers {
+                    account_id,
+                    hbar: Hbar::ZERO,
+                    tokens: Vec::new(),
+                    nfts_sent: Vec::new(),
+                    nfts_received: Vec::new(),
+                    is_approval: false,
+                });
+
+                self.transfers.len() - 1
+            }
+        };
+
+        &mut self.transfers[index]
+    }
+
+    fn push_hbar_transfers(
+        &mut self,
+        transfers: Vec<services::AccountAmount>,
+    ) -> crate::Result<()> {
+        for transfer in transfers {
+            let account_id = AccountId::from_protobuf(pb_getf!(transfer, account_id)?)?;
+
+            let account = self.account_mut(account_id);
+            let hbar =
+                account.hbar.to_tinybars().checked_add(transfer.amount).ok_or_else(|| {
+                    Error::from_protobuf(format!("hbar transfers for `{account_id}` overflow"))
+                })?;
+
+            account.hbar = Hbar::from_tinybars(hbar);
+            account.is_approval |= transfer.is_approval;
+        }
+
+        Ok(())
+    }
+
+    fn push_token_transfers(
+        &mut self,
+        token_transfers: Vec<services::TokenTransferList>,
+    ) -> crate::Result<()> {
+        for token_transfer in Vec::<TokenTransfer>::from_protobuf(token_transfers)? {
+            let token_id = token_transfer.token_id;
+
+            for transfer in token_transfer.transfers {
+                let account = self.account_mut(transfer.account_id);
+                account.is_approval |= transfer.is_approval;
+
+                match account.tokens.iter_mut().find(|it| it.0 == token_id) {
+                    Some(it) => {
+                        it.1 = it.1.checked_add(transfer.amount).ok_or_else(|| {
+                            Error::from_protobuf(format!(
+                                "`{token_id}` transfers for `{}` overflow",
+                                transfer.account_id
+                            ))
+                        })?;
+                    }
+                    None => account.tokens.push((token_id, transfer.amount)),
+                }
+            }
+
+            for transfer in token_transfer.nft_transfers {
+                let nft_id = NftId { token_id, serial: transfer.serial };
+
+                let sender = self.account_mut(transfer.sender);
+                sender.nfts_sent.push(nft_id);
+                sender.is_approval |= transfer.is_approved;
+
+                self.account_mut(transfer.receiver).nfts_received.push(nft_id);
+            }
+        }
+
+        Ok(())
+    }
+
+    // one arm per transaction kind, splitting this up would only make it harder to check for missing kinds.
+    #[allow(clippy::too_many_lines)]
+    fn from_data(data: services::transaction_body::Data) -> crate::Result<Self> {
+        use services::transaction_body::Data;
+
+        let description = match data {
+            Data::CryptoTransfer(pb) => {
+                let mut description = Self::new("TransferTransaction");
+                description.push_hbar_transfers(
+                    pb.transfers.map(|it| it.account_amounts).unwrap_or_default(),
+                )?;
+                description.push_token_transfers(pb.token_transfers)?;
+                description
+            }
+            Data::TokenAirdrop(pb) => {
+                let mut description = Self::new("TokenAirdropTransaction");
+                description.push_token_transfers(pb.token_transfers)?;
+                description
+            }
+            Data::CryptoCreateAccount(pb) => {
+                let mut description = Self::new("AccountCreateTransaction");
+                description.push_key("key", pb.key)?;
+                description
+            }
+            Data::CryptoUpdateAccount(pb) => {
+                let mut description = Self::new("AccountUpdateTransaction");
+                description.push_key("key", pb.key)?;
+                description
+            }
+            Data::ContractCreateInstance(pb) => {
+                let mut description = Self::new("ContractCreateTransaction");
+                description.push_key("admin_key", pb.admin_key)?;
+                description
+            }
+            Data::ContractUpdateInstance(pb) => {
+                let mut description = Self::new("ContractUpdateTransaction");
+                description.push_key("admin_key", pb.admin_key)?;
+                description
+            }
+            Data::FileCreate(pb) => {
+                let mut description = Self::new("FileCreateTransaction");
+                description.push_key("keys", pb.keys.map(key_list_to_key))?;
+                description
+            }
+            Data::FileUpdate(pb) => {
+                let mut description = Self::new("FileUpdateTransaction");
+                description.push_key("keys", pb.keys.map(key_list_to_key))?;
+                description
+            }
+            Data::ConsensusCreateTopic(pb) => {
+                let mut description = Self::new("TopicCreateTransaction");
+                description.push_key("admin_key", pb.admin_key)?;
+                description.push_key("submit_key", pb.submit_key)?;
+                description.push_key("fee_schedule_key", pb.fee_schedule_key)?;
+                description
+            }
+            Data::ConsensusUpdateTopic(pb) => {
+                let mut description = Self::new("TopicUpdateTransaction");
+                description.push_key("admin_key", pb.admin_key)?;
+                description.push_key("submit_key", pb.submit_key)?;
+                description.push_key("fee_schedule_key", pb.fee_schedule_key)?;
+                description
+            }
+            Data::TokenCreation(pb) => {
+                let mut description = Self::new("TokenCreateTransaction");
+                description.push_key("admin_key", pb.admin_key)?;
+                description.push_key("kyc_key", pb.kyc_key)?;
+                description.push_key("freeze_key", pb.freeze_key)?;
+                description.push_key("wipe_key", pb.wipe_key)?;
+                description.push_key("supply_key", pb.supply_key)?;
+                description.push_key("fee_schedule_key", pb.fee_schedule_key)?;
+                description.push_key("pause_key", pb.pause_key)?;
+                description.push_key("metadata_key", pb.metadata_key)?;
+                description
+            }
+            Data::TokenUpdate(pb) => {
+                let mut description = Self::new("TokenUpdateTransaction");
+                description.push_key("admin_key", pb.admin_key)?;
+                description.push_key("kyc_key", pb.kyc_key)?;
+                description.push_key("freeze_key", pb.freeze_key)?;
+                description.push_key("wipe_key", pb.wipe_key)?;
+                description.push_key("supply_key", pb.supply_key)?;
+                description.push_key("fee_schedule_key", pb.fee_schedule_key)?;
+                description.push_key("pause_key", pb.pause_key)?;
+                description.push_key("metadata_key", pb.metadata_key)?;
+                description
+            }
+            Data::NodeCreate(pb) => {
+                let mut description = Self::new("NodeCreateTransaction");
+                description.push_key("admin_key", pb.admin_key)?;
+                description
+            }
+            Data::NodeUpdate(pb) => {
+                let mut description = Self::new("NodeUpdateTransaction");
+                description.push_key("admin_key", pb.admin_key)?;
+                description
+            }
+            Data::ScheduleCreate(pb) => {
+                let mut description = Self::new("ScheduleCreateTransaction");
+                description.push_key("admin_key", pb.admin_key)?;
+
+                let payer_account_id = Option::<AccountId>::from_protobuf(pb.payer_account_id)?;
+
+                description.scheduled_transaction = pb
+                    .scheduled_transaction_body
+                    .map(|it| Self::from_scheduled_body(it, payer_account_id))
+                    .transpose()?
+                    .map(Box::new);
+
+                description
+            }
+            Data::ContractCall(_) => Self::new("ContractExecuteTransaction"),
+            Data::ContractDeleteInstance(_) => Self::new("ContractDeleteTransaction"),
+            Data::EthereumTransaction(_) => Self::new("EthereumTransaction"),
+            Data::CryptoApproveAllowance(_) => Self::new("AccountAllowanceApproveTransaction"),
+            Data::CryptoDeleteAllowance(_) => Self::new("AccountAllowanceDeleteTransaction"),
+            Data::CryptoDelete(_) => Self::new("AccountDeleteTransaction"),
+            Data::FileAppend(_) => Self::new("FileAppendTransaction"),
+            Data::FileDelete(_) => Self::new("FileDeleteTransaction"),
+            Data::UtilPrng(_) => Self::new("PrngTransaction"),
+            Data::AtomicBatch(_) => Self::new("BatchTransaction"),
+            Data::SystemDelete(_) => Self::new("SystemDeleteTransaction"),
+            Data::SystemUndelete(_) => Self::new("SystemUndeleteTransaction"),
+            Data::Freeze(_) => Self::new("FreezeTransaction"),
+            Data::ConsensusDeleteTopic(_) => Self::new("TopicDeleteTransaction"),
+            Data::ConsensusSubmitMessage(_) => Self::new("TopicMessageSubmitTransaction"),
+            Data::TokenFreeze(_) => Self::new("TokenFreezeTransaction"),
+            Data::TokenUnfreeze(_) => Self::new("TokenUnfreezeTransaction"),
+            Data::TokenGrantKyc(_) => Self::new("TokenGrantKycTransaction"),
+            Data::TokenRevokeKyc(_) => Self::new("TokenRevokeKycTransaction"),
+            Data::TokenDeletion(_) => Self::new("TokenDeleteTransaction"),
+            Data::TokenMint(_) => Self::new("TokenMintTransaction"),
+            Data::TokenBurn(_) => Self::new("TokenBurnTransaction"),
+            Data::TokenWipe(_) => Self::new("TokenWipeTransaction"),
+            Data::TokenAssociate(_) => Self::new("TokenAssociateTransaction"),
+            Data::TokenDissociate(_) => Self::new("TokenDissociateTransaction"),
+            Data::TokenFeeScheduleUpdate(_) => Self::new("TokenFeeScheduleUpdateTransaction"),
+            Data::TokenPause(_) => Self::new("TokenPauseTransaction"),
+            Data::TokenUnpause(_) => Self::new("TokenUnpauseTransaction"),
+            Data::TokenReject(_) => Self::new("TokenRejectTransaction"),
+            Data::ScheduleDelete(_) => Self::new("ScheduleDeleteTransaction"),
+            Data::ScheduleSign(_) => Self::new("ScheduleSignTransaction"),
+            Data::TokenUpdateNfts(_) => Self::new("TokenUpdateNftsTransaction"),
+            Data::NodeDelete(_) => Self::new("NodeDeleteTransaction"),
+            Data::TokenClaimAirdrop(_) => Self::new("TokenClaimAirdropTransaction"),
+            Data::TokenCancelAirdrop(_) => Self::new("TokenCancelAirdropTransaction"),
+            Data::CryptoAddLiveHash(_) => Self::new("AddLiveHashTransaction"),
+            Data::CryptoDeleteLiveHash(_) => Self::new("DeleteLiveHashTransaction"),
+            Data::UncheckedSubmit(_) => Self::new("UncheckedSubmitTransaction"),
+            Data::NodeStakeUpdate(_) => Self::new("NodeStakeUpdateTransaction"),
+        };
+
+        Ok(description)
+    }
+
+    fn fmt_indented(&self, f: &mut Formatter<'_>, indent: usize) -> fmt::Result {
+        let pad = "  ".repeat(indent);
+
+        writeln!(f, "{pad}{}", self.kind)?;
+
+        if let Some(transaction_id) = &self.transaction_id {
+            writeln!(f, "{pad}  transaction ID: {transaction_id}")?;
+        }
+
+        if let Some(payer_account_id) = &self.payer_account_id {
+            writeln!(f, "{pad}  payer: {payer_account_id}")?;
+        }
+
+        if let Some(max_transaction_fee) = &self.max_transaction_fee {
+            writeln!(f, "{pad}  max transaction fee: {max_transaction_fee}")?;
+        }
+
+        if !self.transaction_memo.is_empty() {
+            writeln!(f, "{pad}  memo: {:?}", self.transaction_memo)?;
+        }
+
+        if let Some(valid_start) = &self.valid_start {
+            write!(f, "{pad}  valid from: {valid_start}")?;
+
+            if let Some(valid_until) =
+                self.transaction_valid_duration.and_then(|it| valid_start.checked_add(it))
+            {
+                write!(f, " until {valid_until}")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        if !self.node_account_ids.is_empty() {
+            write!(f, "{pad}  nodes:")?;
+            for node_account_id in &self.node_account_ids {
+                write!(f, " {node_account_id}")?;
+            }
+            writeln!(f)?;
+        }
+
+        if !self.transfers.is_empty() {
+            writeln!(f, "{pad}  transfers:")?;
+            for transfer in &self.transfers {
+                writeln!(f, "{pad}    {transfer}")?;
+            }
+        }
+
+        if !self.keys.is_empty() {
+            writeln!(f, "{pad}  keys:")?;
+            for key in &self.keys {
+                writeln!(f, "{pad}    {key}")?;
+            }
+        }
+
+        if !self.signatures.is_empty() {
+            writeln!(f, "{pad}  signatures:")?;
+            for signature in &self.signatures {
+                writeln!(f, "{pad}    {signature}")?;
+            }
+        }
+
+        if let Some(scheduled_transaction) = &self.scheduled_transaction {
+            writeln!(f, "{pad}  scheduled transaction:")?;
+            scheduled_transaction.fmt_indented(f, indent + 2)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for TransactionDescription {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+impl Display for AccountTransfers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.account_id)?;
+
+        let mut separator = " ";
+
+        if self.hbar != Hbar::ZERO {
+            let sign = if self.hbar > Hbar::ZERO { "+" } else { "" };
+            write!(f, "{separator}{sign}{}", self.hbar)?;
+            separator = ", ";
+        }
+
+        for (token_id, amount) in &self.tokens {
+            write!(f, "{separator}{amount:+} of {token_id}")?;
+            separator = ", ";
+        }
+
+        for nft_id in &self.nfts_sent {
+            write!(f, "{separator}sends {nft_id}")?;
+            separator = ", ";
+        }
+
+        for nft_id in &self.nfts_received {
+            write!(f, "{separator}receives {nft_id}")?;
+            separator = ", ";
+        }
+
+        if self.is_approval {
+            write!(f, " (approved allowance)")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for KeyDescription {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.role)?;
+        fmt_key(&self.key, f)
+    }
+}
+
+impl Display for SignatureDescription {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.public_key {
+            Some(public_key) => write!(f, "{public_key}"),
+            None => write!(f, "prefix {}", hex::encode(&self.public_key_prefix)),
+        }
+    }
+}
+
+impl SignatureDescription {
+    fn from_protobuf_ref(pb: &services::SignaturePair) -> Self {
+        use services::signature_pair::Signature;
+
+        let public_key = match &pb.signature {
+            Some(Signature::Ed25519(_)) => PublicKey::from_bytes_ed25519(&pb.pub_key_prefix).ok(),
+            Some(Signature::EcdsaSecp256k1(_)) => {
+                PublicKey::from_bytes_ecdsa(&pb.pub_key_prefix).ok()
+            }
+            _ => None,
+        };
+
+        Self { public_key_prefix: pb.pub_key_prefix.clone(), public_key }
+    }
+}
+
+fn fmt_key(key: &Key, f: &mut Formatter<'_>) -> fmt::Result {
+    match key {
+        Key::Single(public_key) => write!(f, "{public_key}"),
+        Key::ContractId(contract_id) => write!(f, "contract {contract_id}"),
+        Key::DelegateContractId(contract_id) => write!(f, "delegatable contract {contract_id}"),
+        Key::KeyList(key_list) => {
+            match key_list.threshold {
+                Some(threshold) => write!(f, "{threshold} of [")?,
+                None => write!(f, "all of [")?,
+            }
+
+            for (index, key) in key_list.keys.iter().enumerate() {
+                if index != 0 {
+                    write!(f, ", ")?;
+                }
+
+                fmt_key(key, f)?;
+            }
+
+            write!(f, "]")
+        }
+    }
+}
+
+fn key_list_to_key(keys: services::KeyList) -> services::Key {
+    services::Key { key: Some(services::key::Key::KeyList(keys)) }
+}
+
+/// Converts the data of a scheduled transaction into the equivalent data for a top level transaction.
+fn schedulable_data_to_transaction_data(
+    data: services::schedulable_transaction_body::Data,
+) -> services::transaction_body::Data {
+    use services::schedulable_transaction_body::Data as Scheduled;
+    use services::transaction_body::Data;
+
+    match data {
+        Scheduled::ContractCall(it) => Data::ContractCall(it),
+        Scheduled::ContractCreateInstance(it) => Data::ContractCreateInstance(it),
+        Scheduled::ContractUpdateInstance(it) => Data::ContractUpdateInstance(it),
+        Scheduled::ContractDeleteInstance(it) => Data::ContractDeleteInstance(it),
+        Scheduled::CryptoApproveAllowance(it) => Data::CryptoApproveAllowance(it),
+        Scheduled::CryptoDeleteAllowance(it) => Data::CryptoDeleteAllowance(it),
+        Scheduled::CryptoCreateAccount(it) => Data::CryptoCreateAccount(it),
+        Scheduled::CryptoDelete(it) => Data::CryptoDelete(it),
+        Scheduled::CryptoTransfer(it) => Data::CryptoTransfer(it),
+        Scheduled::CryptoUpdateAccount(it) => Data::CryptoUpdateAccount(it),
+        Scheduled::FileAppend(it) => Data::FileAppend(it),
+        Scheduled::FileCreate(it) => Data::FileCreate(it),
+        Scheduled::FileDelete(it) => Data::FileDelete(it),
+        Scheduled::FileUpdate(it) => Data::FileUpdate(it),
+        Scheduled::SystemDelete(it) => Data::SystemDelete(it),
+        Scheduled::SystemUndelete(it) => Data::SystemUndelete(it),
+        Scheduled::Freeze(it) => Data::Freeze(it),
+        Scheduled::ConsensusCreateTopic(it) => Data::ConsensusCreateTopic(it),
+        Scheduled::ConsensusUpdateTopic(it) => Data::ConsensusUpdateTopic(it),
+        Scheduled::ConsensusDeleteTopic(it) => Data::ConsensusDeleteTopic(it),
+        Scheduled::ConsensusSubmitMessage(it) => Data::ConsensusSubmitMessage(it),
+        Scheduled::TokenCreation(it) => Data::TokenCreation(it),
+        Scheduled::TokenFreeze(it) => Data::TokenFreeze(it),
+        Scheduled::TokenUnfreeze(it) => Data::TokenUnfreeze(it),
+        Scheduled::TokenGrantKyc(it) => Data::TokenGrantKyc(it),
+        Scheduled::TokenRevokeKyc(it) => Data::TokenRevokeKyc(it),
+        Scheduled::TokenDeletion(it) => Data::TokenDeletion(it),
+        Scheduled::TokenUpdate(it) => Data::TokenUpdate(it),
+        Scheduled::TokenMint(it) => Data::TokenMint(it),
+        Scheduled::TokenBurn(it) => Data::TokenBurn(it),
+        Scheduled::TokenWipe(it) => Data::TokenWipe(it),
+        Scheduled::TokenAssociate(it) => Data::TokenAssociate(it),
+        Scheduled::TokenDissociate(it) => Data::TokenDissociate(it),
+        Scheduled::TokenFeeScheduleUpdate(it) => Data::TokenFeeScheduleUpdate(it),
+        Scheduled::TokenPause(it) => Data::TokenPause(it),
+        Scheduled::TokenUnpause(it) => Data::TokenUnpause(it),
+        Scheduled::TokenReject(it) => Data::TokenReject(it),
+        Scheduled::ScheduleDelete(it) => Data::ScheduleDelete(it),
+        Scheduled::UtilPrng(it) => Data::UtilPrng(it),
+        Scheduled::TokenUpdateNfts(it) => Data::TokenUpdateNfts(it),
+        Scheduled::NodeCreate(it) => Data::NodeCreate(it),
+        Scheduled::NodeUpdate(it) => Data::NodeUpdate(it),
+        Scheduled::NodeDelete(it) => Data::NodeDelete(it),
+        Scheduled::TokenAirdrop(it) => Data::TokenAirdrop(it),
+        Scheduled::TokenClaimAirdrop(it) => Data::TokenClaimAirdrop(it),
+        Scheduled::TokenCancelAirdrop(it) => Data::TokenCancelAirdrop(it),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hedera_proto::services;
+
+    use super::TransactionDescription;
+    use crate::protobuf::ToProtobuf;
+    use crate::transaction::test_helpers::{
+        unused_private_key,
+        TEST_NODE_ACCOUNT_IDS,
+        TEST_TX_ID,
+    };
+    use crate::{
+        AccountId,
+        AnyTransaction,
+        Hbar,
+        Key,
+        NftId,
+        TokenCreateTransaction,
+        TokenId,
+        TransferTransaction,
+    };
+
+    fn account_amount(amount: i64) -> services::AccountAmount {
+        services::AccountAmount {
+            account_id: Some(AccountId::new(0, 0, 2).to_protobuf()),
+            amount,
+            is_approval: false,
+        }
+    }
+
+    #[test]
+    fn hbar_transfer_overflow() {
+        let mut description = TransactionDescription::new("TransferTransaction");
+
+        let res = description
+            .push_hbar_transfers(vec![account_amount(i64::MAX), account_amount(i64::MAX)]);
+
+        assert!(matches!(res, Err(crate::Error::FromProtobuf(_))));
+    }
+
+    #[test]
+    fn token_transfer_overflow() {
+        let mut description = TransactionDescription::new("TransferTransaction");
+
+        let res = description.push_token_transfers(vec![services::TokenTransferList {
+            token: Some(TokenId::new(0, 0, 7).to_protobuf()),
+            transfers: vec![account_amount(i64::MIN), account_amount(-1)],
+            ..Default::default()
+        }]);
+
+        assert!(matches!(res, Err(crate::Error::FromProtobuf(_))));
+    }
+
+    #[test]
+    fn describe_transfer() -> crate::Result<()> {
+        let mut tx = TransferTransaction::new_for_tests();
+
+        tx.transaction_memo("rent")
+            .hbar_transfer(AccountId::new(0, 0, 2), Hbar::new(-3))
+            .hbar_transfer(AccountId::new(0, 0, 3), Hbar::new(3))
+            .token_transfer(TokenId::new(0, 0, 7), AccountId::new(0, 0, 2), -10)
+            .token_transfer(TokenId::new(0, 0, 7), AccountId::new(0, 0, 4), 10)
+            .nft_transfer(
+                NftId { token_id: TokenId::new(0, 0, 8), serial: 1 },
+                AccountId::new(0, 0, 3),
+                AccountId::new(0, 0, 2),
+            )
+            .freeze()?;
+
+        let description = tx.describe()?;
+
+        assert_eq!(description.kind, "TransferTransaction");
+        assert_eq!(description.transaction_id, Some(TEST_TX_ID));
+        assert_eq!(description.payer_account_id, Some(TEST_TX_ID.account_id));
+        assert_eq!(description.max_transaction_fee, Some(Hbar::new(2)));
+        assert_eq!(description.transaction_memo, "rent");
+        assert_eq!(description.node_account_ids, TEST_NODE_ACCOUNT_IDS);
+
+        let accounts: Vec<_> = description.transfers.iter().map(|it| it.account_id).collect();
+        assert_eq!(
+            accounts,
+            [AccountId::new(0, 0, 2), AccountId::new(0, 0, 3), AccountId::new(0, 0, 4)]
+        );
+
+        let account_2 = &description.transfers[0];
+        assert_eq!(account_2.hbar, Hbar::new(-3));
+        assert_eq!(account_2.tokens, [(TokenId::new(0, 0, 7), -10)]);
+        assert_eq!(account_2.nfts_received, [NftId { token_id: TokenId::new(0, 0, 8), serial: 1 }]);
+
+        let account_3 = &description.transfers[1];
+        assert_eq!(account_3.nfts_sent, [NftId { token_id: TokenId::new(0, 0, 8), serial: 1 }]);
+
+        assert_eq!(description.signatures.len(), 1);
+        assert_eq!(description.signatures[0].public_key, Some(unused_private_key().public_key()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn describe_from_bytes() -> crate::Result<()> {
+        let bytes = TransferTransaction::new_for_tests()
+            .hbar_transfer(AccountId::new(0, 0, 2), Hbar::new(-1))
+            .hbar_transfer(AccountId::new(0, 0, 3), Hbar::new(1))
+            .freeze()?
+            .to_bytes()?;
+
+        let description = AnyTransaction::from_bytes(&bytes)?.describe()?;
+
+        assert_eq!(description.kind, "TransferTransaction");
+        assert_eq!(description.transfers.len(), 2);
+        assert_eq!(description.signatures.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn describe_scheduled() -> crate::Result<()> {
+        let key = unused_private_key().public_key();
+
+        let mut inner = TokenCreateTransaction::new();
+        inner.name("ffff").symbol("F").admin_key(key).supply_key(key);
+
+        let mut tx = inner.schedule();
+        tx.node_account_ids(TEST_NODE_ACCOUNT_IDS)
+            .transaction_id(TEST_TX_ID)
+            .payer_account_id(AccountId::new(0, 0, 9))
+            .freeze()?;
+
+        let description = tx.describe()?;
+
+        assert_eq!(description.kind, "ScheduleCreateTransaction");
+        assert!(description.signatures.is_empty());
+
+        let scheduled = description.scheduled_transaction.unwrap();
+
+        assert_eq!(scheduled.kind, "TokenCreateTransaction");
+        assert_eq!(scheduled.payer_account_id, Some(AccountId::new(0, 0, 9)));
+
+        let roles: Vec<_> = scheduled.keys.iter().map(|it| it.role).collect();
+        assert_eq!(roles, ["admin_key", "supply_key"]);
+        assert_eq!(scheduled.keys[0].key, Key::Single(key));
+
+        Ok(())
+    }
+}
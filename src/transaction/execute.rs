@@ -153,6 +153,16 @@ pub trait TransactionData: Clone + Into<AnyTransactionData> {
     fn wait_for_receipt(&self) -> bool {
         false
     }
+
+    /// Returns the IDs of the transactions that are executed as part of this one, if this is a batch transaction.
+    fn inner_transaction_ids(&self) -> Vec<TransactionId> {
+        Vec::new()
+    }
+
+    /// Checks that the transaction can be encoded for the network, this is checked when the transaction is frozen.
+    fn validate_encoding(&self) -> crate::Result<()> {
+        Ok(())
+    }
 }
 
 pub trait TransactionExecute:
@@ -230,6 +240,7 @@ where
             transaction_id: *transaction_id.unwrap(),
             transaction_hash,
             validate_status: true,
+            inner_transaction_ids: self.data().inner_transaction_ids(),
         })
     }
 
@@ -299,6 +310,8 @@ where
             node_account_id: Some(chunk_info.node_account_id.to_protobuf()),
             generate_record: false,
             transaction_fee,
+            batch_key: self.body.batch_key.to_protobuf(),
+            max_custom_fees: self.body.custom_fee_limits.to_protobuf(),
         }
     }
 }
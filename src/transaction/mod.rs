@@ -29,6 +29,7 @@ use std::num::NonZeroUsize;
 
 use hedera_proto::services;
 use prost::Message;
+use rust_decimal::Decimal;
 use time::Duration;
 use triomphe::Arc;
 
@@ -38,8 +39,10 @@ use crate::signer::AnySigner;
 use crate::{
     AccountId,
     Client,
+    CustomFeeLimit,
     Error,
     Hbar,
+    Key,
     Operator,
     PrivateKey,
     PublicKey,
@@ -53,6 +56,7 @@ use crate::{
 mod any;
 mod chunked;
 mod cost;
+mod description;
 mod execute;
 mod protobuf;
 mod source;
@@ -67,6 +71,12 @@ pub(crate) use chunked::{
     ChunkedTransactionData,
 };
 pub(crate) use cost::CostTransaction;
+pub use description::{
+    AccountTransfers,
+    KeyDescription,
+    SignatureDescription,
+    TransactionDescription,
+};
 pub(crate) use execute::{
     TransactionData,
     TransactionExecute,
@@ -100,6 +110,8 @@ pub(crate) struct TransactionBody<D> {
 
     pub(crate) max_transaction_fee: Option<Hbar>,
 
+    pub(crate) max_transaction_fee_usd_cents: Option<Decimal>,
+
     pub(crate) transaction_memo: String,
 
     pub(crate) transaction_id: Option<TransactionId>,
@@ -109,6 +121,10 @@ pub(crate) struct TransactionBody<D> {
     pub(crate) is_frozen: bool,
 
     pub(crate) regenerate_transaction_id: Option<bool>,
+
+    pub(crate) batch_key: Option<Key>,
+
+    pub(crate) custom_fee_limits: Vec<CustomFeeLimit>,
 }
 
 impl<D> Default for Transaction<D>
@@ -122,11 +138,14 @@ where
                 node_account_ids: None,
                 transaction_valid_duration: None,
                 max_transaction_fee: None,
+                max_transaction_fee_usd_cents: None,
                 transaction_memo: String::new(),
                 transaction_id: None,
                 operator: None,
                 is_frozen: false,
                 regenerate_transaction_id: None,
+                batch_key: None,
+                custom_fee_limits: Vec::new(),
             },
             signers: Vec::new(),
             sources: None,
@@ -188,9 +207,28 @@ impl<D> Transaction<D> {
         );
     }
 
+    #[inline(always)]
+    pub(crate) fn body(&self) -> &TransactionBody<D> {
+        &self.body
+    }
+
+    /// Refreshes `client`'s exchange rates if freezing `self` needs them to convert a fee in cents (USD).
+    async fn refresh_exchange_rates_for_fee(&self, client: &Client) -> crate::Result<()> {
+        let needs_rates = !self.is_frozen()
+            && self.body.max_transaction_fee.is_none()
+            && (self.body.max_transaction_fee_usd_cents.is_some()
+                || client.default_max_transaction_fee_usd_cents().is_some());
+
+        if needs_rates {
+            client.refresh_exchange_rates().await?;
+        }
+
+        Ok(())
+    }
+
     /// # Panics
     /// If `self.is_frozen()`.
-    fn body_mut(&mut self) -> &mut TransactionBody<D> {
+    pub(crate) fn body_mut(&mut self) -> &mut TransactionBody<D> {
         self.require_not_frozen();
         &mut self.body
     }
@@ -256,8 +294,32 @@ impl<D> Transaction<D> {
     }
 
     /// Sets the maximum transaction fee the paying account is willing to pay.
+    ///
+    /// This replaces a fee set with [`max_transaction_fee_usd_cents`](Self::max_transaction_fee_usd_cents).
     pub fn max_transaction_fee(&mut self, fee: Hbar) -> &mut Self {
-        self.body_mut().max_transaction_fee = Some(fee);
+        let body = self.body_mut();
+        body.max_transaction_fee = Some(fee);
+        body.max_transaction_fee_usd_cents = None;
+        self
+    }
+
+    /// Returns the maximum transaction fee the paying account is willing to pay, in cents (USD).
+    #[must_use]
+    pub fn get_max_transaction_fee_usd_cents(&self) -> Option<Decimal> {
+        self.body.max_transaction_fee_usd_cents
+    }
+
+    /// Sets the maximum transaction fee the paying account is willing to pay, in cents (USD).
+    ///
+    /// The fee is converted to hbar when the transaction is frozen, at the client's cached
+    /// [`ExchangeRates`](crate::ExchangeRates), which `execute` refreshes once the current rate expires.
+    /// When freezing the transaction manually, see [`Client::update_exchange_rates`].
+    ///
+    /// This replaces a fee set with [`max_transaction_fee`](Self::max_transaction_fee).
+    pub fn max_transaction_fee_usd_cents(&mut self, cents: Decimal) -> &mut Self {
+        let body = self.body_mut();
+        body.max_transaction_fee = None;
+        body.max_transaction_fee_usd_cents = Some(cents);
         self
     }
 
@@ -293,6 +355,20 @@ impl<D> Transaction<D> {
         self
     }
 
+    /// Returns the key that must sign the batch this transaction is part of.
+    #[must_use]
+    pub fn get_batch_key(&self) -> Option<&Key> {
+        self.body.batch_key.as_ref()
+    }
+
+    /// Sets the key that must sign the batch this transaction is part of.
+    ///
+    /// A transaction with a batch key can only be executed as an inner transaction of a [`BatchTransaction`](crate::BatchTransaction).
+    pub fn batch_key(&mut self, key: impl Into<Key>) -> &mut Self {
+        self.body_mut().batch_key = Some(key.into());
+        self
+    }
+
     /// Sign the transaction.
     pub fn sign(&mut self, private_key: PrivateKey) -> &mut Self {
         self.sign_signer(AnySigner::PrivateKey(private_key))
@@ -372,11 +448,12 @@ impl<D: ChunkedTransactionData> Transaction<D> {
     }
 }
 
-impl<D: ValidateChecksums> Transaction<D> {
+impl<D: ValidateChecksums + TransactionData> Transaction<D> {
     /// Freeze the transaction so that no further modifications can be made.
     ///
     /// # Errors
     /// - [`Error::FreezeUnsetNodeAccountIds`] if no [`node_account_ids`](Self::node_account_ids) were set.
+    /// - [`Error::InvalidNodeDetails`] if a node transaction has an IPv6 endpoint, which the network can't store.
     ///
     /// # Panics
     /// - If `node_account_ids` is explicitly set to empty (IE: `tx.node_account_ids([]).freeze_with(None)`).
@@ -388,6 +465,8 @@ impl<D: ValidateChecksums> Transaction<D> {
     ///
     /// # Errors
     /// - [`Error::FreezeUnsetNodeAccountIds`] if no [`node_account_ids`](Self::node_account_ids) were set and `client.is_none()`.
+    /// - [`Error::ExchangeRatesUnavailable`] if the max transaction fee is in cents (USD) and `client` has no current exchange rates.
+    /// - [`Error::InvalidNodeDetails`] if a node transaction has an IPv6 endpoint, which the network can't store.
     ///
     /// # Panics
     /// - If `node_account_ids` is explicitly set to empty (IE: `tx.node_account_ids([]).freeze_with(None)`).
@@ -398,6 +477,8 @@ impl<D: ValidateChecksums> Transaction<D> {
         if self.is_frozen() {
             return Ok(self);
         }
+
+        self.data().validate_encoding()?;
         let client: Option<&Client> = client.into();
 
         let node_account_ids = match &self.body.node_account_ids {
@@ -421,11 +502,19 @@ impl<D: ValidateChecksums> Transaction<D> {
         };
 
         // note to reviewer: this is intentionally still an option, fallback is used later, swift doesn't *have* default max transaction fee and fixing it is a massive PITA.
-        let max_transaction_fee = self.body.max_transaction_fee.or_else(|| {
-            // no max has been set on the *transaction*
-            // check if there is a global max set on the client
-            client.and_then(Client::default_max_transaction_fee)
-        });
+        let max_transaction_fee =
+            match (self.body.max_transaction_fee, self.body.max_transaction_fee_usd_cents) {
+                (Some(fee), _) => Some(fee),
+                (None, Some(cents)) => {
+                    Some(client.ok_or(Error::ExchangeRatesUnavailable)?.usd_cents_to_hbar(cents)?)
+                }
+                // no max has been set on the *transaction*
+                // check if there is a global max set on the client
+                (None, None) => match client {
+                    Some(client) => client.resolved_default_max_transaction_fee()?,
+                    None => None,
+                },
+            };
 
         let operator = client.and_then(Client::full_load_operator);
 
@@ -467,6 +556,30 @@ impl<D: ValidateChecksums> Transaction<D> {
 
         Ok(self)
     }
+
+    /// Prepare the transaction to be an inner transaction of a [`BatchTransaction`](crate::BatchTransaction).
+    ///
+    /// This sets the batch key, sets the node account ID to `0.0.0`, freezes the transaction with `client` and signs it with the `client`'s operator.
+    ///
+    /// # Errors
+    /// - [`Error::NoPayerAccountOrTransactionId`] if `client` has no operator.
+    /// - If [`freeze_with`](Self::freeze_with) would error for this transaction.
+    ///
+    /// # Panics
+    /// - If `self.is_frozen()`.
+    pub fn batchify(
+        &mut self,
+        client: &Client,
+        batch_key: impl Into<Key>,
+    ) -> crate::Result<&mut Self> {
+        if client.full_load_operator().is_none() {
+            return Err(Error::NoPayerAccountOrTransactionId);
+        }
+
+        self.batch_key(batch_key).node_account_ids([AccountId::new(0, 0, 0)]);
+
+        self.sign_with_operator(client)
+    }
 }
 
 impl<D: TransactionExecute> Transaction<D> {
@@ -630,16 +743,31 @@ impl<D: TransactionExecute> Transaction<D> {
             "Transaction must be frozen before calling `get_transaction_hash`"
         );
 
-        let sources = self.make_sources()?;
+        self.store_sources()?;
 
-        let sources = match sources {
-            Cow::Borrowed(it) => it,
-            Cow::Owned(it) => &*self.sources.insert(it),
-        };
+        let sources = self.sources().unwrap();
 
         Ok(TransactionHash::new(&sources.transactions().first().unwrap().signed_transaction_bytes))
     }
 
+    /// Builds the signed sources for this transaction and keeps them,
+    /// so that the transaction ID and signatures won't change anymore.
+    ///
+    /// # Panics
+    /// - If `!self.is_frozen()`.
+    pub(crate) fn store_sources(&mut self) -> crate::Result<()> {
+        if let Cow::Owned(sources) = self.make_sources()? {
+            self.sources = Some(sources);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the transaction ID of the first chunk, if the sources have been stored with [`store_sources`](Self::store_sources).
+    pub(crate) fn stored_transaction_id(&self) -> Option<TransactionId> {
+        self.sources().and_then(|it| it.chunks().next()).map(|it| it.transaction_id())
+    }
+
     /// Get the hashes for this transaction.
     ///
     /// Note: Calling this function _disables_ transaction ID regeneration.
@@ -727,6 +855,8 @@ where
         client: &Client,
         timeout: Option<std::time::Duration>,
     ) -> crate::Result<TransactionResponse> {
+        self.refresh_exchange_rates_for_fee(client).await?;
+
         // it's fine to call freeze while already frozen, so, let `freeze_with` handle the freeze check.
         self.freeze_with(Some(client))?;
 
@@ -846,6 +976,8 @@ where
         client: &Client,
         timeout_per_chunk: Option<std::time::Duration>,
     ) -> crate::Result<Vec<TransactionResponse>> {
+        self.refresh_exchange_rates_for_fee(client).await?;
+
         // it's fine to call freeze while already frozen, so, let `freeze_with` handle the freeze check.
         self.freeze_with(Some(client))?;
 
@@ -900,6 +1032,11 @@ impl AnyTransaction {
             list.transaction_list
         };
 
+        Self::from_transactions(list)
+    }
+
+    /// Creates a transaction from the protobuf transactions making it up, IE, the entries of a `TransactionList`.
+    pub(crate) fn from_transactions(list: Vec<services::Transaction>) -> crate::Result<Self> {
         let sources = TransactionSources::new(list)?;
 
         let transaction_bodies: Result<Vec<_>, _> = sources
@@ -963,6 +1100,8 @@ fn pb_transaction_body_eq(
         transaction_valid_duration,
         generate_record,
         memo,
+        batch_key,
+        max_custom_fees,
         data,
     } = rhs;
 
@@ -982,6 +1121,14 @@ fn pb_transaction_body_eq(
         return false;
     }
 
+    if &lhs.batch_key != batch_key {
+        return false;
+    }
+
+    if &lhs.max_custom_fees != max_custom_fees {
+        return false;
+    }
+
     match (&lhs.data, data) {
         (None, None) => {}
         (Some(lhs), Some(rhs)) => match (lhs, rhs) {
@@ -1050,11 +1197,14 @@ where
             node_account_ids,
             transaction_valid_duration,
             max_transaction_fee,
+            max_transaction_fee_usd_cents,
             transaction_memo,
             transaction_id,
             operator,
             is_frozen,
             regenerate_transaction_id,
+            batch_key,
+            custom_fee_limits,
         } = body;
 
         // not a `map().map_err()` because ownership.
@@ -1065,11 +1215,14 @@ where
                     node_account_ids,
                     transaction_valid_duration,
                     max_transaction_fee,
+                    max_transaction_fee_usd_cents,
                     transaction_memo,
                     transaction_id,
                     operator,
                     is_frozen,
                     regenerate_transaction_id,
+                    batch_key,
+                    custom_fee_limits,
                 },
                 signers,
                 sources,
@@ -1081,11 +1234,14 @@ where
                     node_account_ids,
                     transaction_valid_duration,
                     max_transaction_fee,
+                    max_transaction_fee_usd_cents,
                     transaction_memo,
                     transaction_id,
                     operator,
                     is_frozen,
                     regenerate_transaction_id,
+                    batch_key,
+                    custom_fee_limits,
                 },
                 signers,
                 sources,
@@ -1168,6 +1324,8 @@ pub(crate) mod test_helpers {
             transaction_valid_duration,
             generate_record,
             memo,
+            batch_key,
+            max_custom_fees,
             data,
         } = body;
 
@@ -1181,6 +1339,8 @@ pub(crate) mod test_helpers {
         assert_eq!(transaction_valid_duration, Some(services::Duration { seconds: 120 }));
         assert_eq!(generate_record, false);
         assert_eq!(memo, "");
+        assert_eq!(batch_key, None);
+        assert!(max_custom_fees.is_empty());
 
         data.unwrap()
     }
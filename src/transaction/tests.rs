@@ -119,3 +119,29 @@ async fn chunked_to_from_bytes() -> crate::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn batchify_without_operator() {
+    let client = Client::for_testnet();
+
+    let mut tx = TransferTransaction::new();
+    let res = tx.batchify(&client, PrivateKey::generate_ed25519().public_key());
+
+    assert_matches!(res, Err(crate::Error::NoPayerAccountOrTransactionId));
+}
+
+#[test]
+fn max_transaction_fee_usd_cents_needs_exchange_rates() {
+    let mut tx = TransferTransaction::new();
+
+    tx.max_transaction_fee_usd_cents(rust_decimal::Decimal::from(5))
+        .node_account_ids([6.into()])
+        .transaction_id(TransactionId::generate(101.into()));
+
+    assert_matches!(tx.freeze(), Err(crate::Error::ExchangeRatesUnavailable));
+
+    tx.max_transaction_fee(Hbar::new(1));
+
+    assert_eq!(tx.get_max_transaction_fee_usd_cents(), None);
+    assert!(tx.freeze().is_ok());
+}
@@ -21,6 +21,8 @@
 use std::collections::HashMap;
 
 use hedera_proto::services;
+#[cfg(test)]
+pub(super) use tests::make_record;
 use time::OffsetDateTime;
 
 use crate::protobuf::ToProtobuf;
@@ -360,7 +362,11 @@ mod tests {
         Transfer,
     };
 
-    fn make_record(prng_bytes: Option<Vec<u8>>, prng_number: Option<u32>) -> TransactionRecord {
+    // needed in `token_airdrop_flow`.
+    pub(crate) fn make_record(
+        prng_bytes: Option<Vec<u8>>,
+        prng_number: Option<u32>,
+    ) -> TransactionRecord {
         TransactionRecord {
             receipt: crate::transaction_receipt::make_receipt(),
             transaction_hash: b"hello".to_vec(),
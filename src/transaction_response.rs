@@ -39,6 +39,7 @@ use crate::{
 /// receipt (free), or can buy a more detailed record (not free).
 ///
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct TransactionResponse {
     /// The account ID of the node that the transaction was submitted to.
     pub node_account_id: AccountId,
@@ -55,6 +56,11 @@ pub struct TransactionResponse {
 
     /// Whether the receipt/record status should be validated.
     pub validate_status: bool,
+
+    /// The transaction IDs of the inner transactions, if the transaction was a [`BatchTransaction`](crate::BatchTransaction).
+    ///
+    /// Inner transactions get their own receipts and records, see [`get_inner_receipts`](Self::get_inner_receipts).
+    pub inner_transaction_ids: Vec<TransactionId>,
 }
 
 impl TransactionResponse {
@@ -133,4 +139,76 @@ impl TransactionResponse {
     ) -> crate::Result<TransactionRecord> {
         self.get_record_query().execute_with_timeout(client, timeout).await
     }
+
+    /// Create queries that will get the receipts for the inner transactions of a [`BatchTransaction`](crate::BatchTransaction).
+    ///
+    /// Returns an empty list for any other kind of transaction.
+    #[must_use]
+    pub fn get_inner_receipt_queries(&self) -> Vec<TransactionReceiptQuery> {
+        self.inner_transaction_ids
+            .iter()
+            .map(|&transaction_id| {
+                let mut query = TransactionReceiptQuery::new();
+
+                query.transaction_id(transaction_id).validate_status(self.validate_status);
+
+                query
+            })
+            .collect()
+    }
+
+    /// Create queries that will get the records for the inner transactions of a [`BatchTransaction`](crate::BatchTransaction).
+    ///
+    /// Returns an empty list for any other kind of transaction.
+    #[must_use]
+    pub fn get_inner_record_queries(&self) -> Vec<TransactionRecordQuery> {
+        self.inner_transaction_ids
+            .iter()
+            .map(|&transaction_id| {
+                let mut query = TransactionRecordQuery::new();
+
+                query.transaction_id(transaction_id).validate_status(self.validate_status);
+
+                query
+            })
+            .collect()
+    }
+
+    /// Get the receipts for the inner transactions of a [`BatchTransaction`](crate::BatchTransaction), in the order they were added to the batch.
+    /// Will wait for consensus.
+    ///
+    /// # Errors
+    /// - if [`validate_status`](Self.validate_status) is `true`:
+    ///   [`Error::ReceiptStatus`](crate::Error::ReceiptStatus) for the first failing receipt.
+    pub async fn get_inner_receipts(
+        &self,
+        client: &Client,
+    ) -> crate::Result<Vec<TransactionReceipt>> {
+        let mut receipts = Vec::with_capacity(self.inner_transaction_ids.len());
+
+        for mut query in self.get_inner_receipt_queries() {
+            receipts.push(query.execute(client).await?);
+        }
+
+        Ok(receipts)
+    }
+
+    /// Get the records for the inner transactions of a [`BatchTransaction`](crate::BatchTransaction), in the order they were added to the batch.
+    /// Will wait for consensus.
+    ///
+    /// # Errors
+    /// - if [`validate_status`](Self.validate_status) is `true`:
+    ///   [`Error::ReceiptStatus`](crate::Error::ReceiptStatus) for the first failing receipt in a record.
+    pub async fn get_inner_records(
+        &self,
+        client: &Client,
+    ) -> crate::Result<Vec<TransactionRecord>> {
+        let mut records = Vec::with_capacity(self.inner_transaction_ids.len());
+
+        for mut query in self.get_inner_record_queries() {
+            records.push(query.execute(client).await?);
+        }
+
+        Ok(records)
+    }
 }
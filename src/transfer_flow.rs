@@ -0,0 +1,626 @@
+/*
+ * ‌
+ * Hedera Rust SDK
+ * ​
+ * Copyright (C) 2022 - 2023 Hedera Hashgraph, LLC
+ * ​
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * ‍
+ */
+
+use std::time::Duration;
+
+#[cfg(feature = "mirror-rest")]
+use crate::mirror_rest;
+use crate::signer::AnySigner;
+use crate::{
+    AccountId,
+    AccountInfo,
+    AccountInfoQuery,
+    Client,
+    Error,
+    PrivateKey,
+    PublicKey,
+    Status,
+    TokenAssociateTransaction,
+    TokenAssociation,
+    TokenId,
+    TransactionResponse,
+    TransferTransaction,
+};
+
+/// Execute a transfer, associating the receivers with the transferred tokens first.
+///
+/// The operation of this flow is as follows:
+/// 1. Look up the tokens every receiver of a token is associated with, and compare them
+///    against the tokens it would receive.
+/// 2. Fail with [`Error::TokenNotAssociated`] if a receiver would be missing an association
+///    that can't be created, before anything is submitted.
+/// 3. Execute a [`TokenAssociateTransaction`] for every receiver whose key is held by this flow.
+/// 4. Execute the [`TransferTransaction`].
+///
+/// Receivers whose key isn't held by this flow must have enough free automatic association slots.
+///
+/// With the `mirror-rest` feature the associations come from the mirror node.
+/// Without it they come from the receiver's [`AccountInfo`], which networks that no longer report
+/// token relationships leave empty. The associations of such a receiver are reported as
+/// [`unknown`](TransferAssociationReport::unknown) and don't stop the transfer.
+#[derive(Default, Debug)]
+pub struct TransferFlow {
+    node_account_ids: Option<Vec<AccountId>>,
+    transfer: TransferTransaction,
+    signers: Vec<AnySigner>,
+    controlled_accounts: Vec<(AccountId, AnySigner)>,
+}
+
+/// How the receivers of a [`TransferFlow`] will be able to accept their tokens.
+#[derive(Debug, Clone, Default)]
+pub struct TransferAssociationReport {
+    /// Associations that will be created by [`TokenAssociateTransaction`]s before the transfer.
+    pub to_associate: Vec<TokenAssociation>,
+
+    /// Associations that the network will create automatically, using the receivers' automatic association slots.
+    pub automatic: Vec<TokenAssociation>,
+
+    /// Associations that are missing and can't be created, the transfer would fail because of these.
+    pub unassociated: Vec<TokenAssociation>,
+
+    /// Associations that couldn't be looked up, because the network didn't report the receiver's tokens
+    /// and the `mirror-rest` feature is disabled.
+    ///
+    /// The transfer is still executed, and fails if any of these are missing and can't be created automatically.
+    pub unknown: Vec<TokenAssociation>,
+}
+
+// the tokens a receiver is associated with.
+#[derive(Debug, Clone)]
+struct ReceiverAssociations {
+    account_id: AccountId,
+    // `-1` means unlimited automatic associations.
+    max_automatic_associations: i32,
+    // every associated token, and whether it was associated automatically, or `None` if they're unknown.
+    tokens: Option<Vec<(TokenId, bool)>>,
+}
+
+impl ReceiverAssociations {
+    fn from_info(info: &AccountInfo) -> Self {
+        // networks that no longer report token relationships leave them empty, so an empty list tells us nothing.
+        let tokens = (!info.token_relationships.is_empty()).then(|| {
+            info.token_relationships
+                .iter()
+                .map(|it| (it.token_id, it.automatic_association))
+                .collect()
+        });
+
+        Self {
+            account_id: info.account_id,
+            max_automatic_associations: info.max_automatic_token_associations as i32,
+            tokens,
+        }
+    }
+
+    fn is_associated(&self, token_id: TokenId) -> bool {
+        self.tokens.iter().flatten().any(|(id, _)| *id == token_id)
+    }
+
+    fn free_automatic_slots(&self) -> usize {
+        let used = self.tokens.iter().flatten().filter(|(_, automatic)| *automatic).count();
+
+        match usize::try_from(self.max_automatic_associations) {
+            Ok(max) => max.saturating_sub(used),
+            Err(_) => usize::MAX,
+        }
+    }
+}
+
+#[cfg(feature = "mirror-rest")]
+#[derive(serde_derive::Deserialize)]
+struct MirrorTokenRelationship {
+    token_id: String,
+    automatic_association: bool,
+}
+
+impl TransferFlow {
+    /// Create a new `TransferFlow`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the account IDs of the nodes the transactions may be submitted to.
+    #[must_use]
+    pub fn get_node_account_ids(&self) -> Option<&[AccountId]> {
+        self.node_account_ids.as_deref()
+    }
+
+    /// Sets the account IDs of the nodes the transactions may be submitted to.
+    pub fn node_account_ids(
+        &mut self,
+        node_account_ids: impl IntoIterator<Item = AccountId>,
+    ) -> &mut Self {
+        self.node_account_ids = Some(node_account_ids.into_iter().collect());
+
+        self
+    }
+
+    /// Returns the transfer to execute.
+    #[must_use]
+    pub fn get_transfer_transaction(&self) -> &TransferTransaction {
+        &self.transfer
+    }
+
+    /// Sets the transfer to execute.
+    pub fn transfer_transaction(&mut self, transaction: TransferTransaction) -> &mut Self {
+        self.transfer = transaction;
+
+        self
+    }
+
+    /// Adds a key to sign the transfer with.
+    pub fn sign(&mut self, key: PrivateKey) -> &mut Self {
+        self.signers.push(AnySigner::PrivateKey(key));
+
+        self
+    }
+
+    /// Adds a signer to sign the transfer with.
+    pub fn sign_with<F: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static>(
+        &mut self,
+        public_key: PublicKey,
+        signer: F,
+    ) -> &mut Self {
+        self.signers.push(AnySigner::arbitrary(Box::new(public_key), signer));
+
+        self
+    }
+
+    /// Marks `account_id` as controlled by `key`, so it's associated with the tokens it receives.
+    pub fn associate_with_key(&mut self, account_id: AccountId, key: PrivateKey) -> &mut Self {
+        self.controlled_accounts.push((account_id, AnySigner::PrivateKey(key)));
+
+        self
+    }
+
+    /// Marks `account_id` as controlled by `signer`, so it's associated with the tokens it receives.
+    pub fn associate_with_signer<F: Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static>(
+        &mut self,
+        account_id: AccountId,
+        public_key: PublicKey,
+        signer: F,
+    ) -> &mut Self {
+        self.controlled_accounts
+            .push((account_id, AnySigner::arbitrary(Box::new(public_key), signer)));
+
+        self
+    }
+
+    // every account that receives a token, along with the tokens it receives.
+    fn receivers(&self) -> Vec<(AccountId, Vec<TokenId>)> {
+        let mut receivers: Vec<(AccountId, Vec<TokenId>)> = Vec::new();
+
+        for transfer in self.transfer.data().token_transfers() {
+            let accounts = transfer
+                .transfers
+                .iter()
+                .filter(|it| it.amount > 0)
+                .map(|it| it.account_id)
+                .chain(transfer.nft_transfers.iter().map(|it| it.receiver));
+
+            for account_id in accounts {
+                let index = match receivers.iter().position(|(id, _)| *id == account_id) {
+                    Some(index) => index,
+                    None => {
+                        receivers.push((account_id, Vec::new()));
+                        receivers.len() - 1
+                    }
+                };
+
+                let tokens = &mut receivers[index].1;
+
+                if !tokens.contains(&transfer.token_id) {
+                    tokens.push(transfer.token_id);
+                }
+            }
+        }
+
+        receivers
+    }
+
+    fn assess_receiver(
+        &self,
+        receiver: &ReceiverAssociations,
+        tokens: &[TokenId],
+        report: &mut TransferAssociationReport,
+    ) {
+        let account_id = receiver.account_id;
+
+        let controlled = self.controlled_accounts.iter().any(|(id, _)| *id == account_id);
+
+        if receiver.tokens.is_none() {
+            let associations =
+                tokens.iter().map(|&token_id| TokenAssociation { token_id, account_id });
+
+            // associating a token that's already associated is tolerated, so only uncontrolled receivers are unknown.
+            if controlled {
+                report.to_associate.extend(associations);
+            } else {
+                report.unknown.extend(associations);
+            }
+
+            return;
+        }
+
+        let missing = tokens.iter().copied().filter(|token_id| !receiver.is_associated(*token_id));
+
+        if controlled {
+            report
+                .to_associate
+                .extend(missing.map(|token_id| TokenAssociation { token_id, account_id }));
+
+            return;
+        }
+
+        let mut free_slots = receiver.free_automatic_slots();
+
+        for token_id in missing {
+            let association = TokenAssociation { token_id, account_id };
+
+            if free_slots > 0 {
+                free_slots -= 1;
+                report.automatic.push(association);
+            } else {
+                report.unassociated.push(association);
+            }
+        }
+    }
+
+    async fn receiver_associations(
+        &self,
+        client: &Client,
+        account_id: AccountId,
+        timeout: Option<Duration>,
+    ) -> crate::Result<ReceiverAssociations> {
+        let mut query = AccountInfoQuery::new();
+
+        query.account_id(account_id);
+
+        if let Some(node_account_ids) = &self.node_account_ids {
+            query.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        let info = query.execute_with_optional_timeout(client, timeout).await?;
+
+        #[cfg_attr(not(feature = "mirror-rest"), allow(unused_mut))]
+        let mut receiver = ReceiverAssociations::from_info(&info);
+
+        // the network no longer reports token relationships in account info, but the mirror node has all of them.
+        #[cfg(feature = "mirror-rest")]
+        {
+            let tokens: Vec<MirrorTokenRelationship> = mirror_rest::get_all(
+                client,
+                &format!("/api/v1/accounts/{account_id}/tokens?limit=100"),
+                "tokens",
+            )
+            .await?;
+
+            receiver.tokens = Some(
+                tokens
+                    .into_iter()
+                    .map(|it| Ok((it.token_id.parse()?, it.automatic_association)))
+                    .collect::<crate::Result<_>>()?,
+            );
+        }
+
+        Ok(receiver)
+    }
+
+    /// Checks whether every receiver of the transfer can accept its tokens, without submitting anything.
+    ///
+    /// # Errors
+    /// - Any error from querying a receiver's [`AccountInfo`], or its tokens from the mirror node.
+    pub async fn check(&self, client: &Client) -> crate::Result<TransferAssociationReport> {
+        self.check_with_optional_timeout(client, None).await
+    }
+
+    async fn check_with_optional_timeout(
+        &self,
+        client: &Client,
+        timeout: Option<Duration>,
+    ) -> crate::Result<TransferAssociationReport> {
+        let mut report = TransferAssociationReport::default();
+
+        for (account_id, tokens) in self.receivers() {
+            let receiver = self.receiver_associations(client, account_id, timeout).await?;
+
+            self.assess_receiver(&receiver, &tokens, &mut report);
+        }
+
+        Ok(report)
+    }
+
+    /// Associates the receivers with their tokens, then executes the transfer.
+    ///
+    /// # Errors
+    /// - [`Error::TokenNotAssociated`] if a receiver can't accept its tokens, nothing is submitted in this case.
+    /// - Any error from looking up a receiver's associations, associating a receiver, or executing the transfer.
+    pub async fn execute(&self, client: &Client) -> crate::Result<TransactionResponse> {
+        self.execute_with_optional_timeout(client, None).await
+    }
+
+    /// Associates the receivers with their tokens, then executes the transfer.
+    ///
+    /// # Errors
+    /// - [`Error::TokenNotAssociated`] if a receiver can't accept its tokens, nothing is submitted in this case.
+    /// - Any error from looking up a receiver's associations, associating a receiver, or executing the transfer.
+    pub async fn execute_with_timeout(
+        &self,
+        client: &Client,
+        timeout_per_transaction: Duration,
+    ) -> crate::Result<TransactionResponse> {
+        self.execute_with_optional_timeout(client, Some(timeout_per_transaction)).await
+    }
+
+    async fn execute_with_optional_timeout(
+        &self,
+        client: &Client,
+        timeout: Option<Duration>,
+    ) -> crate::Result<TransactionResponse> {
+        let report = self.check_with_optional_timeout(client, timeout).await?;
+
+        if !report.unassociated.is_empty() {
+            return Err(Error::TokenNotAssociated { associations: report.unassociated });
+        }
+
+        for (account_id, signer) in &self.controlled_accounts {
+            let token_ids: Vec<_> = report
+                .to_associate
+                .iter()
+                .filter(|it| it.account_id == *account_id)
+                .map(|it| it.token_id)
+                .collect();
+
+            if token_ids.is_empty() {
+                continue;
+            }
+
+            match self.associate(client, *account_id, signer, &token_ids, timeout).await {
+                // the associations we looked up were out of date, so associate the tokens one at a time.
+                Err(Error::ReceiptStatus {
+                    status: Status::TokenAlreadyAssociatedToAccount,
+                    ..
+                }) => {
+                    for token_id in token_ids {
+                        match self
+                            .associate(client, *account_id, signer, &[token_id], timeout)
+                            .await
+                        {
+                            Ok(())
+                            | Err(Error::ReceiptStatus {
+                                status: Status::TokenAlreadyAssociatedToAccount,
+                                ..
+                            }) => {}
+                            Err(error) => return Err(error),
+                        }
+                    }
+                }
+                result => result?,
+            }
+        }
+
+        let mut transfer = self.transfer.clone();
+
+        if let Some(node_account_ids) = &self.node_account_ids {
+            transfer.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        for signer in &self.signers {
+            transfer.sign_signer(signer.clone());
+        }
+
+        transfer.execute_with_optional_timeout(client, timeout).await
+    }
+
+    async fn associate(
+        &self,
+        client: &Client,
+        account_id: AccountId,
+        signer: &AnySigner,
+        token_ids: &[TokenId],
+        timeout: Option<Duration>,
+    ) -> crate::Result<()> {
+        let mut transaction = TokenAssociateTransaction::new();
+
+        transaction.account_id(account_id).token_ids(token_ids.iter().copied());
+
+        if let Some(node_account_ids) = &self.node_account_ids {
+            transaction.node_account_ids(node_account_ids.iter().copied());
+        }
+
+        transaction
+            .sign_signer(signer.clone())
+            .execute_with_optional_timeout(client, timeout)
+            .await?
+            .get_receipt_query()
+            .execute_with_optional_timeout(client, timeout)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ReceiverAssociations,
+        TransferAssociationReport,
+    };
+    use crate::{
+        AccountId,
+        PrivateKey,
+        TokenAssociation,
+        TokenId,
+        TransferFlow,
+        TransferTransaction,
+    };
+
+    const TOKEN_A: TokenId = TokenId::new(0, 0, 1000);
+    const TOKEN_B: TokenId = TokenId::new(0, 0, 2000);
+    const TOKEN_C: TokenId = TokenId::new(0, 0, 3000);
+    const TOKEN_D: TokenId = TokenId::new(0, 0, 4000);
+    const RECEIVER: AccountId = AccountId::new(0, 0, 5001);
+
+    fn assess(
+        flow: &TransferFlow,
+        max_automatic_associations: i32,
+        associated: &[(TokenId, bool)],
+        tokens: &[TokenId],
+    ) -> TransferAssociationReport {
+        let receiver = ReceiverAssociations {
+            account_id: RECEIVER,
+            max_automatic_associations,
+            tokens: Some(associated.to_vec()),
+        };
+
+        let mut report = TransferAssociationReport::default();
+
+        flow.assess_receiver(&receiver, tokens, &mut report);
+
+        report
+    }
+
+    fn associations(token_ids: &[TokenId]) -> Vec<TokenAssociation> {
+        token_ids
+            .iter()
+            .map(|&token_id| TokenAssociation { token_id, account_id: RECEIVER })
+            .collect()
+    }
+
+    #[test]
+    fn receivers() {
+        let token_a = TokenId::new(0, 0, 1000);
+        let token_b = TokenId::new(0, 0, 2000);
+        let sender = AccountId::new(0, 0, 3);
+        let receiver = AccountId::new(0, 0, 5001);
+
+        let mut transfer = TransferTransaction::new();
+
+        transfer
+            .token_transfer(token_a, sender, -10)
+            .token_transfer(token_a, receiver, 10)
+            .nft_transfer(token_b.nft(1), sender, receiver);
+
+        let mut flow = TransferFlow::new();
+
+        flow.transfer_transaction(transfer);
+
+        assert_eq!(flow.receivers(), [(receiver, Vec::from([token_a, token_b]))]);
+    }
+
+    #[test]
+    fn assess_controlled_receiver() {
+        let mut flow = TransferFlow::new();
+
+        flow.associate_with_key(RECEIVER, PrivateKey::generate_ed25519());
+
+        let report = assess(&flow, 0, &[(TOKEN_A, false)], &[TOKEN_A, TOKEN_B]);
+
+        assert_eq!(report.to_associate, associations(&[TOKEN_B]));
+        assert!(report.automatic.is_empty());
+        assert!(report.unassociated.is_empty());
+    }
+
+    #[test]
+    fn assess_receiver_counts_only_automatic_associations_as_used() {
+        let flow = TransferFlow::new();
+
+        // 2 of the 3 slots are used by automatic associations, the manual association of `TOKEN_B` doesn't use one.
+        let report = assess(
+            &flow,
+            3,
+            &[(TOKEN_A, true), (TOKEN_B, false), (TOKEN_C, true)],
+            &[TOKEN_A, TOKEN_D, TokenId::new(0, 0, 5000)],
+        );
+
+        assert!(report.to_associate.is_empty());
+        assert_eq!(report.automatic, associations(&[TOKEN_D]));
+        assert_eq!(report.unassociated, associations(&[TokenId::new(0, 0, 5000)]));
+    }
+
+    #[test]
+    fn assess_receiver_with_unlimited_slots() {
+        let flow = TransferFlow::new();
+
+        let report = assess(&flow, -1, &[(TOKEN_A, true)], &[TOKEN_B, TOKEN_C]);
+
+        assert_eq!(report.automatic, associations(&[TOKEN_B, TOKEN_C]));
+        assert!(report.unassociated.is_empty());
+    }
+
+    #[test]
+    fn assess_receiver_without_slots() {
+        let flow = TransferFlow::new();
+
+        let report = assess(&flow, 0, &[(TOKEN_A, false)], &[TOKEN_A, TOKEN_B]);
+
+        assert!(report.automatic.is_empty());
+        assert_eq!(report.unassociated, associations(&[TOKEN_B]));
+    }
+
+    #[test]
+    fn assess_receiver_with_more_automatic_associations_than_slots() {
+        let flow = TransferFlow::new();
+
+        // the limit was lowered after the associations were made.
+        let report = assess(&flow, 1, &[(TOKEN_A, true), (TOKEN_B, true)], &[TOKEN_C]);
+
+        assert_eq!(report.unassociated, associations(&[TOKEN_C]));
+    }
+
+    #[test]
+    fn assess_receiver_with_unknown_associations() {
+        let flow = TransferFlow::new();
+
+        let receiver = ReceiverAssociations {
+            account_id: RECEIVER,
+            max_automatic_associations: 0,
+            tokens: None,
+        };
+
+        let mut report = TransferAssociationReport::default();
+
+        flow.assess_receiver(&receiver, &[TOKEN_A, TOKEN_B], &mut report);
+
+        assert_eq!(report.unknown, associations(&[TOKEN_A, TOKEN_B]));
+        assert!(report.unassociated.is_empty());
+    }
+
+    #[test]
+    fn assess_controlled_receiver_with_unknown_associations() {
+        let mut flow = TransferFlow::new();
+
+        flow.associate_with_key(RECEIVER, PrivateKey::generate_ed25519());
+
+        let receiver = ReceiverAssociations {
+            account_id: RECEIVER,
+            max_automatic_associations: 0,
+            tokens: None,
+        };
+
+        let mut report = TransferAssociationReport::default();
+
+        flow.assess_receiver(&receiver, &[TOKEN_A], &mut report);
+
+        assert_eq!(report.to_associate, associations(&[TOKEN_A]));
+        assert!(report.unknown.is_empty());
+    }
+}
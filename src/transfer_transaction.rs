@@ -293,6 +293,16 @@ impl TransferTransaction {
     }
 }
 
+impl TransferTransactionData {
+    pub(crate) fn transfers(&self) -> &[Transfer] {
+        &self.transfers
+    }
+
+    pub(crate) fn token_transfers(&self) -> &[TokenTransfer] {
+        &self.token_transfers
+    }
+}
+
 impl TransactionExecute for TransferTransactionData {
     // noinspection DuplicatedCode
     fn execute(